@@ -161,6 +161,14 @@ pub struct SimpleObjectField {
     #[darling(default)]
     pub override_from: Option<String>,
     #[darling(default)]
+    pub override_label: Option<String>,
+    #[darling(default)]
+    pub authenticated: bool,
+    #[darling(default, multiple)]
+    pub requires_scopes: Vec<String>,
+    #[darling(default, multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
+    #[darling(default)]
     pub guard: Option<SpannedValue<String>>,
     #[darling(default)]
     pub visible: Option<Visible>,
@@ -224,6 +232,18 @@ pub struct SimpleObject {
     pub input_name: Option<String>,
     #[darling(default)]
     pub guard: Option<SpannedValue<String>>,
+    #[darling(default)]
+    pub node: bool,
+    #[darling(default)]
+    pub interface_object: bool,
+    #[darling(default)]
+    pub unresolvable: Option<String>,
+    #[darling(default)]
+    pub authenticated: bool,
+    #[darling(default, multiple)]
+    pub requires_scopes: Vec<String>,
+    #[darling(default, multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
 }
 
 #[derive(FromMeta, Default)]
@@ -241,6 +261,8 @@ pub struct Argument {
     pub inaccessible: bool,
     #[darling(multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
     pub secret: bool,
 }
 
@@ -265,6 +287,14 @@ pub struct Object {
     pub concretes: Vec<ConcreteType>,
     #[darling(default)]
     pub guard: Option<SpannedValue<String>>,
+    pub node: bool,
+    pub interface_object: bool,
+    pub unresolvable: Option<String>,
+    pub authenticated: bool,
+    #[darling(multiple)]
+    pub requires_scopes: Vec<String>,
+    #[darling(multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
 }
 
 pub enum ComplexityType {
@@ -306,12 +336,19 @@ pub struct ObjectField {
     #[darling(multiple, rename = "tag")]
     pub tags: Vec<String>,
     pub override_from: Option<String>,
+    pub override_label: Option<String>,
+    pub authenticated: bool,
+    #[darling(multiple)]
+    pub requires_scopes: Vec<String>,
+    #[darling(multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
     #[darling(default, multiple)]
     pub derived: Vec<DerivedField>,
     pub flatten: bool,
+    pub batch: bool,
 }
 
 #[derive(FromMeta, Default, Clone)]
@@ -368,6 +405,8 @@ pub struct EnumItem {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default, multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
 }
 
 #[derive(FromDeriveInput)]
@@ -438,6 +477,8 @@ pub struct InputObjectField {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default, multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
     #[darling(default)]
     pub secret: bool,
 }
@@ -574,6 +615,12 @@ pub struct InterfaceField {
     pub shareable: bool,
     #[darling(default)]
     pub override_from: Option<String>,
+    #[darling(default)]
+    pub override_label: Option<String>,
+    #[darling(default)]
+    pub authenticated: bool,
+    #[darling(default, multiple)]
+    pub requires_scopes: Vec<String>,
 }
 
 #[derive(FromVariant)]
@@ -610,6 +657,10 @@ pub struct Interface {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default)]
+    pub authenticated: bool,
+    #[darling(default, multiple)]
+    pub requires_scopes: Vec<String>,
 }
 
 #[derive(FromMeta, Default)]
@@ -657,6 +708,38 @@ pub struct SubscriptionFieldArgument {
     pub secret: bool,
 }
 
+#[derive(Clone, Copy)]
+pub enum SubscriptionBufferPolicy {
+    Unbounded,
+    DropOldest(usize),
+    CoalesceLatest,
+    DisconnectOnOverflow(usize),
+}
+
+impl FromMeta for SubscriptionBufferPolicy {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        match items {
+            [NestedMeta::Meta(Meta::Path(path))] if path.is_ident("unbounded") => {
+                Ok(SubscriptionBufferPolicy::Unbounded)
+            }
+            [NestedMeta::Meta(Meta::Path(path))] if path.is_ident("coalesce_latest") => {
+                Ok(SubscriptionBufferPolicy::CoalesceLatest)
+            }
+            [NestedMeta::Meta(Meta::NameValue(nv))] if nv.path.is_ident("drop_oldest") => {
+                let size = usize::from_value(&nv.lit)?;
+                Ok(SubscriptionBufferPolicy::DropOldest(size))
+            }
+            [NestedMeta::Meta(Meta::NameValue(nv))]
+                if nv.path.is_ident("disconnect_on_overflow") =>
+            {
+                let size = usize::from_value(&nv.lit)?;
+                Ok(SubscriptionBufferPolicy::DisconnectOnOverflow(size))
+            }
+            _ => Err(darling::Error::custom("Invalid buffer policy")),
+        }
+    }
+}
+
 #[derive(FromMeta, Default)]
 #[darling(default)]
 pub struct SubscriptionField {
@@ -666,6 +749,8 @@ pub struct SubscriptionField {
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
+    pub buffer_policy: Option<SubscriptionBufferPolicy>,
+    pub replay: Option<String>,
 }
 
 #[derive(FromField)]
@@ -878,12 +963,19 @@ pub struct ComplexObjectField {
     #[darling(multiple, rename = "tag")]
     pub tags: Vec<String>,
     pub override_from: Option<String>,
+    pub override_label: Option<String>,
+    pub authenticated: bool,
+    #[darling(multiple)]
+    pub requires_scopes: Vec<String>,
+    #[darling(multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
     #[darling(multiple)]
     pub derived: Vec<DerivedField>,
     pub flatten: bool,
+    pub batch: bool,
 }
 
 #[derive(FromMeta, Default)]
@@ -901,15 +993,59 @@ pub struct Directive {
 }
 
 #[derive(Debug, Copy, Clone, FromMeta)]
-#[darling(rename_all = "lowercase")]
+#[darling(rename_all = "snake_case")]
 pub enum DirectiveLocation {
     Field,
+    FragmentSpread,
+    InlineFragment,
+    Query,
+    Mutation,
+    VariableDefinition,
 }
 
 impl Display for DirectiveLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             DirectiveLocation::Field => write!(f, "FIELD"),
+            DirectiveLocation::FragmentSpread => write!(f, "FRAGMENT_SPREAD"),
+            DirectiveLocation::InlineFragment => write!(f, "INLINE_FRAGMENT"),
+            DirectiveLocation::Query => write!(f, "QUERY"),
+            DirectiveLocation::Mutation => write!(f, "MUTATION"),
+            DirectiveLocation::VariableDefinition => write!(f, "VARIABLE_DEFINITION"),
+        }
+    }
+}
+
+#[derive(FromMeta, Default)]
+#[darling(default)]
+pub struct TypeDirective {
+    pub internal: bool,
+    pub name: Option<String>,
+    pub visible: Option<Visible>,
+    pub repeatable: bool,
+    pub rename_args: Option<RenameRule>,
+    #[darling(multiple, rename = "location")]
+    pub locations: Vec<TypeDirectiveLocation>,
+}
+
+#[derive(Debug, Copy, Clone, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub enum TypeDirectiveLocation {
+    Object,
+    FieldDefinition,
+    ArgumentDefinition,
+    EnumValue,
+    InputFieldDefinition,
+}
+
+impl Display for TypeDirectiveLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeDirectiveLocation::Object => write!(f, "OBJECT"),
+            TypeDirectiveLocation::FieldDefinition => write!(f, "FIELD_DEFINITION"),
+            TypeDirectiveLocation::ArgumentDefinition => write!(f, "ARGUMENT_DEFINITION"),
+            TypeDirectiveLocation::EnumValue => write!(f, "ENUM_VALUE"),
+            TypeDirectiveLocation::InputFieldDefinition => write!(f, "INPUT_FIELD_DEFINITION"),
         }
     }
 }
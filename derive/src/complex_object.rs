@@ -12,7 +12,8 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
+        batch_element_type, extract_batch_input_args, extract_input_args, gen_deprecation,
+        generate_default, generate_directive_invocations, generate_guards, get_cfg_attrs,
         get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
         parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
     },
@@ -28,6 +29,7 @@ pub fn generate(
     let where_clause = &item_impl.generics.where_clause;
 
     let mut resolvers = Vec::new();
+    let mut has_batch = false;
     let mut schema_fields = Vec::new();
 
     // Computation of the derivated fields
@@ -177,6 +179,16 @@ pub fn generate(
                 Some(from) => quote! { ::std::option::Option::Some(#from) },
                 None => quote! { ::std::option::Option::None },
             };
+            let override_label = match &method_args.override_label {
+                Some(label) => quote! { ::std::option::Option::Some(#label) },
+                None => quote! { ::std::option::Option::None },
+            };
+            let authenticated = method_args.authenticated;
+            let requires_scopes = method_args
+                .requires_scopes
+                .iter()
+                .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+                .collect::<Vec<_>>();
             let inaccessible = method_args.inaccessible;
             let tags = method_args
                 .tags
@@ -206,7 +218,11 @@ pub fn generate(
                 }
             };
 
-            let args = extract_input_args::<args::Argument>(&crate_name, method)?;
+            let args = if method_args.batch {
+                extract_batch_input_args::<args::Argument>(&crate_name, method)?
+            } else {
+                extract_input_args::<args::Argument>(&crate_name, method)?
+            };
             let mut schema_args = Vec::new();
             let mut use_params = Vec::new();
             let mut get_params = Vec::new();
@@ -225,6 +241,7 @@ pub fn generate(
                     inaccessible,
                     tags,
                     secret,
+                    directives,
                     ..
                 },
             ) in &args
@@ -255,6 +272,8 @@ pub fn generate(
                     .iter()
                     .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
                     .collect::<Vec<_>>();
+                let arg_directive_invocations =
+                    generate_directive_invocations(&crate_name, directives)?;
                 schema_args.push(quote! {
                         args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
                             name: ::std::string::ToString::to_string(#name),
@@ -265,6 +284,7 @@ pub fn generate(
                             inaccessible: #inaccessible,
                             tags: ::std::vec![ #(#tags),* ],
                             is_secret: #secret,
+                            directive_invocations: #arg_directive_invocations,
                         });
                     });
 
@@ -318,8 +338,14 @@ pub fn generate(
                     .into())
                 }
             };
-            let schema_ty = ty.value_type();
+            let schema_ty = if method_args.batch {
+                batch_element_type(&ty.value_type())?
+            } else {
+                ty.value_type()
+            };
             let visible = visible_fn(&method_args.visible);
+            let directive_invocations =
+                generate_directive_invocations(&crate_name, &method_args.directives)?;
 
             let complexity = if let Some(complexity) = &method_args.complexity {
                 match complexity {
@@ -392,8 +418,12 @@ pub fn generate(
                     inaccessible: #inaccessible,
                     tags: ::std::vec![ #(#tags),* ],
                     override_from: #override_from,
+                    override_label: #override_label,
+                    authenticated: #authenticated,
+                    requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                     visible: #visible,
                     compute_complexity: #complexity,
+                    directive_invocations: #directive_invocations,
                 }));
             });
 
@@ -414,10 +444,20 @@ pub fn generate(
                         .expect("invalid result type");
             }
 
-            let resolve_obj = quote! {
-                {
-                    let res = self.#field_ident(ctx, #(#use_params),*).await;
-                    res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+            let resolve_obj = if method_args.batch {
+                has_batch = true;
+                quote! {
+                    ctx.resolve_batch(self, |parents| async move {
+                        let res = Self::#field_ident(&parents, ctx, #(#use_params),*).await;
+                        res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                    }).await
+                }
+            } else {
+                quote! {
+                    {
+                        let res = self.#field_ident(ctx, #(#use_params),*).await;
+                        res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                    }
                 }
             };
 
@@ -447,6 +487,16 @@ pub fn generate(
         }
     }
 
+    let batch_parent = if has_batch {
+        Some(quote! {
+            fn batch_parent(&self) -> ::std::option::Option<&(dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync)> {
+                ::std::option::Option::Some(self)
+            }
+        })
+    } else {
+        None
+    };
+
     let expanded = quote! {
         #item_impl
 
@@ -463,6 +513,8 @@ pub fn generate(
                 #(#resolvers)*
                 ::std::result::Result::Ok(::std::option::Option::None)
             }
+
+            #batch_parent
         }
     };
 
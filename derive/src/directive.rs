@@ -97,6 +97,7 @@ pub fn generate(
                 inaccessible: false,
                 tags: ::std::default::Default::default(),
                 is_secret: #secret,
+                directive_invocations: ::std::vec::Vec::new(),
             });
         });
 
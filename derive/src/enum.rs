@@ -5,7 +5,10 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{gen_deprecation, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{
+        gen_deprecation, generate_directive_invocations, get_crate_name, get_rustdoc, visible_fn,
+        GeneratorResult,
+    },
 };
 
 pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
@@ -65,6 +68,8 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
             .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
             .collect::<Vec<_>>();
         let item_deprecation = gen_deprecation(&variant.deprecation, &crate_name);
+        let directive_invocations =
+            generate_directive_invocations(&crate_name, &variant.directives)?;
         let item_desc = get_rustdoc(&variant.attrs)?
             .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
@@ -86,6 +91,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                 visible: #visible,
                 inaccessible: #inaccessible,
                 tags: ::std::vec![ #(#tags),* ],
+                directive_invocations: #directive_invocations,
             });
         });
     }
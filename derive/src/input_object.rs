@@ -5,7 +5,10 @@ use syn::{ext::IdentExt, Error, Expr};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{
+        generate_default, generate_directive_invocations, get_crate_name, get_rustdoc, visible_fn,
+        GeneratorResult,
+    },
 };
 
 pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream> {
@@ -161,6 +164,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
             })
             .unwrap_or_else(|| quote!(::std::option::Option::None));
         let secret = field.secret;
+        let directive_invocations = generate_directive_invocations(&crate_name, &field.directives)?;
 
         if let Some(default) = default {
             get_fields.push(quote! {
@@ -209,6 +213,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                 inaccessible: #inaccessible,
                 tags: ::std::vec![ #(#tags),* ],
                 is_secret: #secret,
+                directive_invocations: #directive_invocations,
             });
         })
     }
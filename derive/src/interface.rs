@@ -37,6 +37,12 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         .iter()
         .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
         .collect::<Vec<_>>();
+    let authenticated = interface_args.authenticated;
+    let requires_scopes = interface_args
+        .requires_scopes
+        .iter()
+        .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+        .collect::<Vec<_>>();
     let gql_typename = if !interface_args.name_type {
         let name = interface_args
             .name
@@ -152,6 +158,9 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         inaccessible,
         tags,
         override_from,
+        override_label,
+        authenticated,
+        requires_scopes,
     } in &interface_args.fields
     {
         let (name, method_name) = if let Some(method) = method {
@@ -186,6 +195,14 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             Some(from) => quote! { ::std::option::Option::Some(#from) },
             None => quote! { ::std::option::Option::None },
         };
+        let override_label = match &override_label {
+            Some(label) => quote! { ::std::option::Option::Some(#label) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let requires_scopes = requires_scopes
+            .iter()
+            .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+            .collect::<Vec<_>>();
 
         decl_params.push(quote! { ctx: &'ctx #crate_name::Context<'ctx> });
         use_params.push(quote! { ctx });
@@ -252,6 +269,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
                         is_secret: #secret,
+                        directive_invocations: ::std::vec::Vec::new(),
                     });
                 });
         }
@@ -311,8 +329,12 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 inaccessible: #inaccessible,
                 tags: ::std::vec![ #(#tags),* ],
                 override_from: #override_from,
+                override_label: #override_label,
+                authenticated: #authenticated,
+                requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                 visible: #visible,
                 compute_complexity: ::std::option::Option::None,
+                directive_invocations: ::std::vec::Vec::new(),
             });
         });
 
@@ -395,6 +417,8 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         },
                         extends: #extends,
                         keys: ::std::option::Option::None,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
@@ -20,6 +20,7 @@ mod output_type;
 mod scalar;
 mod simple_object;
 mod subscription;
+mod type_directive;
 mod union;
 mod utils;
 mod validators;
@@ -218,6 +219,21 @@ pub fn Directive(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn TypeDirective(args: TokenStream, input: TokenStream) -> TokenStream {
+    let directive_args =
+        match args::TypeDirective::from_list(&parse_macro_input!(args as AttributeArgs)) {
+            Ok(directive_args) => directive_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    let item_fn = parse_macro_input!(input as ItemFn);
+    match type_directive::generate(&directive_args, &item_fn) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(OneofObject, attributes(graphql))]
 pub fn derive_oneof_object(input: TokenStream) -> TokenStream {
     let object_args =
@@ -86,6 +86,15 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
             async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) ->  #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
                #create_merged_obj.find_entity(ctx, params).await
             }
+
+            async fn find_entities(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                type_name: &::std::primitive::str,
+                representations: &[(::std::primitive::usize, &#crate_name::Value)],
+            ) -> ::std::option::Option<::std::vec::Vec<#crate_name::ServerResult<::std::option::Option<#crate_name::Value>>>> {
+               #create_merged_obj.find_entities(ctx, type_name, representations).await
+            }
         }
 
         #[allow(clippy::all, clippy::pedantic)]
@@ -119,9 +128,14 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
                         keys: ::std::option::Option::None,
+                        resolvable: true,
+                        interface_object: false,
+                        authenticated: false,
+                        requires_scopes: ::std::vec::Vec::new(),
                         visible: #visible,
                         is_subscription: false,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                        directive_invocations: ::std::vec::Vec::new(),
                     }
                 })
             }
@@ -78,12 +78,17 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
                         cache_control: ::std::default::Default::default(),
                         extends: #extends,
                         keys: ::std::option::Option::None,
+                        resolvable: true,
+                        interface_object: false,
+                        authenticated: false,
+                        requires_scopes: ::std::vec::Vec::new(),
                         visible: #visible,
                         shareable: false,
                         inaccessible: false,
                         tags: ::std::default::Default::default(),
                         is_subscription: true,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                        directive_invocations: ::std::vec::Vec::new(),
                     }
                 })
             }
@@ -2,7 +2,7 @@ use std::{iter::FromIterator, str::FromStr};
 
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     ext::IdentExt, punctuated::Punctuated, Block, Error, FnArg, ImplItem, ItemImpl, Pat,
     ReturnType, Token, Type, TypeReference,
@@ -12,8 +12,9 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
+        batch_element_type, extract_batch_input_args, extract_input_args, gen_deprecation,
+        generate_default, generate_directive_invocations, generate_guards, generic_argument,
+        get_cfg_attrs, get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
         parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
     },
 };
@@ -33,6 +34,22 @@ pub fn generate(
         .iter()
         .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
         .collect::<Vec<_>>();
+    let interface_object = object_args.interface_object;
+    let authenticated = object_args.authenticated;
+    let requires_scopes = object_args
+        .requires_scopes
+        .iter()
+        .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+        .collect::<Vec<_>>();
+    let resolvable = object_args.unresolvable.is_none();
+    let directive_invocations =
+        generate_directive_invocations(&crate_name, &object_args.directives)?;
+    let keys = match &object_args.unresolvable {
+        Some(keys) => {
+            quote! { ::std::option::Option::Some(::std::vec![::std::string::ToString::to_string(#keys)]) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
     let gql_typename = if !object_args.name_type {
         object_args
             .name
@@ -57,8 +74,11 @@ pub fn generate(
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
     let mut find_entities = Vec::new();
+    let mut batch_entities = Vec::new();
     let mut add_keys = Vec::new();
     let mut create_entity_types = Vec::new();
+    let mut has_batch = false;
+    let mut has_node_id = false;
 
     // Computation of the derivated fields
     let mut derived_impls = vec![];
@@ -170,12 +190,24 @@ pub fn generate(
                     }
                 };
 
-                let entity_type = ty.value_type();
+                let entity_type = if method_args.batch {
+                    generic_argument(&ty.value_type(), "Vec")
+                        .and_then(|ty| generic_argument(&ty, "Option"))
+                        .ok_or_else(|| {
+                            Error::new_spanned(
+                                &method.sig.output,
+                                "A batch entity resolver must return `Vec<Option<T>>` or `Result<Vec<Option<T>>>`.",
+                            )
+                        })?
+                } else {
+                    ty.value_type()
+                };
                 let mut key_pat = Vec::new();
                 let mut key_getter = Vec::new();
                 let mut use_keys = Vec::new();
                 let mut get_federation_key = Vec::new();
                 let mut requires_getter = Vec::new();
+                let mut batch_values = Vec::new();
                 let all_key = args.iter().all(|(_, _, arg)| !arg.key);
 
                 if args.is_empty() {
@@ -193,6 +225,16 @@ pub fn generate(
                             .rename_args
                             .rename(ident.ident.unraw().to_string(), RenameTarget::Argument)
                     });
+                    let ty = if method_args.batch {
+                        generic_argument(ty, "Vec").ok_or_else(|| {
+                            Error::new_spanned(
+                                ty,
+                                "The arguments of a batch entity resolver must be `Vec<T>`.",
+                            )
+                        })?
+                    } else {
+                        ty.clone()
+                    };
 
                     if is_key {
                         get_federation_key.push(quote! {
@@ -212,6 +254,17 @@ pub fn generate(
                                 value
                             })
                         });
+                    } else if method_args.batch {
+                        // requires
+                        requires_getter.push(quote! {
+                            let #ident: #ty = match #crate_name::InputType::parse(params.get(#name).cloned()) {
+                                ::std::result::Result::Ok(value) => value,
+                                ::std::result::Result::Err(err) => {
+                                    results[pos] = ::std::option::Option::Some(::std::result::Result::Err(err.into_server_error(ctx.item.pos)));
+                                    continue;
+                                }
+                            };
+                        });
                     } else {
                         // requires
                         requires_getter.push(quote! {
@@ -219,6 +272,7 @@ pub fn generate(
                                 map_err(|err| err.into_server_error(ctx.item.pos))?;
                         });
                     }
+                    batch_values.push(format_ident!("__batch_{}", ident.ident.unraw()));
                     use_keys.push(ident);
                 }
 
@@ -247,6 +301,79 @@ pub fn generate(
                         syn::parse2::<ReturnType>(quote! { -> #crate_name::Result<#inner_ty> })
                             .expect("invalid result type");
                 }
+
+                if method_args.batch {
+                    batch_entities.push((
+                        args.len(),
+                        quote! {
+                            #(#cfg_attrs)*
+                            if type_name == <#entity_type as #crate_name::OutputType>::type_name() {
+                                found = true;
+                                #(let mut #batch_values = ::std::vec::Vec::new();)*
+                                let mut positions = ::std::vec::Vec::new();
+                                for (pos, (_, params)) in representations.iter().enumerate() {
+                                    if results[pos].is_some() {
+                                        continue;
+                                    }
+                                    let params = match params {
+                                        #crate_name::Value::Object(params) => params,
+                                        _ => continue,
+                                    };
+                                    if let (#(#key_pat),*) = (#(#key_getter),*) {
+                                        #(#requires_getter)*
+                                        #(#batch_values.push(#use_keys);)*
+                                        positions.push(pos);
+                                    }
+                                }
+
+                                if !positions.is_empty() {
+                                    let res = self.#field_ident(ctx, #(#batch_values),*)
+                                        .await.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err)
+                                        .into_server_error(ctx.item.pos));
+                                    match res {
+                                        ::std::result::Result::Ok(objs) if objs.len() == positions.len() => {
+                                            let values = #crate_name::futures_util::future::join_all(
+                                                objs.into_iter().zip(&positions).map(|(obj, pos)| {
+                                                    let idx = representations[*pos].0;
+                                                    async move {
+                                                        match obj {
+                                                            ::std::option::Option::Some(obj) => {
+                                                                let ctx = ctx.with_index(idx);
+                                                                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                                                                #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some)
+                                                            }
+                                                            ::std::option::Option::None => ::std::result::Result::Ok(::std::option::Option::None),
+                                                        }
+                                                    }
+                                                }),
+                                            )
+                                            .await;
+                                            for (pos, value) in positions.iter().zip(values) {
+                                                results[*pos] = ::std::option::Option::Some(value);
+                                            }
+                                        }
+                                        ::std::result::Result::Ok(_) => {
+                                            for pos in &positions {
+                                                results[*pos] = ::std::option::Option::Some(::std::result::Result::Err(#crate_name::ServerError::new(
+                                                    "The batch entity resolver must return a value for each representation.",
+                                                    ::std::option::Option::Some(ctx.item.pos),
+                                                )));
+                                            }
+                                        }
+                                        ::std::result::Result::Err(err) => {
+                                            for pos in &positions {
+                                                results[*pos] = ::std::option::Option::Some(::std::result::Result::Err(::std::clone::Clone::clone(&err)));
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                    ));
+                    remove_graphql_attrs(&mut method.attrs);
+                    continue;
+                }
+
                 let do_find = quote! {
                     self.#field_ident(ctx, #(#use_keys),*)
                         .await.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err)
@@ -333,6 +460,16 @@ pub fn generate(
                     Some(from) => quote! { ::std::option::Option::Some(#from) },
                     None => quote! { ::std::option::Option::None },
                 };
+                let override_label = match &method_args.override_label {
+                    Some(label) => quote! { ::std::option::Option::Some(#label) },
+                    None => quote! { ::std::option::Option::None },
+                };
+                let authenticated = method_args.authenticated;
+                let requires_scopes = method_args
+                    .requires_scopes
+                    .iter()
+                    .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+                    .collect::<Vec<_>>();
                 let requires = match &method_args.requires {
                     Some(requires) => quote! { ::std::option::Option::Some(#requires) },
                     None => quote! { ::std::option::Option::None },
@@ -356,7 +493,19 @@ pub fn generate(
                     }
                 };
 
-                let args = extract_input_args::<args::Argument>(&crate_name, method)?;
+                if method_args.batch && !object_args.concretes.is_empty() {
+                    return Err(Error::new_spanned(
+                        &method.sig,
+                        "Batch resolvers are not supported by generic objects.",
+                    )
+                    .into());
+                }
+
+                let args = if method_args.batch {
+                    extract_batch_input_args::<args::Argument>(&crate_name, method)?
+                } else {
+                    extract_input_args::<args::Argument>(&crate_name, method)?
+                };
                 let mut schema_args = Vec::new();
                 let mut use_params = Vec::new();
                 let mut get_params = Vec::new();
@@ -375,6 +524,7 @@ pub fn generate(
                         secret,
                         inaccessible,
                         tags,
+                        directives,
                         ..
                     },
                 ) in &args
@@ -405,6 +555,8 @@ pub fn generate(
                         .iter()
                         .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
                         .collect::<Vec<_>>();
+                    let arg_directive_invocations =
+                        generate_directive_invocations(&crate_name, directives)?;
 
                     schema_args.push(quote! {
                             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
@@ -416,6 +568,7 @@ pub fn generate(
                                 inaccessible: #inaccessible,
                                 tags: ::std::vec![ #(#tags),* ],
                                 is_secret: #secret,
+                                directive_invocations: #arg_directive_invocations,
                             });
                         });
 
@@ -468,8 +621,21 @@ pub fn generate(
                         .into())
                     }
                 };
-                let schema_ty = ty.value_type();
+                let schema_ty = if method_args.batch {
+                    batch_element_type(&ty.value_type())?
+                } else {
+                    ty.value_type()
+                };
+                let is_node_id = object_args.node && field_name == "id";
+                has_node_id |= is_node_id;
+                let schema_ty = if is_node_id {
+                    quote! { ::std::string::ToString::to_string("ID!") }
+                } else {
+                    quote! { <#schema_ty as #crate_name::OutputType>::create_type_info(registry) }
+                };
                 let visible = visible_fn(&method_args.visible);
+                let directive_invocations =
+                    generate_directive_invocations(&crate_name, &method_args.directives)?;
 
                 let complexity = if let Some(complexity) = &method_args.complexity {
                     match complexity {
@@ -532,7 +698,7 @@ pub fn generate(
                             #(#schema_args)*
                             args
                         },
-                        ty: <#schema_ty as #crate_name::OutputType>::create_type_info(registry),
+                        ty: #schema_ty,
                         deprecation: #field_deprecation,
                         cache_control: #cache_control,
                         external: #external,
@@ -542,8 +708,12 @@ pub fn generate(
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
                         override_from: #override_from,
+                        override_label: #override_label,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         compute_complexity: #complexity,
+                        directive_invocations: #directive_invocations,
                     });
                 });
 
@@ -564,10 +734,20 @@ pub fn generate(
                             .expect("invalid result type");
                 }
 
-                let resolve_obj = quote! {
-                    {
-                        let res = self.#field_ident(ctx, #(#use_params),*).await;
-                        res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                let resolve_obj = if method_args.batch {
+                    has_batch = true;
+                    quote! {
+                        ctx.resolve_batch(self, |parents| async move {
+                            let res = Self::#field_ident(&parents, ctx, #(#use_params),*).await;
+                            res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                        }).await
+                    }
+                } else {
+                    quote! {
+                        {
+                            let res = self.#field_ident(ctx, #(#use_params),*).await;
+                            res.map_err(|err| ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos))
+                        }
                     }
                 };
 
@@ -579,6 +759,19 @@ pub fn generate(
                     None => None,
                 };
 
+                let resolve_value = if is_node_id {
+                    quote! {
+                        return ::std::result::Result::Ok(::std::option::Option::Some(#crate_name::Value::String(
+                            #crate_name::relay::GlobalId::new(#gql_typename, &obj).encode()
+                        )));
+                    }
+                } else {
+                    quote! {
+                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                        return #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                    }
+                };
+
                 resolvers.push(quote! {
                     #(#cfg_attrs)*
                     if ctx.item.node.name.node == #field_name {
@@ -588,8 +781,7 @@ pub fn generate(
                             #resolve_obj
                         };
                         let obj = f.await.map_err(|err| ctx.set_error_path(err))?;
-                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                        return #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                        #resolve_value
                     }
                 });
             }
@@ -616,6 +808,39 @@ pub fn generate(
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
 
+    batch_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
+    let find_batch_entities = |fn_name: Ident, find_entity: proc_macro2::TokenStream| {
+        if batch_entities.is_empty() {
+            return None;
+        }
+        let batch_entities_iter = batch_entities.iter().map(|(_, code)| code);
+        Some(quote! {
+            async fn #fn_name(
+                &self,
+                ctx: &#crate_name::Context<'_>,
+                type_name: &::std::primitive::str,
+                representations: &[(::std::primitive::usize, &#crate_name::Value)],
+            ) -> ::std::option::Option<::std::vec::Vec<#crate_name::ServerResult<::std::option::Option<#crate_name::Value>>>> {
+                let mut results: ::std::vec::Vec<::std::option::Option<#crate_name::ServerResult<::std::option::Option<#crate_name::Value>>>> =
+                    representations.iter().map(|_| ::std::option::Option::None).collect();
+                let mut found = false;
+                #(#batch_entities_iter)*
+                if !found {
+                    return ::std::option::Option::None;
+                }
+
+                let mut values = ::std::vec::Vec::with_capacity(results.len());
+                for ((idx, params), res) in representations.iter().zip(results) {
+                    values.push(match res {
+                        ::std::option::Option::Some(res) => res,
+                        ::std::option::Option::None => #find_entity(&ctx.with_index(*idx), params).await,
+                    });
+                }
+                ::std::option::Option::Some(values)
+            }
+        })
+    };
+
     if resolvers.is_empty() && create_entity_types.is_empty() {
         return Err(Error::new_spanned(
             self_ty,
@@ -624,6 +849,34 @@ pub fn generate(
         .into());
     }
 
+    if object_args.node && !has_node_id {
+        return Err(Error::new_spanned(self_ty, "A node must have an `id` field.").into());
+    }
+
+    if object_args.node && !object_args.concretes.is_empty() {
+        return Err(Error::new_spanned(
+            self_ty,
+            "The `node` attribute cannot be used with concrete types.",
+        )
+        .into());
+    }
+
+    let add_node_type = if object_args.node {
+        Some(quote! { registry.add_node_type::<Self>(); })
+    } else {
+        None
+    };
+
+    let batch_parent = if has_batch {
+        Some(quote! {
+            fn batch_parent(&self) -> ::std::option::Option<&(dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync)> {
+                ::std::option::Option::Some(self)
+            }
+        })
+    } else {
+        None
+    };
+
     let visible = visible_fn(&object_args.visible);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
@@ -632,6 +885,8 @@ pub fn generate(
     };
 
     let expanded = if object_args.concretes.is_empty() {
+        let find_entities_fn =
+            find_batch_entities(format_ident!("find_entities"), quote!(self.find_entity));
         quote! {
             #item_impl
 
@@ -659,6 +914,8 @@ pub fn generate(
                     #(#find_entities_iter)*
                     ::std::result::Result::Ok(::std::option::Option::None)
                 }
+
+                #find_entities_fn
             }
 
             #[allow(clippy::all, clippy::pedantic)]
@@ -682,13 +939,19 @@ pub fn generate(
                         shareable: #shareable,
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
-                        keys: ::std::option::Option::None,
+                        keys: #keys,
+                        resolvable: #resolvable,
+                        interface_object: #interface_object,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         is_subscription: false,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                        directive_invocations: #directive_invocations,
                     });
                     #(#create_entity_types)*
                     #(#add_keys)*
+                    #add_node_type
                     ty
                 }
 
@@ -699,12 +962,30 @@ pub fn generate(
                 ) -> #crate_name::ServerResult<#crate_name::Value> {
                     #resolve_container
                 }
+
+                #batch_parent
             }
 
             impl #impl_generics #crate_name::ObjectType for #self_ty #where_clause {}
         }
     } else {
         let mut codes = Vec::new();
+        let internal_find_entities_fn = find_batch_entities(
+            format_ident!("__internal_find_entities"),
+            quote!(self.__internal_find_entity),
+        );
+        let concrete_find_entities = internal_find_entities_fn.as_ref().map(|_| {
+            quote! {
+                async fn find_entities(
+                    &self,
+                    ctx: &#crate_name::Context<'_>,
+                    type_name: &::std::primitive::str,
+                    representations: &[(::std::primitive::usize, &#crate_name::Value)],
+                ) -> ::std::option::Option<::std::vec::Vec<#crate_name::ServerResult<::std::option::Option<#crate_name::Value>>>> {
+                    self.__internal_find_entities(ctx, type_name, representations).await
+                }
+            }
+        });
 
         codes.push(quote! {
             #item_impl
@@ -724,10 +1005,15 @@ pub fn generate(
                         shareable: #shareable,
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
-                        keys: ::std::option::Option::None,
+                        keys: #keys,
+                        resolvable: #resolvable,
+                        interface_object: #interface_object,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         is_subscription: false,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                        directive_invocations: #directive_invocations,
                     });
                     #(#create_entity_types)*
                     #(#add_keys)*
@@ -754,6 +1040,8 @@ pub fn generate(
                     #(#find_entities_iter)*
                     ::std::result::Result::Ok(::std::option::Option::None)
                 }
+
+                #internal_find_entities_fn
             }
         });
 
@@ -779,6 +1067,8 @@ pub fn generate(
                     async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
                         self.__internal_find_entity(ctx, params).await
                     }
+
+                    #concrete_find_entities
                 }
 
                 #[#crate_name::async_trait::async_trait]
@@ -101,6 +101,7 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
                     inaccessible: #inaccessible,
                     tags: ::std::vec![ #(#tags),* ],
                     is_secret: #secret,
+                    directive_invocations: ::std::vec::Vec::new(),
                 });
             });
 
@@ -8,7 +8,8 @@ use syn::{ext::IdentExt, visit::Visit, Error, Ident, LifetimeDef, Path, Type};
 use crate::{
     args::{self, RenameRuleExt, RenameTarget, SimpleObjectField},
     utils::{
-        gen_deprecation, generate_guards, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+        gen_deprecation, generate_directive_invocations, generate_guards, get_crate_name,
+        get_rustdoc, visible_fn, GeneratorResult,
     },
 };
 
@@ -37,6 +38,22 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         .iter()
         .map(|tag| quote!(::std::string::ToString::to_string(#tag)))
         .collect::<Vec<_>>();
+    let interface_object = object_args.interface_object;
+    let authenticated = object_args.authenticated;
+    let requires_scopes = object_args
+        .requires_scopes
+        .iter()
+        .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+        .collect::<Vec<_>>();
+    let resolvable = object_args.unresolvable.is_none();
+    let directive_invocations =
+        generate_directive_invocations(&crate_name, &object_args.directives)?;
+    let keys = match &object_args.unresolvable {
+        Some(keys) => {
+            quote! { ::std::option::Option::Some(::std::vec![::std::string::ToString::to_string(#keys)]) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
     let gql_typename = if !object_args.name_type {
         object_args
             .name
@@ -67,6 +84,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let mut schema_fields = Vec::new();
 
     let mut processed_fields: Vec<SimpleObjectFieldGenerator> = vec![];
+    let mut has_node_id = false;
 
     // Before processing the fields, we generate the derivated fields
     for field in &s.fields {
@@ -143,6 +161,16 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(from) => quote! { ::std::option::Option::Some(#from) },
             None => quote! { ::std::option::Option::None },
         };
+        let override_label = match &field.override_label {
+            Some(label) => quote! { ::std::option::Option::Some(#label) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let authenticated = field.authenticated;
+        let requires_scopes = field
+            .requires_scopes
+            .iter()
+            .map(|scopes| quote!(::std::string::ToString::to_string(#scopes)))
+            .collect::<Vec<_>>();
         let requires = match &field.requires {
             Some(requires) => quote! { ::std::option::Option::Some(#requires) },
             None => quote! { ::std::option::Option::None },
@@ -151,6 +179,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(provides) => quote! { ::std::option::Option::Some(#provides) },
             None => quote! { ::std::option::Option::None },
         };
+        let directive_invocations = generate_directive_invocations(&crate_name, &field.directives)?;
         let vis = &field.vis;
 
         let ty = if let Some(derived) = derived {
@@ -181,6 +210,13 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         };
 
         let visible = visible_fn(&field.visible);
+        let is_node_id = object_args.node && !field.flatten && field_name == "id";
+        has_node_id |= is_node_id;
+        let schema_ty = if is_node_id {
+            quote! { ::std::string::ToString::to_string("ID!") }
+        } else {
+            quote! { <#ty as #crate_name::OutputType>::create_type_info(registry) }
+        };
 
         if !field.flatten {
             schema_fields.push(quote! {
@@ -188,7 +224,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     name: ::std::borrow::ToOwned::to_owned(#field_name),
                     description: #field_desc,
                     args: ::std::default::Default::default(),
-                    ty: <#ty as #crate_name::OutputType>::create_type_info(registry),
+                    ty: #schema_ty,
                     deprecation: #field_deprecation,
                     cache_control: #cache_control,
                     external: #external,
@@ -198,8 +234,12 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     inaccessible: #inaccessible,
                     tags: ::std::vec![ #(#tags),* ],
                     override_from: #override_from,
+                    override_label: #override_label,
+                    authenticated: #authenticated,
+                    requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                     visible: #visible,
                     compute_complexity: ::std::option::Option::None,
+                    directive_invocations: #directive_invocations,
                 });
             });
         } else {
@@ -246,6 +286,19 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             false => quote! { #ty },
         };
 
+        let resolve_obj = if is_node_id {
+            quote! {
+                return ::std::result::Result::Ok(::std::option::Option::Some(#crate_name::Value::String(
+                    #crate_name::relay::GlobalId::new(#gql_typename, &obj).encode()
+                )));
+            }
+        } else {
+            quote! {
+                let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                return #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+            }
+        };
+
         if !field.flatten {
             getters.push(quote! {
                  #[inline]
@@ -262,8 +315,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         self.#ident(ctx).await.map_err(|err| err.into_server_error(ctx.item.pos))
                     };
                     let obj = f.await.map_err(|err| ctx.set_error_path(err))?;
-                    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-                    return #crate_name::OutputType::resolve(&obj, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                    #resolve_obj
                 }
             });
         } else {
@@ -275,6 +327,18 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         }
     }
 
+    if object_args.node && !has_node_id {
+        return Err(Error::new_spanned(ident, "A node must have an `id` field.").into());
+    }
+
+    if object_args.node && !object_args.concretes.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            "The `node` attribute cannot be used with concrete types.",
+        )
+        .into());
+    }
+
     if !object_args.fake && resolvers.is_empty() {
         return Err(Error::new_spanned(
             ident,
@@ -302,6 +366,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
 
     let mut concat_complex_fields = quote!();
     let mut complex_resolver = quote!();
+    let mut complex_batch_parent = quote!();
 
     if object_args.complex {
         concat_complex_fields = quote! {
@@ -312,8 +377,19 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 return Ok(Some(value));
             }
         };
+        complex_batch_parent = quote! {
+            fn batch_parent(&self) -> ::std::option::Option<&(dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync)> {
+                <Self as #crate_name::ComplexObject>::batch_parent(self)
+            }
+        };
     }
 
+    let add_node_type = if object_args.node {
+        Some(quote! { registry.add_node_type::<Self>(); })
+    } else {
+        None
+    };
+
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -346,7 +422,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 }
 
                 fn create_type_info(registry: &mut #crate_name::registry::Registry) -> ::std::string::String {
-                    registry.create_output_type::<Self, _>(#crate_name::registry::MetaTypeId::Object, |registry| #crate_name::registry::MetaType::Object {
+                    let ty = registry.create_output_type::<Self, _>(#crate_name::registry::MetaTypeId::Object, |registry| #crate_name::registry::MetaType::Object {
                         name: ::std::borrow::Cow::into_owned(#gql_typename),
                         description: #desc,
                         fields: {
@@ -360,16 +436,25 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         shareable: #shareable,
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
-                        keys: ::std::option::Option::None,
+                        keys: #keys,
+                        resolvable: #resolvable,
+                        interface_object: #interface_object,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         is_subscription: false,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
-                    })
+                        directive_invocations: #directive_invocations,
+                    });
+                    #add_node_type
+                    ty
                 }
 
                 async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::Value> {
                     #resolve_container
                 }
+
+                #complex_batch_parent
             }
 
             impl #impl_generics #crate_name::ObjectType for #ident #ty_generics #where_clause {}
@@ -427,10 +512,15 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         shareable: #shareable,
                         inaccessible: #inaccessible,
                         tags: ::std::vec![ #(#tags),* ],
-                        keys: ::std::option::Option::None,
+                        keys: #keys,
+                        resolvable: #resolvable,
+                        interface_object: #interface_object,
+                        authenticated: #authenticated,
+                        requires_scopes: ::std::vec![ #(#requires_scopes),* ],
                         visible: #visible,
                         is_subscription: false,
                         rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                        directive_invocations: #directive_invocations,
                     })
                 }
 
@@ -472,6 +562,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::Value> {
                         #resolve_container
                     }
+
+                    #complex_batch_parent
                 }
 
                 impl #def_lifetimes #crate_name::ObjectType for #concrete_type {}
@@ -6,7 +6,10 @@ use syn::{
 };
 
 use crate::{
-    args::{self, ComplexityType, RenameRuleExt, RenameTarget, SubscriptionField},
+    args::{
+        self, ComplexityType, RenameRuleExt, RenameTarget, SubscriptionBufferPolicy,
+        SubscriptionField,
+    },
     output_type::OutputType,
     utils::{
         extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
@@ -127,6 +130,7 @@ pub fn generate(
                             inaccessible: false,
                             tags: ::std::default::Default::default(),
                             is_secret: #secret,
+                            directive_invocations: ::std::vec::Vec::new(),
                         });
                     });
 
@@ -276,10 +280,14 @@ pub fn generate(
                     provides: ::std::option::Option::None,
                     shareable: false,
                     override_from: ::std::option::Option::None,
+                    override_label: ::std::option::Option::None,
+                    authenticated: false,
+                    requires_scopes: ::std::vec::Vec::new(),
                     visible: #visible,
                     inaccessible: false,
                     tags: ::std::default::Default::default(),
                     compute_complexity: #complexity,
+                    directive_invocations: ::std::vec::Vec::new(),
                 });
             });
 
@@ -302,6 +310,38 @@ pub fn generate(
                 Some(code) => Some(generate_guards(&crate_name, code, guard_map_err)?),
                 None => None,
             };
+            let buffer_policy = match field.buffer_policy {
+                Some(SubscriptionBufferPolicy::Unbounded) => {
+                    quote!(#crate_name::SubscriptionBufferPolicy::Unbounded)
+                }
+                Some(SubscriptionBufferPolicy::DropOldest(size)) => {
+                    quote!(#crate_name::SubscriptionBufferPolicy::DropOldest(#size))
+                }
+                Some(SubscriptionBufferPolicy::CoalesceLatest) => {
+                    quote!(#crate_name::SubscriptionBufferPolicy::CoalesceLatest)
+                }
+                Some(SubscriptionBufferPolicy::DisconnectOnOverflow(size)) => {
+                    quote!(#crate_name::SubscriptionBufferPolicy::DisconnectOnOverflow(#size))
+                }
+                None => quote!(ctx.schema_env.subscription_buffer_policy),
+            };
+            let replay = match &field.replay {
+                Some(fn_path) => {
+                    let fn_path: syn::ExprPath = syn::parse_str(fn_path)?;
+                    quote! {
+                        match ctx.last_event_id() {
+                            ::std::option::Option::Some(last_event_id) => #fn_path(self, ctx, last_event_id)
+                                .await
+                                .map_err(|err| {
+                                    ::std::convert::Into::<#crate_name::Error>::into(err).into_server_error(ctx.item.pos)
+                                        .with_path(::std::vec![#crate_name::PathSegment::Field(::std::borrow::ToOwned::to_owned(&*field_name))])
+                                })?,
+                            ::std::option::Option::None => ::std::vec::Vec::new(),
+                        }
+                    }
+                }
+                None => quote!(::std::vec::Vec::new()),
+            };
             let stream_fn = quote! {
                 let field_name = ::std::clone::Clone::clone(&ctx.item.node.response_key().node);
                 let field = ::std::sync::Arc::new(::std::clone::Clone::clone(&ctx.item));
@@ -309,9 +349,12 @@ pub fn generate(
                 let f = async {
                     #(#get_params)*
                     #guard
-                    #create_field_stream
+                    let stream = #create_field_stream?;
+                    let replayed = #replay;
+                    #crate_name::ServerResult::Ok(#crate_name::replay_events(replayed, stream))
                 };
                 let stream = f.await.map_err(|err| ctx.set_error_path(err))?;
+                let stream = #crate_name::SubscriptionBuffer::new(stream, #buffer_policy, ctx);
 
                 let pos = ctx.item.pos;
                 let schema_env = ::std::clone::Clone::clone(&ctx.schema_env);
@@ -324,6 +367,7 @@ pub fn generate(
                         let field = ::std::clone::Clone::clone(&field);
                         let field_name = ::std::clone::Clone::clone(&field_name);
                         async move {
+                            let msg = msg?;
                             let ctx_selection_set = query_env.create_context(
                                 &schema_env,
                                 ::std::option::Option::Some(#crate_name::QueryPathNode {
@@ -359,6 +403,7 @@ pub fn generate(
 
                                 use ::std::iter::Extend;
                                 resp.errors.extend(::std::mem::take(&mut *query_env.errors.lock().unwrap()));
+                                resp.event_id = #crate_name::OutputType::event_id(&msg).map(::std::borrow::ToOwned::to_owned);
                                 resp
                             };
                             #crate_name::futures_util::pin_mut!(execute_fut);
@@ -420,12 +465,17 @@ pub fn generate(
                     cache_control: ::std::default::Default::default(),
                     extends: #extends,
                     keys: ::std::option::Option::None,
+                    resolvable: true,
+                    interface_object: false,
+                    authenticated: false,
+                    requires_scopes: ::std::vec::Vec::new(),
                     visible: #visible,
                     shareable: false,
                     inaccessible: false,
                     tags: ::std::default::Default::default(),
                     is_subscription: true,
                     rust_typename: ::std::option::Option::Some(::std::any::type_name::<Self>()),
+                    directive_invocations: ::std::vec::Vec::new(),
                 })
             }
 
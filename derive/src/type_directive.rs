@@ -0,0 +1,158 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, Error, FnArg, ItemFn, Pat};
+
+use crate::{
+    args,
+    args::{Argument, RenameRuleExt, RenameTarget},
+    utils::{
+        generate_default, get_crate_name, get_rustdoc, parse_graphql_attrs, visible_fn,
+        GeneratorResult,
+    },
+};
+
+pub fn generate(
+    directive_args: &args::TypeDirective,
+    item_fn: &ItemFn,
+) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(directive_args.internal);
+    let ident = &item_fn.sig.ident;
+    let vis = &item_fn.vis;
+    let directive_name = directive_args
+        .name
+        .clone()
+        .unwrap_or_else(|| item_fn.sig.ident.to_string());
+    let desc = get_rustdoc(&item_fn.attrs)?
+        .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
+        .unwrap_or_else(|| quote!(::std::option::Option::None));
+    let visible = visible_fn(&directive_args.visible);
+    let repeatable = directive_args.repeatable;
+
+    let mut schema_args = Vec::new();
+    let mut apply_params = Vec::new();
+    let mut apply_args = Vec::new();
+
+    for arg in item_fn.sig.inputs.iter() {
+        let (arg_ident, arg_ty, arg_attrs) = match arg {
+            FnArg::Typed(pat) => match &*pat.pat {
+                Pat::Ident(ident) => (&ident.ident, &pat.ty, &pat.attrs),
+                _ => return Err(Error::new_spanned(arg, "Invalid argument type.").into()),
+            },
+            _ => return Err(Error::new_spanned(arg, "Invalid argument type.").into()),
+        };
+
+        let Argument {
+            name,
+            desc,
+            default,
+            default_with,
+            visible,
+            ..
+        } = parse_graphql_attrs::<args::Argument>(arg_attrs)?.unwrap_or_default();
+
+        let name = name.clone().unwrap_or_else(|| {
+            directive_args
+                .rename_args
+                .rename(arg_ident.unraw().to_string(), RenameTarget::Argument)
+        });
+        let desc = desc
+            .as_ref()
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
+        let default = generate_default(&default, &default_with)?;
+        let schema_default = default
+            .as_ref()
+            .map(|value| {
+                quote! {
+                    ::std::option::Option::Some(::std::string::ToString::to_string(
+                        &<#arg_ty as #crate_name::InputType>::to_value(&#value)
+                    ))
+                }
+            })
+            .unwrap_or_else(|| quote! {::std::option::Option::None});
+        let visible = visible_fn(&visible);
+
+        schema_args.push(quote! {
+            args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
+                name: ::std::string::ToString::to_string(#name),
+                description: #desc,
+                ty: <#arg_ty as #crate_name::InputType>::create_type_info(registry),
+                default_value: #schema_default,
+                visible: #visible,
+                inaccessible: false,
+                tags: ::std::default::Default::default(),
+                is_secret: false,
+                directive_invocations: ::std::vec::Vec::new(),
+            });
+        });
+
+        apply_params.push(quote! { #arg_ident: #arg_ty });
+        apply_args.push(quote! {
+            let value = <#arg_ty as #crate_name::InputType>::to_value(&#arg_ident);
+            if value != #crate_name::Value::Null {
+                args.insert(::std::string::ToString::to_string(#name), value);
+            }
+        });
+    }
+
+    let locations = directive_args
+        .locations
+        .iter()
+        .map(|loc| {
+            let loc = quote::format_ident!("{}", loc.to_string());
+            quote!(#crate_name::registry::__DirectiveLocation::#loc)
+        })
+        .collect::<Vec<_>>();
+
+    if locations.is_empty() {
+        return Err(Error::new(
+            ident.span(),
+            "At least one location is required for the directive.",
+        )
+        .into());
+    }
+
+    let expanded = quote! {
+        #[allow(non_camel_case_types)]
+        #vis struct #ident;
+
+        impl #ident {
+            /// Creates an invocation of the directive that can be applied to
+            /// a schema element.
+            #vis fn apply(#(#apply_params),*) -> #crate_name::registry::MetaDirectiveInvocation {
+                #[allow(unused_mut)]
+                let mut args = #crate_name::indexmap::IndexMap::new();
+                #(#apply_args)*
+                #crate_name::registry::MetaDirectiveInvocation {
+                    name: ::std::string::ToString::to_string(#directive_name),
+                    args,
+                }
+            }
+        }
+
+        impl #crate_name::TypeDirective for #ident {
+            fn name(&self) -> ::std::borrow::Cow<'static, ::std::primitive::str> {
+                ::std::borrow::Cow::Borrowed(#directive_name)
+            }
+
+            fn register(&self, registry: &mut #crate_name::registry::Registry) {
+                let meta = #crate_name::registry::MetaDirective {
+                    name: ::std::string::ToString::to_string(#directive_name),
+                    description: #desc,
+                    locations: vec![#(#locations),*],
+                    args: {
+                        #[allow(unused_mut)]
+                        let mut args = #crate_name::indexmap::IndexMap::new();
+                        #(#schema_args)*
+                        args
+                    },
+                    is_repeatable: #repeatable,
+                    visible: #visible,
+                };
+                registry.add_directive(meta);
+            }
+        }
+    };
+
+    Ok(expanded.into())
+}
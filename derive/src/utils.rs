@@ -5,9 +5,9 @@ use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
 use syn::{
-    visit::Visit, visit_mut, visit_mut::VisitMut, Attribute, Error, Expr, ExprPath, FnArg, Ident,
-    ImplItemMethod, Lifetime, Lit, LitStr, Meta, Pat, PatIdent, Type, TypeGroup, TypeParamBound,
-    TypeReference,
+    visit::Visit, visit_mut, visit_mut::VisitMut, Attribute, Error, Expr, ExprCall, ExprPath,
+    FnArg, GenericArgument, Ident, ImplItemMethod, Lifetime, Lit, LitStr, Meta, Pat, PatIdent,
+    PathArguments, Type, TypeGroup, TypeParamBound, TypeReference,
 };
 use thiserror::Error;
 
@@ -61,6 +61,40 @@ pub fn generate_guards(
     })
 }
 
+pub fn generate_directive_invocations(
+    crate_name: &TokenStream,
+    directives: &[SpannedValue<String>],
+) -> GeneratorResult<TokenStream> {
+    let mut invocations = Vec::new();
+    for code in directives {
+        let expr: ExprCall =
+            syn::parse_str(code).map_err(|err| Error::new(code.span(), err.to_string()))?;
+        let path = match &*expr.func {
+            Expr::Path(ExprPath { path, .. })
+                if path.segments.len() >= 2
+                    && matches!(path.segments.last(), Some(segment) if segment.ident == "apply") =>
+            {
+                path
+            }
+            _ => {
+                return Err(Error::new(
+                    code.span(),
+                    "The directive must be in the form `directive::apply(...)`.",
+                )
+                .into())
+            }
+        };
+        let leading_colon = &path.leading_colon;
+        let segments = path.segments.iter().take(path.segments.len() - 1);
+        let directive = quote!(#leading_colon #(#segments)::*);
+        invocations.push(quote! {{
+            #crate_name::TypeDirective::register(&#directive, registry);
+            #expr
+        }});
+    }
+    Ok(quote!(::std::vec![#(#invocations),*]))
+}
+
 pub fn get_rustdoc(attrs: &[Attribute]) -> GeneratorResult<Option<String>> {
     let mut full_docs = String::new();
     for attr in attrs {
@@ -308,6 +342,58 @@ pub fn extract_input_args<T: FromMeta + Default>(
     Ok(args)
 }
 
+/// Extract the input arguments of a batch resolver, whose first parameter is
+/// the slice of parents instead of the self receiver.
+pub fn extract_batch_input_args<T: FromMeta + Default>(
+    crate_name: &proc_macro2::TokenStream,
+    method: &mut ImplItemMethod,
+) -> GeneratorResult<Vec<(PatIdent, Type, T)>> {
+    let parents = match method.sig.inputs.first() {
+        Some(FnArg::Typed(pat)) if matches!(&*pat.ty, Type::Reference(TypeReference { elem, .. }) if matches!(elem.as_ref(), Type::Slice(_))) => {
+            pat.clone()
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                &method.sig,
+                "The parents slice must be the first parameter of a batch resolver.",
+            )
+            .into())
+        }
+    };
+
+    method.sig.inputs[0] = syn::parse2::<FnArg>(quote! { &self }).unwrap();
+    let args = extract_input_args(crate_name, method);
+    method.sig.inputs[0] = FnArg::Typed(parents);
+    args
+}
+
+/// Returns the type argument of a generic type with the given name, e.g. the
+/// `T` of `Vec<T>`.
+pub fn generic_argument(ty: &Type, name: &str) -> Option<Type> {
+    if let Type::Path(path) = ty {
+        let segment = path.path.segments.last().unwrap();
+        if segment.ident == name {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                    return Some(ty.clone());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Returns the element type of the `Vec` returned by a batch resolver.
+pub fn batch_element_type(ty: &Type) -> GeneratorResult<Type> {
+    generic_argument(ty, "Vec").ok_or_else(|| {
+        Error::new_spanned(
+            ty,
+            "A batch resolver must return `Vec<T>` or `Result<Vec<T>>`.",
+        )
+        .into()
+    })
+}
+
 pub struct RemoveLifetime;
 
 impl VisitMut for RemoveLifetime {
@@ -77,7 +77,11 @@ impl FromRequest for GraphQLBatchRequest {
         if req.method() == Method::GET {
             let res = async_graphql::http::parse_query_string(req.query_string())
                 .map_err(|err| io::Error::new(ErrorKind::Other, err));
-            Box::pin(async move { Ok(Self(async_graphql::BatchRequest::Single(res?))) })
+            let req = req.clone();
+            Box::pin(async move {
+                let request = async_graphql::BatchRequest::Single(res?);
+                Ok(Self(apply_headers(request, &req)))
+            })
         } else if req.method() == Method::POST {
             let content_type = req
                 .headers()
@@ -97,41 +101,37 @@ impl FromRequest for GraphQLBatchRequest {
                 }
             });
 
+            let req = req.clone();
             Box::pin(async move {
-                Ok(GraphQLBatchRequest(
-                    async_graphql::http::receive_batch_body(
-                        content_type,
-                        rx.map_err(|e| match e {
-                            PayloadError::Incomplete(Some(e)) | PayloadError::Io(e) => e,
-                            PayloadError::Incomplete(None) => {
-                                io::Error::from(ErrorKind::UnexpectedEof)
-                            }
-                            PayloadError::EncodingCorrupted => io::Error::new(
-                                ErrorKind::InvalidData,
-                                "cannot decode content-encoding",
-                            ),
-                            PayloadError::Overflow => io::Error::new(
-                                ErrorKind::InvalidData,
-                                "a payload reached size limit",
-                            ),
-                            PayloadError::UnknownLength => {
-                                io::Error::new(ErrorKind::Other, "a payload length is unknown")
-                            }
-                            PayloadError::Http2Payload(e) if e.is_io() => e.into_io().unwrap(),
-                            PayloadError::Http2Payload(e) => io::Error::new(ErrorKind::Other, e),
-                            _ => io::Error::new(ErrorKind::Other, e),
-                        })
-                        .into_async_read(),
-                        config,
-                    )
-                    .await
-                    .map_err(|err| match err {
-                        ParseRequestError::PayloadTooLarge => {
-                            actix_web::error::ErrorPayloadTooLarge(err)
+                let request = async_graphql::http::receive_batch_body(
+                    content_type,
+                    rx.map_err(|e| match e {
+                        PayloadError::Incomplete(Some(e)) | PayloadError::Io(e) => e,
+                        PayloadError::Incomplete(None) => io::Error::from(ErrorKind::UnexpectedEof),
+                        PayloadError::EncodingCorrupted => {
+                            io::Error::new(ErrorKind::InvalidData, "cannot decode content-encoding")
                         }
-                        _ => actix_web::error::ErrorBadRequest(err),
-                    })?,
-                ))
+                        PayloadError::Overflow => {
+                            io::Error::new(ErrorKind::InvalidData, "a payload reached size limit")
+                        }
+                        PayloadError::UnknownLength => {
+                            io::Error::new(ErrorKind::Other, "a payload length is unknown")
+                        }
+                        PayloadError::Http2Payload(e) if e.is_io() => e.into_io().unwrap(),
+                        PayloadError::Http2Payload(e) => io::Error::new(ErrorKind::Other, e),
+                        _ => io::Error::new(ErrorKind::Other, e),
+                    })
+                    .into_async_read(),
+                    config,
+                )
+                .await
+                .map_err(|err| match err {
+                    ParseRequestError::PayloadTooLarge => {
+                        actix_web::error::ErrorPayloadTooLarge(err)
+                    }
+                    _ => actix_web::error::ErrorBadRequest(err),
+                })?;
+                Ok(GraphQLBatchRequest(apply_headers(request, &req)))
             })
         } else {
             Box::pin(async move {
@@ -143,6 +143,17 @@ impl FromRequest for GraphQLBatchRequest {
     }
 }
 
+fn apply_headers(
+    request: async_graphql::BatchRequest,
+    req: &HttpRequest,
+) -> async_graphql::BatchRequest {
+    async_graphql::http::apply_headers(request, |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    })
+}
+
 /// Responder for a GraphQL response.
 ///
 /// This contains a batch response, but since regular responses are a type of
@@ -114,7 +114,8 @@ where
                         format!("failed to parse graphql request from uri query: {}", err),
                     ))
                 });
-            Ok(Self(async_graphql::BatchRequest::Single(res?), PhantomData))
+            let request = async_graphql::BatchRequest::Single(res?);
+            Ok(Self(apply_headers(request, req.headers()), PhantomData))
         } else {
             let content_type = req
                 .headers()
@@ -131,15 +132,22 @@ where
                 })?
                 .map_err(|err| std::io::Error::new(ErrorKind::Other, err.to_string()));
             let body_reader = tokio_util::io::StreamReader::new(body_stream).compat();
-            Ok(Self(
-                async_graphql::http::receive_batch_body(
-                    content_type,
-                    body_reader,
-                    MultipartOptions::default(),
-                )
-                .await?,
-                PhantomData,
-            ))
+            let request = async_graphql::http::receive_batch_body(
+                content_type,
+                body_reader,
+                MultipartOptions::default(),
+            )
+            .await?;
+            Ok(Self(apply_headers(request, req.headers()), PhantomData))
         }
     }
 }
+
+fn apply_headers(
+    request: async_graphql::BatchRequest,
+    headers: &http::HeaderMap,
+) -> async_graphql::BatchRequest {
+    async_graphql::http::apply_headers(request, |name| {
+        headers.get(name).and_then(|value| value.to_str().ok())
+    })
+}
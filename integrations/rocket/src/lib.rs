@@ -67,7 +67,11 @@ impl<'r> FromData<'r> for GraphQLBatchRequest {
         .await;
 
         match request {
-            Ok(request) => data::Outcome::Success(Self(request)),
+            Ok(request) => {
+                data::Outcome::Success(Self(async_graphql::http::apply_headers(request, |name| {
+                    req.headers().get_one(name)
+                })))
+            }
             Err(e) => data::Outcome::Failure((
                 match e {
                     ParseRequestError::PayloadTooLarge => Status::PayloadTooLarge,
@@ -124,7 +124,7 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
     mut request: Request<State>,
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::BatchRequest> {
-    if request.method() == Method::Get {
+    let batch_request = if request.method() == Method::Get {
         async_graphql::http::parse_query_string(request.url().query().unwrap_or_default())
             .map(Into::into)
             .map_err(|err| tide::Error::new(StatusCode::BadRequest, err))
@@ -151,7 +151,13 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
             StatusCode::MethodNotAllowed,
             "GraphQL only supports GET and POST requests",
         ))
-    }
+    }?;
+    Ok(async_graphql::http::apply_headers(batch_request, |name| {
+        request
+            .header(name)
+            .and_then(|values| values.get(0))
+            .map(HeaderValue::as_str)
+    }))
 }
 
 /// Convert a GraphQL response to a Tide response.
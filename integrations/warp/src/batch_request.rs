@@ -2,7 +2,7 @@ use std::{io, io::ErrorKind};
 
 use async_graphql::{http::MultipartOptions, BatchRequest, Executor};
 use futures_util::TryStreamExt;
-use warp::{reply::Response as WarpResponse, Buf, Filter, Rejection, Reply};
+use warp::{http::HeaderMap, reply::Response as WarpResponse, Buf, Filter, Rejection, Reply};
 
 use crate::GraphQLBadRequest;
 
@@ -54,7 +54,13 @@ where
                 .map_err(|e| warp::reject::custom(GraphQLBadRequest(e)))
             }))
         .unify()
-        .map(move |res| (executor.clone(), res))
+        .and(warp::header::headers_cloned())
+        .map(move |res, headers: HeaderMap| {
+            let res = async_graphql::http::apply_headers(res, |name| {
+                headers.get(name).and_then(|value| value.to_str().ok())
+            });
+            (executor.clone(), res)
+        })
 }
 
 /// Reply for `async_graphql::BatchRequest`.
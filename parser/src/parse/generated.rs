@@ -384,6 +384,10 @@ impl ::pest::Parser<Rule> for GraphQLParser {
                                             .and_then(|state| {
                                                 state.optional(|state| { self::default_value(state) })
                                             })
+                                            .and_then(|state| { super::hidden::skip(state) })
+                                            .and_then(|state| {
+                                                state.optional(|state| { self::directives(state) })
+                                            })
                                     })
                             },
                         )
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{any::Any, borrow::Cow, sync::Arc};
 
 use async_graphql_value::ConstValue;
 
@@ -87,6 +87,19 @@ pub trait OutputType: Send + Sync {
         ctx: &ContextSelectionSet<'_>,
         field: &Positioned<Field>,
     ) -> ServerResult<Value>;
+
+    /// Get the event id, only for `SubscriptionEvent`.
+    #[doc(hidden)]
+    fn event_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the parent passed to the batch resolvers, only for objects with
+    /// batch resolvers.
+    #[doc(hidden)]
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -107,6 +120,14 @@ impl<T: OutputType + ?Sized> OutputType for &T {
     ) -> ServerResult<Value> {
         T::resolve(*self, ctx, field).await
     }
+
+    fn event_id(&self) -> Option<&str> {
+        T::event_id(*self)
+    }
+
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::batch_parent(*self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -131,6 +152,9 @@ impl<T: OutputType + Sync, E: Into<Error> + Send + Sync + Clone> OutputType for
             }
         }
     }
+    fn event_id(&self) -> Option<&str> {
+        self.as_ref().ok().and_then(OutputType::event_id)
+    }
 }
 
 /// A GraphQL object.
@@ -175,6 +199,14 @@ impl<T: OutputType + ?Sized> OutputType for Box<T> {
     ) -> ServerResult<Value> {
         T::resolve(&**self, ctx, field).await
     }
+
+    fn event_id(&self) -> Option<&str> {
+        T::event_id(&**self)
+    }
+
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::batch_parent(&**self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -222,6 +254,14 @@ impl<T: OutputType + ?Sized> OutputType for Arc<T> {
     ) -> ServerResult<Value> {
         T::resolve(&**self, ctx, field).await
     }
+
+    fn event_id(&self) -> Option<&str> {
+        T::event_id(&**self)
+    }
+
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        T::batch_parent(&**self)
+    }
 }
 
 impl<T: InputType> InputType for Arc<T> {
@@ -256,4 +296,8 @@ pub trait ComplexObject {
     fn fields(registry: &mut registry::Registry) -> Vec<(String, registry::MetaField)>;
 
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>>;
+
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        None
+    }
 }
@@ -0,0 +1,57 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use futures_channel::mpsc;
+use futures_util::stream::{BoxStream, StreamExt};
+
+use super::BrokerBackend;
+use crate::{Result, Value};
+
+/// In-memory broker backend.
+///
+/// Every subscriber owns a bounded channel. When the buffer of a subscriber
+/// is full, new messages for that subscriber are dropped until it catches up,
+/// so a slow subscriber never blocks the publisher.
+#[derive(Default)]
+pub struct MemoryBackend {
+    subscribers: Mutex<HashMap<String, Vec<mpsc::Sender<Value>>>>,
+}
+
+impl MemoryBackend {
+    /// Create an in-memory broker backend.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl BrokerBackend for MemoryBackend {
+    fn publish(&self, topic: &str, message: Value) -> Result<()> {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        if let Some(senders) = subscribers.get_mut(topic) {
+            let mut idx = 0;
+            while idx < senders.len() {
+                match senders[idx].try_send(message.clone()) {
+                    Err(err) if err.is_disconnected() => {
+                        senders.swap_remove(idx);
+                    }
+                    _ => idx += 1,
+                }
+            }
+            if senders.is_empty() {
+                subscribers.remove(topic);
+            }
+        }
+        Ok(())
+    }
+
+    fn subscribe(&self, topic: &str, buffer_size: usize) -> BoxStream<'static, Value> {
+        // The channel always reserves one slot for its sender.
+        let (tx, rx) = mpsc::channel(buffer_size.saturating_sub(1));
+        self.subscribers
+            .lock()
+            .unwrap()
+            .entry(topic.to_string())
+            .or_default()
+            .push(tx);
+        rx.boxed()
+    }
+}
@@ -0,0 +1,277 @@
+//! Publish/subscribe broker, used to feed subscription streams.
+//!
+//! A [Broker] delivers messages published on a topic to every stream that
+//! subscribed to that topic. Messages are converted to [Value] before they are
+//! handed to the [BrokerBackend], so a backend for an external message system
+//! can serialize them without knowing their Rust type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::*;
+//! use async_graphql::broker::Broker;
+//! use futures_util::stream::{Stream, StreamExt};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(SimpleObject, Serialize, Deserialize, Clone)]
+//! struct BookChanged {
+//!     id: i32,
+//!     name: String,
+//! }
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn value(&self) -> i32 {
+//!         10
+//!     }
+//! }
+//!
+//! struct Mutation;
+//!
+//! #[Object]
+//! impl Mutation {
+//!     async fn rename_book(&self, ctx: &Context<'_>, id: i32, name: String) -> Result<bool> {
+//!         ctx.broker()?.publish("books", &BookChanged { id, name })?;
+//!         Ok(true)
+//!     }
+//! }
+//!
+//! struct Subscription;
+//!
+//! #[Subscription]
+//! impl Subscription {
+//!     async fn book_changed(&self, ctx: &Context<'_>, id: i32) -> Result<impl Stream<Item = BookChanged>> {
+//!         Ok(ctx
+//!             .broker()?
+//!             .subscribe_with_filter("books", move |event: &BookChanged| event.id == id))
+//!     }
+//! }
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+//! let schema = Schema::build(Query, Mutation, Subscription)
+//!     .data(Broker::new())
+//!     .finish();
+//!
+//! let mut stream = schema.execute_stream("subscription { bookChanged(id: 1) { name } }");
+//! let mut next = stream.next();
+//! futures_util::future::poll_immediate(&mut next).await;
+//!
+//! schema.execute(r#"mutation { renameBook(id: 2, name: "abc") }"#).await;
+//! schema.execute(r#"mutation { renameBook(id: 1, name: "def") }"#).await;
+//!
+//! assert_eq!(
+//!     next.await.unwrap().into_result().unwrap().data,
+//!     value!({ "bookChanged": { "name": "def" } })
+//! );
+//! # });
+//! ```
+
+mod memory;
+
+use std::sync::Arc;
+
+use futures_util::stream::{BoxStream, StreamExt};
+pub use memory::MemoryBackend;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Error, Result, Value};
+
+/// A backend used by [Broker] to deliver messages.
+///
+/// The default backend is [MemoryBackend], implement this trait to
+/// distribute messages through an external message system such as Redis or
+/// NATS.
+pub trait BrokerBackend: Send + Sync + 'static {
+    /// Publish a message to all subscribers of the `topic`.
+    fn publish(&self, topic: &str, message: Value) -> Result<()>;
+
+    /// Subscribe to the `topic`.
+    ///
+    /// The backend buffers at most `buffer_size` messages for this subscriber.
+    fn subscribe(&self, topic: &str, buffer_size: usize) -> BoxStream<'static, Value>;
+}
+
+/// Topic-based publish/subscribe broker.
+///
+/// Put the broker into the schema data, then use
+/// [`Context::broker`](crate::context::ContextBase::broker) to publish or
+/// subscribe in resolvers.
+#[derive(Clone)]
+pub struct Broker {
+    backend: Arc<dyn BrokerBackend>,
+    buffer_size: usize,
+}
+
+impl Default for Broker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Broker {
+    /// Create a broker with the in-memory backend.
+    pub fn new() -> Self {
+        Self::with_backend(MemoryBackend::new())
+    }
+
+    /// Create a broker with the specified backend.
+    pub fn with_backend(backend: impl BrokerBackend) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            buffer_size: 16,
+        }
+    }
+
+    /// Specify the number of messages buffered for each subscriber, the
+    /// default is `16`.
+    #[must_use]
+    pub fn buffer_size(self, buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            ..self
+        }
+    }
+
+    /// Publish a message to all subscribers of the `topic`.
+    pub fn publish<T: Serialize>(&self, topic: &str, message: &T) -> Result<()> {
+        let message = crate::to_value(message).map_err(|err| Error::new(err.to_string()))?;
+        self.backend.publish(topic, message)
+    }
+
+    /// Subscribe to the `topic`.
+    ///
+    /// Messages that cannot be converted to `T` are skipped.
+    pub fn subscribe<T>(&self, topic: &str) -> BoxStream<'static, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.subscribe_with_filter(topic, |_: &T| true)
+    }
+
+    /// Subscribe to the `topic`, only the messages for which the `filter`
+    /// returns `true` are received.
+    ///
+    /// Messages that cannot be converted to `T` are skipped.
+    pub fn subscribe_with_filter<T, F>(&self, topic: &str, filter: F) -> BoxStream<'static, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.backend
+            .subscribe(topic, self.buffer_size)
+            .filter_map(move |message| {
+                futures_util::future::ready(
+                    crate::from_value::<T>(message)
+                        .ok()
+                        .filter(|message| filter(message)),
+                )
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use futures_util::future::poll_immediate;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Event {
+        id: i32,
+    }
+
+    #[tokio::test]
+    async fn test_broker() {
+        let broker = Broker::new();
+        let mut a = broker.subscribe::<Event>("a");
+        let mut b = broker.subscribe::<Event>("b");
+
+        broker.publish("a", &Event { id: 1 }).unwrap();
+        broker.publish("b", &Event { id: 2 }).unwrap();
+        broker.publish("a", &Event { id: 3 }).unwrap();
+
+        assert_eq!(a.next().await, Some(Event { id: 1 }));
+        assert_eq!(a.next().await, Some(Event { id: 3 }));
+        assert_eq!(b.next().await, Some(Event { id: 2 }));
+        assert_eq!(poll_immediate(b.next()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_broker_filter() {
+        let broker = Broker::new();
+        let mut stream = broker.subscribe_with_filter("a", |event: &Event| event.id % 2 == 0);
+
+        for id in 0..5 {
+            broker.publish("a", &Event { id }).unwrap();
+        }
+        broker.publish("a", &"not an event").unwrap();
+
+        assert_eq!(stream.next().await, Some(Event { id: 0 }));
+        assert_eq!(stream.next().await, Some(Event { id: 2 }));
+        assert_eq!(stream.next().await, Some(Event { id: 4 }));
+        assert_eq!(poll_immediate(stream.next()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_broker_buffer_size() {
+        let broker = Broker::new().buffer_size(2);
+        let mut stream = broker.subscribe::<Event>("a");
+
+        for id in 0..5 {
+            broker.publish("a", &Event { id }).unwrap();
+        }
+
+        assert_eq!(stream.next().await, Some(Event { id: 0 }));
+        assert_eq!(stream.next().await, Some(Event { id: 1 }));
+        assert_eq!(poll_immediate(stream.next()).await, None);
+
+        broker.publish("a", &Event { id: 5 }).unwrap();
+        assert_eq!(stream.next().await, Some(Event { id: 5 }));
+    }
+
+    #[tokio::test]
+    async fn test_broker_custom_backend() {
+        #[derive(Default)]
+        struct MyBackend {
+            published: Arc<Mutex<HashMap<String, Vec<Value>>>>,
+        }
+
+        impl BrokerBackend for MyBackend {
+            fn publish(&self, topic: &str, message: Value) -> Result<()> {
+                self.published
+                    .lock()
+                    .unwrap()
+                    .entry(topic.to_string())
+                    .or_default()
+                    .push(message);
+                Ok(())
+            }
+
+            fn subscribe(&self, topic: &str, _buffer_size: usize) -> BoxStream<'static, Value> {
+                let messages = self
+                    .published
+                    .lock()
+                    .unwrap()
+                    .get(topic)
+                    .cloned()
+                    .unwrap_or_default();
+                futures_util::stream::iter(messages).boxed()
+            }
+        }
+
+        let broker = Broker::with_backend(MyBackend::default());
+        broker.publish("a", &Event { id: 1 }).unwrap();
+        broker.publish("a", &Event { id: 2 }).unwrap();
+
+        assert_eq!(
+            broker.subscribe::<Event>("a").collect::<Vec<_>>().await,
+            vec![Event { id: 1 }, Event { id: 2 }]
+        );
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     any::{Any, TypeId},
     collections::HashMap,
     fmt::{self, Debug, Display, Formatter},
+    future::Future,
     ops::Deref,
     sync::{Arc, Mutex},
 };
@@ -24,7 +25,9 @@ use crate::{
     parser::types::{
         Directive, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
     },
+    resolver_utils::{resolve_batch_single, BatchScope},
     schema::{IntrospectionMode, SchemaEnv},
+    types::UploadStreams,
     Error, InputType, Lookahead, Name, OneofObjectType, PathSegment, Pos, Positioned, Result,
     ServerError, ServerResult, UploadValue, Value,
 };
@@ -244,6 +247,7 @@ pub struct ContextBase<'a, T> {
     pub schema_env: &'a SchemaEnv,
     #[doc(hidden)]
     pub query_env: &'a QueryEnv,
+    pub(crate) batch_scope: Option<&'a BatchScope<'a>>,
 }
 
 #[doc(hidden)]
@@ -260,6 +264,8 @@ pub struct QueryEnvInner {
     pub http_headers: Mutex<HeaderMap>,
     pub introspection_mode: IntrospectionMode,
     pub errors: Mutex<Vec<ServerError>>,
+    pub last_event_id: Option<String>,
+    pub(crate) upload_streams: Option<Arc<UploadStreams>>,
 }
 
 #[doc(hidden)]
@@ -280,6 +286,10 @@ impl QueryEnv {
         QueryEnv(Arc::new(inner))
     }
 
+    pub(crate) fn get_mut(&mut self) -> Option<&mut QueryEnvInner> {
+        Arc::get_mut(&mut self.0)
+    }
+
     #[doc(hidden)]
     pub fn create_context<'a, T>(
         &'a self,
@@ -293,6 +303,7 @@ impl QueryEnv {
             item,
             schema_env,
             query_env: self,
+            batch_scope: None,
         }
     }
 }
@@ -326,6 +337,7 @@ impl<'a, T> ContextBase<'a, T> {
             item: field,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            batch_scope: self.batch_scope,
         }
     }
 
@@ -340,6 +352,7 @@ impl<'a, T> ContextBase<'a, T> {
             item: selection_set,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            batch_scope: self.batch_scope,
         }
     }
 
@@ -407,6 +420,27 @@ impl<'a, T> ContextBase<'a, T> {
             .and_then(|d| d.downcast_ref::<D>())
     }
 
+    /// Gets the [`Broker`](crate::broker::Broker) defined in the `Context` or
+    /// `Schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if the broker does not exist.
+    #[cfg(feature = "broker")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "broker")))]
+    pub fn broker(&self) -> Result<&'a crate::broker::Broker> {
+        self.data::<crate::broker::Broker>()
+    }
+
+    /// Returns the id of the last event received by the client, which is
+    /// passed with the `lastEventId` extension of the request.
+    ///
+    /// Subscriptions can use it to replay the events that the client missed,
+    /// see [`SubscriptionEvent`](crate::SubscriptionEvent).
+    pub fn last_event_id(&self) -> Option<&'a str> {
+        self.query_env.last_event_id.as_deref()
+    }
+
     /// Returns whether the HTTP header `key` is currently set on the response
     ///
     /// # Examples
@@ -615,11 +649,26 @@ impl<'a, T> ContextBase<'a, T> {
             item: self.item,
             schema_env: self.schema_env,
             query_env: self.query_env,
+            batch_scope: self.batch_scope,
         }
     }
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Field>> {
+    #[doc(hidden)]
+    pub async fn resolve_batch<'p, P, T, F, Fut>(&'p self, parent: &'p P, f: F) -> ServerResult<T>
+    where
+        P: Any + Send + Sync,
+        T: Send + 'static,
+        F: FnOnce(Vec<&'p P>) -> Fut,
+        Fut: Future<Output = ServerResult<Vec<T>>>,
+    {
+        match (self.batch_scope, &self.path_node) {
+            (Some(scope), Some(path_node)) => scope.resolve(self.item, path_node, parent, f).await,
+            _ => resolve_batch_single(self.item, parent, f).await,
+        }
+    }
+
     #[doc(hidden)]
     pub fn param_value<T: InputType>(
         &self,
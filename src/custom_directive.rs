@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::{
     extensions::ResolveFut, parser::types::Directive, registry::Registry, Context,
-    ContextDirective, ServerResult, Value,
+    ContextDirective, ContextSelectionSet, ServerResult, Value,
 };
 
 #[doc(hidden)]
@@ -18,6 +18,13 @@ pub trait CustomDirectiveFactory: Send + Sync + 'static {
     ) -> ServerResult<Box<dyn CustomDirective>>;
 }
 
+#[doc(hidden)]
+pub trait TypeDirective {
+    fn name(&self) -> Cow<'static, str>;
+
+    fn register(&self, registry: &mut Registry);
+}
+
 /// Represents a custom directive.
 #[async_trait::async_trait]
 #[allow(unused_variables)]
@@ -30,4 +37,43 @@ pub trait CustomDirective: Sync + Send + 'static {
     ) -> ServerResult<Option<Value>> {
         resolve.await
     }
+
+    /// Called at resolve a fragment spread or an inline fragment.
+    ///
+    /// `resolve` resolves the fields selected by the fragment into an object,
+    /// and the fields of the returned object are merged into the parent
+    /// object. Returning `None` omits the fields of the fragment.
+    async fn resolve_fragment(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        resolve: ResolveFut<'_>,
+    ) -> ServerResult<Option<Value>> {
+        resolve.await
+    }
+
+    /// Called at execute a query or mutation operation.
+    ///
+    /// `resolve` resolves the selection set of the operation into the data of
+    /// the response.
+    async fn resolve_operation(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        resolve: ResolveFut<'_>,
+    ) -> ServerResult<Option<Value>> {
+        resolve.await
+    }
+
+    /// Called before the operation is executed for a directive on a variable
+    /// definition, returns the value used for the variable.
+    ///
+    /// `value` is the value of the variable in the request, or its default
+    /// value if it is not provided.
+    async fn resolve_variable(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        name: &str,
+        value: Value,
+    ) -> ServerResult<Value> {
+        Ok(value)
+    }
 }
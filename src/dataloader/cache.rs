@@ -1,10 +1,17 @@
 use std::{
+    any::{Any, TypeId},
     borrow::Cow,
     collections::{hash_map::RandomState, HashMap},
     hash::{BuildHasher, Hash},
     marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
+use fnv::FnvHashMap;
+
+use super::{Loader, PartialLoader};
+
 /// Factory for creating cache storage.
 pub trait CacheFactory: Send + Sync + 'static {
     /// Create a cache storage.
@@ -14,6 +21,20 @@ pub trait CacheFactory: Send + Sync + 'static {
     where
         K: Send + Sync + Clone + Eq + Hash + 'static,
         V: Send + Sync + Clone + 'static;
+
+    /// Create a cache storage for the records loaded by the loader `T`.
+    ///
+    /// The default implementation calls [`CacheFactory::create`], the
+    /// factories that share their records between loaders use `T` to keep the
+    /// records of each loader apart.
+    fn create_for<T, K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        T: 'static,
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create::<K, V>()
+    }
 }
 
 /// Cache storage for [DataLoader].
@@ -195,3 +216,259 @@ where
         self.0.clear();
     }
 }
+
+/// A cache shared by many [`DataLoader`](super::DataLoader)s, the records
+/// expire after the specified time to live.
+///
+/// Cloning a `SharedCache` is cheap, all the clones share the same records.
+/// Create it once, for example in the schema data, and use it to create the
+/// `DataLoader` of every request, so the records loaded by one request are
+/// used by the following requests.
+///
+/// Mutations can use [`SharedCache::invalidate`] and
+/// [`SharedCache::invalidate_if`] to remove the records that have changed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{collections::HashMap, convert::Infallible, time::Duration};
+///
+/// use async_graphql::dataloader::*;
+///
+/// struct UserNameLoader;
+///
+/// #[async_trait::async_trait]
+/// impl Loader<u64> for UserNameLoader {
+///     type Value = String;
+///     type Error = Infallible;
+///
+///     async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+///         Ok(keys.iter().map(|id| (*id, format!("user{}", id))).collect())
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let cache = SharedCache::new(Duration::from_secs(60));
+///
+/// // The records loaded by this loader are cached ...
+/// let loader = DataLoader::with_cache(UserNameLoader, tokio::spawn, cache.clone());
+/// assert_eq!(loader.load_one(1).await.unwrap().as_deref(), Some("user1"));
+///
+/// // ... and are used by the loaders of the following requests.
+/// let loader = DataLoader::with_cache(UserNameLoader, tokio::spawn, cache.clone());
+/// assert_eq!(loader.load_one(1).await.unwrap().as_deref(), Some("user1"));
+///
+/// // Remove the record after the user has been changed.
+/// cache.invalidate::<UserNameLoader, _>(&1);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct SharedCache {
+    ttl: Duration,
+    stores: Arc<Mutex<FnvHashMap<TypeId, Box<dyn ErasedSharedStore>>>>,
+}
+
+type SharedStoreRef<K, V> = Arc<Mutex<SharedStore<K, V>>>;
+
+trait ErasedSharedStore: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn clear(&self);
+}
+
+impl<K, V> ErasedSharedStore for SharedStoreRef<K, V>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clear(&self) {
+        self.lock().unwrap().records.clear();
+    }
+}
+
+struct SharedStore<K, V> {
+    records: HashMap<K, (V, Instant)>,
+    last_pruned: Instant,
+}
+
+impl<K: Eq + Hash, V> SharedStore<K, V> {
+    /// Removes the expired records, at most once in the time to live.
+    fn prune(&mut self, now: Instant, ttl: Duration) {
+        if now.duration_since(self.last_pruned) >= ttl {
+            self.records.retain(|_, (_, expires_at)| *expires_at > now);
+            self.last_pruned = now;
+        }
+    }
+}
+
+impl SharedCache {
+    /// Creates a shared cache whose records expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            stores: Default::default(),
+        }
+    }
+
+    fn store<T, K, V>(&self) -> SharedStoreRef<K, V>
+    where
+        T: 'static,
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.stores
+            .lock()
+            .unwrap()
+            .entry(TypeId::of::<(T, K, V)>())
+            .or_insert_with(|| {
+                Box::new(SharedStoreRef::<K, V>::new(Mutex::new(SharedStore {
+                    records: HashMap::new(),
+                    last_pruned: Instant::now(),
+                })))
+            })
+            .as_any()
+            .downcast_ref::<SharedStoreRef<K, V>>()
+            .unwrap()
+            .clone()
+    }
+
+    /// Removes the record of the key loaded by the loader `T`.
+    pub fn invalidate<T, K>(&self, key: &K)
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        T: Loader<K>,
+    {
+        self.store::<T, K, T::Value>()
+            .lock()
+            .unwrap()
+            .records
+            .remove(key);
+    }
+
+    /// Removes the records loaded by the loader `T` for which the predicate
+    /// returns `true`.
+    pub fn invalidate_if<T, K, F>(&self, mut predicate: F)
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        T: Loader<K>,
+        F: FnMut(&K, &T::Value) -> bool,
+    {
+        self.store::<T, K, T::Value>()
+            .lock()
+            .unwrap()
+            .records
+            .retain(|key, (value, _)| !predicate(key, value));
+    }
+
+    /// Removes the record of the key loaded by the [`PartialLoader`] `T`.
+    pub fn invalidate_partial<T, K>(&self, key: &K)
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        T: PartialLoader<K>,
+    {
+        self.store::<T, K, Result<T::Value, Arc<T::Error>>>()
+            .lock()
+            .unwrap()
+            .records
+            .remove(key);
+    }
+
+    /// Removes the records loaded by the [`PartialLoader`] `T` for which the
+    /// predicate returns `true`.
+    pub fn invalidate_partial_if<T, K, F>(&self, mut predicate: F)
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        T: PartialLoader<K>,
+        F: FnMut(&K, &T::Value) -> bool,
+    {
+        self.store::<T, K, Result<T::Value, Arc<T::Error>>>()
+            .lock()
+            .unwrap()
+            .records
+            .retain(|key, (value, _)| match value {
+                Ok(value) => !predicate(key, value),
+                Err(_) => false,
+            });
+    }
+
+    /// Removes all the records.
+    pub fn invalidate_all(&self) {
+        for store in self.stores.lock().unwrap().values() {
+            store.clear();
+        }
+    }
+}
+
+impl CacheFactory for SharedCache {
+    fn create<K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        self.create_for::<(), K, V>()
+    }
+
+    fn create_for<T, K, V>(&self) -> Box<dyn CacheStorage<Key = K, Value = V>>
+    where
+        T: 'static,
+        K: Send + Sync + Clone + Eq + Hash + 'static,
+        V: Send + Sync + Clone + 'static,
+    {
+        Box::new(SharedCacheImpl {
+            store: self.store::<T, K, V>(),
+            ttl: self.ttl,
+            value: None,
+        })
+    }
+}
+
+struct SharedCacheImpl<K, V> {
+    store: SharedStoreRef<K, V>,
+    ttl: Duration,
+    /// The last value returned by `get`, the records can not be borrowed from
+    /// the shared store.
+    value: Option<V>,
+}
+
+impl<K, V> CacheStorage for SharedCacheImpl<K, V>
+where
+    K: Send + Sync + Clone + Eq + Hash + 'static,
+    V: Send + Sync + Clone + 'static,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&mut self, key: &Self::Key) -> Option<&Self::Value> {
+        let mut store = self.store.lock().unwrap();
+        self.value = match store.records.get(key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                store.records.remove(key);
+                None
+            }
+            None => None,
+        };
+        self.value.as_ref()
+    }
+
+    fn insert(&mut self, key: Cow<'_, Self::Key>, val: Cow<'_, Self::Value>) {
+        let now = Instant::now();
+        let mut store = self.store.lock().unwrap();
+        store.prune(now, self.ttl);
+        store
+            .records
+            .insert(key.into_owned(), (val.into_owned(), now + self.ttl));
+    }
+
+    fn remove(&mut self, key: &Self::Key) {
+        self.store.lock().unwrap().records.remove(key);
+    }
+
+    fn clear(&mut self) {
+        self.store.lock().unwrap().records.clear();
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context as TaskContext, Poll, Waker},
+};
+
+use futures_util::task::{waker, ArcWake, AtomicWaker};
+use pin_project_lite::pin_project;
+
+thread_local! {
+    /// The level of the query that is being polled on this thread.
+    static CURRENT_LEVEL: RefCell<Option<Arc<Level>>> = const { RefCell::new(None) };
+}
+
+/// The outermost `resolve_container` of a query, the inline loaders wait
+/// until all of its fields are blocked before loading their batches.
+#[derive(Default)]
+struct Level {
+    state: Mutex<LevelState>,
+}
+
+#[derive(Default)]
+struct LevelState {
+    flushes: usize,
+    waiters: Vec<Waker>,
+}
+
+impl Level {
+    /// Wakes the loaders waiting for the level to be blocked, returns `false`
+    /// if there are none.
+    fn flush(&self) -> bool {
+        let waiters = {
+            let mut state = self.state.lock().unwrap();
+            if state.waiters.is_empty() {
+                return false;
+            }
+            state.flushes += 1;
+            mem::take(&mut state.waiters)
+        };
+        for waiter in waiters {
+            waiter.wake();
+        }
+        true
+    }
+}
+
+/// Records whether a future of the level has been woken while it was polled.
+struct LevelWaker {
+    woken: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl ArcWake for LevelWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::SeqCst);
+        arc_self.waker.wake();
+    }
+}
+
+/// Restores the previous level when the poll returns or panics.
+struct EnterLevel(Option<Arc<Level>>);
+
+impl EnterLevel {
+    fn new(level: Arc<Level>) -> Self {
+        Self(CURRENT_LEVEL.with(|current| current.replace(Some(level))))
+    }
+}
+
+impl Drop for EnterLevel {
+    fn drop(&mut self) {
+        CURRENT_LEVEL.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+pin_project! {
+    /// A future that resolves the fields of a container, and flushes the
+    /// batches of the inline loaders when none of the fields can make
+    /// progress.
+    pub(crate) struct DriveBatches<F> {
+        #[pin]
+        fut: F,
+        level: Option<(Arc<Level>, Arc<LevelWaker>, Waker)>,
+    }
+}
+
+/// Drives the batches of the inline loaders used by `fut`.
+///
+/// Only the outermost container of a query flushes the batches, so that the
+/// keys of the nested containers, such as the items of a list, are loaded
+/// together.
+pub(crate) fn drive_batches<F: Future>(fut: F) -> DriveBatches<F> {
+    DriveBatches { fut, level: None }
+}
+
+impl<F: Future> Future for DriveBatches<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if this.level.is_none() && CURRENT_LEVEL.with(|current| current.borrow().is_some()) {
+            // a nested container
+            return this.fut.poll(cx);
+        }
+
+        let (level, level_waker, waker) = this.level.get_or_insert_with(|| {
+            let level_waker = Arc::new(LevelWaker {
+                woken: AtomicBool::new(false),
+                waker: AtomicWaker::new(),
+            });
+            let waker = waker(level_waker.clone());
+            (Default::default(), level_waker, waker)
+        });
+
+        loop {
+            level_waker.woken.store(false, Ordering::SeqCst);
+            level_waker.waker.register(cx.waker());
+
+            let res = {
+                let _enter = EnterLevel::new(level.clone());
+                this.fut.as_mut().poll(&mut TaskContext::from_waker(waker))
+            };
+            if res.is_ready() {
+                return res;
+            }
+
+            // A field that has been woken can still add keys to the batches,
+            // it is polled again before they are loaded.
+            if level_waker.woken.load(Ordering::SeqCst) || !level.flush() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// A future that is ready when the fields of the query that is being polled
+/// are blocked.
+pub(crate) struct Blocked {
+    level: Arc<Level>,
+    flushes: usize,
+}
+
+/// Returns `None` if the loader is not used by the fields of a query.
+pub(crate) fn blocked() -> Option<Blocked> {
+    let level = CURRENT_LEVEL.with(|current| current.borrow().clone())?;
+    let flushes = level.state.lock().unwrap().flushes;
+    Some(Blocked { level, flushes })
+}
+
+impl Future for Blocked {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let mut state = self.level.state.lock().unwrap();
+        if state.flushes != self.flushes {
+            return Poll::Ready(());
+        }
+        if !state
+            .waiters
+            .iter()
+            .any(|waiter| waiter.will_wake(cx.waker()))
+        {
+            state.waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
@@ -57,59 +57,78 @@
 //! ```
 
 mod cache;
+mod level;
+mod observer;
 
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
     collections::{HashMap, HashSet},
+    convert::Infallible,
+    future::Future,
     hash::Hash,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
     },
-    time::Duration,
+    task::{Context as TaskContext, Poll},
+    time::{Duration, Instant},
 };
 
-pub use cache::{CacheFactory, CacheStorage, HashMapCache, LruCache, NoCache};
+pub use cache::{CacheFactory, CacheStorage, HashMapCache, LruCache, NoCache, SharedCache};
 use fnv::FnvHashMap;
 use futures_channel::oneshot;
 use futures_timer::Delay;
 use futures_util::future::BoxFuture;
+pub(crate) use level::drive_batches;
+pub use observer::{BatchInfo, CacheLookupInfo, DataLoaderObserver, LoaderStats, MetricsCollector};
 #[cfg(feature = "tracing")]
 use tracing::{info_span, instrument, Instrument};
 #[cfg(feature = "tracing")]
 use tracinglib as tracing;
 
 #[allow(clippy::type_complexity)]
-struct ResSender<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> {
-    use_cache_values: HashMap<K, T::Value>,
-    tx: oneshot::Sender<Result<HashMap<K, T::Value>, T::Error>>,
+struct ResSender<K, V, E> {
+    use_cache_values: HashMap<K, V>,
+    tx: oneshot::Sender<Result<HashMap<K, V>, E>>,
 }
 
-struct Requests<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> {
+struct Requests<K: Send + Sync + Hash + Eq + Clone + 'static, V: Send + Sync + Clone + 'static, E> {
     keys: HashSet<K>,
-    pending: Vec<(HashSet<K>, ResSender<K, T>)>,
-    cache_storage: Box<dyn CacheStorage<Key = K, Value = T::Value>>,
+    pending: Vec<(HashSet<K>, ResSender<K, V, E>)>,
+    cache_storage: Box<dyn CacheStorage<Key = K, Value = V>>,
     disable_cache: bool,
+    started: Option<Instant>,
 }
 
-type KeysAndSender<K, T> = (HashSet<K>, Vec<(HashSet<K>, ResSender<K, T>)>);
+struct Batch<K, V, E> {
+    keys: HashSet<K>,
+    senders: Vec<(HashSet<K>, ResSender<K, V, E>)>,
+    started: Option<Instant>,
+}
 
-impl<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> Requests<K, T> {
-    fn new<C: CacheFactory>(cache_factory: &C) -> Self {
+impl<K, V, E> Requests<K, V, E>
+where
+    K: Send + Sync + Hash + Eq + Clone + 'static,
+    V: Send + Sync + Clone + 'static,
+{
+    fn new<T: 'static, C: CacheFactory>(cache_factory: &C) -> Self {
         Self {
             keys: Default::default(),
             pending: Vec::new(),
-            cache_storage: cache_factory.create::<K, T::Value>(),
+            cache_storage: cache_factory.create_for::<T, K, V>(),
             disable_cache: false,
+            started: None,
         }
     }
 
-    fn take(&mut self) -> KeysAndSender<K, T> {
-        (
-            std::mem::take(&mut self.keys),
-            std::mem::take(&mut self.pending),
-        )
+    fn take(&mut self) -> Batch<K, V, E> {
+        Batch {
+            keys: std::mem::take(&mut self.keys),
+            senders: std::mem::take(&mut self.pending),
+            started: self.started.take(),
+        }
     }
 }
 
@@ -126,36 +145,197 @@ pub trait Loader<K: Send + Sync + Hash + Eq + Clone + 'static>: Send + Sync + 's
     async fn load(&self, keys: &[K]) -> Result<HashMap<K, Self::Value>, Self::Error>;
 }
 
+/// Trait for batch loading with an error for each key.
+///
+/// Unlike [`Loader`], a failure only affects the callers that requested the
+/// failed key, and the error type does not need to implement `Clone`. Use
+/// [`DataLoader::load_one_partial`] and [`DataLoader::load_many_partial`] to
+/// load data with this trait, and the other `_partial` methods of
+/// [`DataLoader`] to manage its cache.
+///
+/// Failed keys are never cached.
+#[async_trait::async_trait]
+pub trait PartialLoader<K: Send + Sync + Hash + Eq + Clone + 'static>:
+    Send + Sync + 'static
+{
+    /// type of value.
+    type Value: Send + Sync + Clone + 'static;
+
+    /// Type of error.
+    type Error: Send + Sync + 'static;
+
+    /// Load the data set specified by the `keys`.
+    ///
+    /// Keys that are missing from the returned map are considered to have no
+    /// value.
+    async fn load(&self, keys: &[K]) -> HashMap<K, Result<Self::Value, Self::Error>>;
+}
+
+type FetchResult<'a, K, V, E> = BoxFuture<'a, Result<HashMap<K, V>, E>>;
+
+type ResReceiver<K, V, E> = oneshot::Receiver<Result<HashMap<K, V>, E>>;
+
+/// Selects how a batch is loaded by a [`Loader`] or a [`PartialLoader`].
+trait Fetcher<K: Send + Sync + Hash + Eq + Clone + 'static, T>: 'static {
+    type Value: Send + Sync + Clone + 'static;
+    type Error: Send + Clone + 'static;
+
+    fn fetch<'a>(loader: &'a T, keys: &'a [K]) -> FetchResult<'a, K, Self::Value, Self::Error>;
+
+    fn is_error(_value: &Self::Value) -> bool {
+        false
+    }
+}
+
+struct FetchAll;
+
+impl<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> Fetcher<K, T> for FetchAll {
+    type Value = T::Value;
+    type Error = T::Error;
+
+    fn fetch<'a>(loader: &'a T, keys: &'a [K]) -> FetchResult<'a, K, Self::Value, Self::Error> {
+        loader.load(keys)
+    }
+}
+
+struct FetchPartial;
+
+impl<K: Send + Sync + Hash + Eq + Clone + 'static, T: PartialLoader<K>> Fetcher<K, T>
+    for FetchPartial
+{
+    type Value = Result<T::Value, Arc<T::Error>>;
+    type Error = Infallible;
+
+    fn fetch<'a>(loader: &'a T, keys: &'a [K]) -> FetchResult<'a, K, Self::Value, Self::Error> {
+        Box::pin(async move {
+            Ok(loader
+                .load(keys)
+                .await
+                .into_iter()
+                .map(|(key, res)| (key, res.map_err(Arc::new)))
+                .collect())
+        })
+    }
+
+    fn is_error(value: &Self::Value) -> bool {
+        value.is_err()
+    }
+}
+
+type FetcherRequests<K, T, F> =
+    Requests<K, <F as Fetcher<K, T>>::Value, <F as Fetcher<K, T>>::Error>;
+
+enum Action<K, V, E> {
+    ImmediateLoad(Batch<K, V, E>),
+    StartFetch,
+    Delay,
+}
+
+/// Yields to the executor once, so that the other pending futures can make
+/// progress.
+///
+/// Only used when the loader is not used by the fields of a query.
+#[derive(Default)]
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
 struct DataLoaderInner<T> {
     requests: Mutex<FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>>,
     loader: T,
 }
 
 impl<T> DataLoaderInner<T> {
-    #[cfg_attr(feature = "tracing", instrument(skip_all))]
-    async fn do_load<K>(&self, disable_cache: bool, (keys, senders): KeysAndSender<K, T>)
+    fn pending_keys<K, F>(&self) -> usize
     where
         K: Send + Sync + Hash + Eq + Clone + 'static,
-        T: Loader<K>,
+        F: Fetcher<K, T>,
     {
-        let tid = TypeId::of::<K>();
-        let keys = keys.into_iter().collect::<Vec<_>>();
+        let tid = TypeId::of::<FetcherRequests<K, T, F>>();
+        let requests = self.requests.lock().unwrap();
+        requests
+            .get(&tid)
+            .and_then(|typed_requests| typed_requests.downcast_ref::<FetcherRequests<K, T, F>>())
+            .map(|typed_requests| typed_requests.keys.len())
+            .unwrap_or_default()
+    }
+
+    fn take<K, F>(&self) -> Batch<K, F::Value, F::Error>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        F: Fetcher<K, T>,
+    {
+        let tid = TypeId::of::<FetcherRequests<K, T, F>>();
+        let mut requests = self.requests.lock().unwrap();
+        let typed_requests = requests
+            .get_mut(&tid)
+            .unwrap()
+            .downcast_mut::<FetcherRequests<K, T, F>>()
+            .unwrap();
+        typed_requests.take()
+    }
 
-        match self.loader.load(&keys).await {
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    async fn do_load<K, F>(
+        &self,
+        disable_cache: bool,
+        observer: Option<&dyn DataLoaderObserver>,
+        batch: Batch<K, F::Value, F::Error>,
+    ) where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        F: Fetcher<K, T>,
+    {
+        let tid = TypeId::of::<FetcherRequests<K, T, F>>();
+        let keys = batch.keys.into_iter().collect::<Vec<_>>();
+        let senders = batch.senders;
+        let load_start = observer.map(|_| Instant::now());
+        let res = F::fetch(&self.loader, &keys).await;
+
+        if let (Some(observer), Some(load_start)) = (observer, load_start) {
+            observer.batch_loaded(&BatchInfo {
+                loader: std::any::type_name::<T>(),
+                batch_size: keys.len(),
+                errors: match &res {
+                    Ok(values) => values.values().filter(|value| F::is_error(value)).count(),
+                    Err(_) => keys.len(),
+                },
+                wait: batch
+                    .started
+                    .map(|started| load_start.saturating_duration_since(started))
+                    .unwrap_or_default(),
+                duration: load_start.elapsed(),
+            });
+        }
+
+        match res {
             Ok(values) => {
                 // update cache
                 let mut request = self.requests.lock().unwrap();
                 let typed_requests = request
                     .get_mut(&tid)
                     .unwrap()
-                    .downcast_mut::<Requests<K, T>>()
+                    .downcast_mut::<FetcherRequests<K, T, F>>()
                     .unwrap();
                 let disable_cache = typed_requests.disable_cache || disable_cache;
                 if !disable_cache {
                     for (key, value) in &values {
-                        typed_requests
-                            .cache_storage
-                            .insert(Cow::Borrowed(key), Cow::Borrowed(value));
+                        if !F::is_error(value) {
+                            typed_requests
+                                .cache_storage
+                                .insert(Cow::Borrowed(key), Cow::Borrowed(value));
+                        }
                     }
                 }
 
@@ -187,7 +367,8 @@ pub struct DataLoader<T, C = NoCache> {
     delay: Duration,
     max_batch_size: usize,
     disable_cache: AtomicBool,
-    spawner: Box<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>,
+    spawner: Option<Box<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>>,
+    observer: Option<Arc<dyn DataLoaderObserver>>,
 }
 
 impl<T> DataLoader<T, NoCache> {
@@ -205,11 +386,28 @@ impl<T> DataLoader<T, NoCache> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
-            spawner: Box::new(move |fut| {
+            spawner: Some(Box::new(move |fut| {
                 spawner(fut);
-            }),
+            })),
+            observer: None,
         }
     }
+
+    /// Use `Loader` to create a [DataLoader] that does not cache records, and
+    /// loads the batches without spawning tasks.
+    ///
+    /// A batch is loaded by one of the resolvers waiting for it, as soon as
+    /// none of the fields of the query can make progress without it: every
+    /// pending resolver waits for a loader, or for something that has not
+    /// woken it yet, such as I/O or a timer. A resolver that is still waiting
+    /// for something else adds its keys to a later batch. It does not depend
+    /// on an async runtime, and the [`delay`](DataLoader::delay) is ignored.
+    ///
+    /// Outside of a query, the batch is loaded when the futures polled with
+    /// the loading one no longer add keys to it after a turn of the executor.
+    pub fn new_inline(loader: T) -> Self {
+        Self::with_cache_inline(loader, NoCache)
+    }
 }
 
 impl<T, C: CacheFactory> DataLoader<T, C> {
@@ -227,9 +425,29 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
             delay: Duration::from_millis(1),
             max_batch_size: 1000,
             disable_cache: false.into(),
-            spawner: Box::new(move |fut| {
+            spawner: Some(Box::new(move |fut| {
                 spawner(fut);
+            })),
+            observer: None,
+        }
+    }
+
+    /// Use `Loader` to create a [DataLoader] with a cache factory, which loads
+    /// the batches without spawning tasks.
+    ///
+    /// See [`DataLoader::new_inline`].
+    pub fn with_cache_inline(loader: T, cache_factory: C) -> Self {
+        Self {
+            inner: Arc::new(DataLoaderInner {
+                requests: Mutex::new(Default::default()),
+                loader,
             }),
+            cache_factory,
+            delay: Duration::from_millis(1),
+            max_batch_size: 1000,
+            disable_cache: false.into(),
+            spawner: None,
+            observer: None,
         }
     }
 
@@ -252,6 +470,15 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         }
     }
 
+    /// Attach an observer to collect metrics of this `DataLoader`.
+    #[must_use]
+    pub fn observer(self, observer: impl DataLoaderObserver) -> Self {
+        Self {
+            observer: Some(Arc::new(observer)),
+            ..self
+        }
+    }
+
     /// Get the loader.
     #[inline]
     pub fn loader(&self) -> &T {
@@ -269,14 +496,16 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         K: Send + Sync + Hash + Eq + Clone + 'static,
         T: Loader<K>,
     {
-        let tid = TypeId::of::<K>();
-        let mut requests = self.inner.requests.lock().unwrap();
-        let typed_requests = requests
-            .get_mut(&tid)
-            .unwrap()
-            .downcast_mut::<Requests<K, T>>()
-            .unwrap();
-        typed_requests.disable_cache = !enable;
+        self.with_requests::<K, FetchAll, _>(|requests| requests.disable_cache = !enable);
+    }
+
+    /// Enable/Disable cache of specified [`PartialLoader`].
+    pub fn enable_partial_cache<K>(&self, enable: bool)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: PartialLoader<K>,
+    {
+        self.with_requests::<K, FetchPartial, _>(|requests| requests.disable_cache = !enable);
     }
 
     /// Use this `DataLoader` load a data.
@@ -298,107 +527,210 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         I: IntoIterator<Item = K>,
         T: Loader<K>,
     {
-        enum Action<K: Send + Sync + Hash + Eq + Clone + 'static, T: Loader<K>> {
-            ImmediateLoad(KeysAndSender<K, T>),
-            StartFetch,
-            Delay,
+        self.load_many_with::<K, I, FetchAll>(keys).await
+    }
+
+    /// Use this `DataLoader` to load a data with a [`PartialLoader`].
+    ///
+    /// Only the error of the specified key is returned.
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    pub async fn load_one_partial<K>(&self, key: K) -> Result<Option<T::Value>, Arc<T::Error>>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: PartialLoader<K>,
+    {
+        let mut values = self.load_many_partial(std::iter::once(key.clone())).await;
+        values.remove(&key).transpose()
+    }
+
+    /// Use this `DataLoader` to load some data with a [`PartialLoader`].
+    ///
+    /// Each key is mapped to its own result, keys without a value are not
+    /// included.
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    pub async fn load_many_partial<K, I>(
+        &self,
+        keys: I,
+    ) -> HashMap<K, Result<T::Value, Arc<T::Error>>>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = K>,
+        T: PartialLoader<K>,
+    {
+        match self.load_many_with::<K, I, FetchPartial>(keys).await {
+            Ok(values) => values,
+            Err(err) => match err {},
         }
+    }
+
+    async fn load_many_with<K, I, F>(&self, keys: I) -> Result<HashMap<K, F::Value>, F::Error>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = K>,
+        T: Send + Sync + 'static,
+        F: Fetcher<K, T>,
+    {
+        let tid = TypeId::of::<FetcherRequests<K, T, F>>();
+        let keys = keys.into_iter().collect::<Vec<_>>();
 
-        let tid = TypeId::of::<K>();
-
-        let (action, rx) = {
-            let mut requests = self.inner.requests.lock().unwrap();
-            let typed_requests = requests
-                .entry(tid)
-                .or_insert_with(|| Box::new(Requests::<K, T>::new(&self.cache_factory)))
-                .downcast_mut::<Requests<K, T>>()
-                .unwrap();
-            let prev_count = typed_requests.keys.len();
-            let mut keys_set = HashSet::new();
-            let mut use_cache_values = HashMap::new();
-
-            if typed_requests.disable_cache || self.disable_cache.load(Ordering::SeqCst) {
-                keys_set = keys.into_iter().collect();
-            } else {
-                for key in keys {
-                    if let Some(value) = typed_requests.cache_storage.get(&key) {
-                        // Already in cache
-                        use_cache_values.insert(key.clone(), value.clone());
-                    } else {
-                        keys_set.insert(key);
+        loop {
+            let (action, rx) = {
+                let mut requests = self.inner.requests.lock().unwrap();
+                let typed_requests = requests
+                    .entry(tid)
+                    .or_insert_with(|| {
+                        Box::new(FetcherRequests::<K, T, F>::new::<T, _>(&self.cache_factory))
+                    })
+                    .downcast_mut::<FetcherRequests<K, T, F>>()
+                    .unwrap();
+                let prev_count = typed_requests.keys.len();
+                let mut keys_set = HashSet::new();
+                let mut use_cache_values = HashMap::new();
+
+                if typed_requests.disable_cache || self.disable_cache.load(Ordering::SeqCst) {
+                    keys_set = keys.iter().cloned().collect();
+                } else {
+                    for key in keys.iter().cloned() {
+                        if let Some(value) = typed_requests.cache_storage.get(&key) {
+                            // Already in cache
+                            use_cache_values.insert(key.clone(), value.clone());
+                        } else {
+                            keys_set.insert(key);
+                        }
                     }
                 }
-            }
 
-            if !use_cache_values.is_empty() && keys_set.is_empty() {
-                return Ok(use_cache_values);
-            } else if use_cache_values.is_empty() && keys_set.is_empty() {
-                return Ok(Default::default());
-            }
+                if let Some(observer) = &self.observer {
+                    observer.cache_lookup(&CacheLookupInfo {
+                        loader: std::any::type_name::<T>(),
+                        hits: use_cache_values.len(),
+                        misses: keys_set.len(),
+                    });
+                }
 
-            typed_requests.keys.extend(keys_set.clone());
-            let (tx, rx) = oneshot::channel();
-            typed_requests.pending.push((
-                keys_set,
-                ResSender {
-                    use_cache_values,
-                    tx,
-                },
-            ));
+                if !use_cache_values.is_empty() && keys_set.is_empty() {
+                    return Ok(use_cache_values);
+                } else if use_cache_values.is_empty() && keys_set.is_empty() {
+                    return Ok(Default::default());
+                }
 
-            if typed_requests.keys.len() >= self.max_batch_size {
-                (Action::ImmediateLoad(typed_requests.take()), rx)
-            } else {
-                (
-                    if !typed_requests.keys.is_empty() && prev_count == 0 {
-                        Action::StartFetch
-                    } else {
-                        Action::Delay
+                if typed_requests.started.is_none() && self.observer.is_some() {
+                    typed_requests.started = Some(Instant::now());
+                }
+                typed_requests.keys.extend(keys_set.clone());
+                let (tx, rx) = oneshot::channel();
+                typed_requests.pending.push((
+                    keys_set,
+                    ResSender {
+                        use_cache_values,
+                        tx,
                     },
-                    rx,
-                )
+                ));
+
+                if typed_requests.keys.len() >= self.max_batch_size {
+                    (Action::ImmediateLoad(typed_requests.take()), rx)
+                } else {
+                    (
+                        if !typed_requests.keys.is_empty() && prev_count == 0 {
+                            Action::StartFetch
+                        } else {
+                            Action::Delay
+                        },
+                        rx,
+                    )
+                }
+            };
+
+            let spawner = match &self.spawner {
+                Some(spawner) => spawner,
+                None => match self.load_inline::<K, F>(action, rx).await {
+                    Some(res) => return res,
+                    // The resolver loading the batch has been cancelled
+                    None => continue,
+                },
+            };
+
+            match action {
+                Action::ImmediateLoad(batch) => {
+                    let inner = self.inner.clone();
+                    let disable_cache = self.disable_cache.load(Ordering::SeqCst);
+                    let observer = self.observer.clone();
+                    let task = async move {
+                        inner
+                            .do_load::<K, F>(disable_cache, observer.as_deref(), batch)
+                            .await
+                    };
+                    #[cfg(feature = "tracing")]
+                    let task = task.instrument(info_span!("immediate_load"));
+                    spawner(Box::pin(task));
+                }
+                Action::StartFetch => {
+                    let inner = self.inner.clone();
+                    let disable_cache = self.disable_cache.load(Ordering::SeqCst);
+                    let observer = self.observer.clone();
+                    let delay = self.delay;
+
+                    let task = async move {
+                        Delay::new(delay).await;
+
+                        let batch = inner.take::<K, F>();
+
+                        if !batch.keys.is_empty() {
+                            inner
+                                .do_load::<K, F>(disable_cache, observer.as_deref(), batch)
+                                .await
+                        }
+                    };
+                    #[cfg(feature = "tracing")]
+                    let task = task.instrument(info_span!("start_fetch"));
+                    spawner(Box::pin(task))
+                }
+                Action::Delay => {}
             }
-        };
 
-        match action {
-            Action::ImmediateLoad(keys) => {
-                let inner = self.inner.clone();
-                let disable_cache = self.disable_cache.load(Ordering::SeqCst);
-                let task = async move { inner.do_load(disable_cache, keys).await };
-                #[cfg(feature = "tracing")]
-                let task = task.instrument(info_span!("immediate_load"));
-                (self.spawner)(Box::pin(task));
-            }
-            Action::StartFetch => {
-                let inner = self.inner.clone();
-                let disable_cache = self.disable_cache.load(Ordering::SeqCst);
-                let delay = self.delay;
-
-                let task = async move {
-                    Delay::new(delay).await;
-
-                    let keys = {
-                        let mut request = inner.requests.lock().unwrap();
-                        let typed_requests = request
-                            .get_mut(&tid)
-                            .unwrap()
-                            .downcast_mut::<Requests<K, T>>()
-                            .unwrap();
-                        typed_requests.take()
-                    };
+            return rx.await.unwrap();
+        }
+    }
 
-                    if !keys.0.is_empty() {
-                        inner.do_load(disable_cache, keys).await
+    async fn load_inline<K, F>(
+        &self,
+        action: Action<K, F::Value, F::Error>,
+        rx: ResReceiver<K, F::Value, F::Error>,
+    ) -> Option<Result<HashMap<K, F::Value>, F::Error>>
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        F: Fetcher<K, T>,
+    {
+        let batch = match action {
+            Action::ImmediateLoad(batch) => batch,
+            Action::StartFetch | Action::Delay => {
+                match level::blocked() {
+                    // Wait until all the fields of the query are blocked
+                    Some(blocked) => blocked.await,
+                    None => {
+                        // Wait until the other futures no longer add keys to the batch
+                        let mut count = self.inner.pending_keys::<K, F>();
+                        loop {
+                            YieldNow::default().await;
+                            let new_count = self.inner.pending_keys::<K, F>();
+                            if new_count == count {
+                                break;
+                            }
+                            count = new_count;
+                        }
                     }
-                };
-                #[cfg(feature = "tracing")]
-                let task = task.instrument(info_span!("start_fetch"));
-                (self.spawner)(Box::pin(task))
+                }
+                self.inner.take::<K, F>()
             }
-            Action::Delay => {}
-        }
+        };
 
-        rx.await.unwrap()
+        if !batch.keys.is_empty() {
+            let disable_cache = self.disable_cache.load(Ordering::SeqCst);
+            self.inner
+                .do_load::<K, F>(disable_cache, self.observer.as_deref(), batch)
+                .await;
+        }
+        rx.await.ok()
     }
 
     /// Feed some data into the cache.
@@ -412,18 +744,7 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         I: IntoIterator<Item = (K, T::Value)>,
         T: Loader<K>,
     {
-        let tid = TypeId::of::<K>();
-        let mut requests = self.inner.requests.lock().unwrap();
-        let typed_requests = requests
-            .entry(tid)
-            .or_insert_with(|| Box::new(Requests::<K, T>::new(&self.cache_factory)))
-            .downcast_mut::<Requests<K, T>>()
-            .unwrap();
-        for (key, value) in values {
-            typed_requests
-                .cache_storage
-                .insert(Cow::Owned(key), Cow::Owned(value));
-        }
+        self.feed_many_with::<K, _, FetchAll>(values);
     }
 
     /// Feed some data into the cache.
@@ -439,6 +760,35 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         self.feed_many(std::iter::once((key, value))).await;
     }
 
+    /// Feed some data into the cache of a [`PartialLoader`].
+    ///
+    /// **NOTE: If the cache type is [NoCache], this function will not take
+    /// effect. **
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    pub async fn feed_many_partial<K, I>(&self, values: I)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = (K, T::Value)>,
+        T: PartialLoader<K>,
+    {
+        self.feed_many_with::<K, _, FetchPartial>(
+            values.into_iter().map(|(key, value)| (key, Ok(value))),
+        );
+    }
+
+    /// Feed a data into the cache of a [`PartialLoader`].
+    ///
+    /// **NOTE: If the cache type is [NoCache], this function will not take
+    /// effect. **
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    pub async fn feed_one_partial<K>(&self, key: K, value: T::Value)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: PartialLoader<K>,
+    {
+        self.feed_many_partial(std::iter::once((key, value))).await;
+    }
+
     /// Clears the cache.
     ///
     /// **NOTE: If the cache type is [NoCache], this function will not take
@@ -449,14 +799,54 @@ impl<T, C: CacheFactory> DataLoader<T, C> {
         K: Send + Sync + Hash + Eq + Clone + 'static,
         T: Loader<K>,
     {
-        let tid = TypeId::of::<K>();
+        self.with_requests::<K, FetchAll, _>(|requests| requests.cache_storage.clear());
+    }
+
+    /// Clears the cache of a [`PartialLoader`].
+    ///
+    /// **NOTE: If the cache type is [NoCache], this function will not take
+    /// effect. **
+    #[cfg_attr(feature = "tracing", instrument(skip_all))]
+    pub fn clear_partial<K>(&self)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: PartialLoader<K>,
+    {
+        self.with_requests::<K, FetchPartial, _>(|requests| requests.cache_storage.clear());
+    }
+
+    fn feed_many_with<K, I, F>(&self, values: I)
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        I: IntoIterator<Item = (K, F::Value)>,
+        T: Send + Sync + 'static,
+        F: Fetcher<K, T>,
+    {
+        self.with_requests::<K, F, _>(|requests| {
+            for (key, value) in values {
+                requests
+                    .cache_storage
+                    .insert(Cow::Owned(key), Cow::Owned(value));
+            }
+        });
+    }
+
+    fn with_requests<K, F, R>(&self, f: impl FnOnce(&mut FetcherRequests<K, T, F>) -> R) -> R
+    where
+        K: Send + Sync + Hash + Eq + Clone + 'static,
+        T: Send + Sync + 'static,
+        F: Fetcher<K, T>,
+    {
+        let tid = TypeId::of::<FetcherRequests<K, T, F>>();
         let mut requests = self.inner.requests.lock().unwrap();
         let typed_requests = requests
             .entry(tid)
-            .or_insert_with(|| Box::new(Requests::<K, T>::new(&self.cache_factory)))
-            .downcast_mut::<Requests<K, T>>()
+            .or_insert_with(|| {
+                Box::new(FetcherRequests::<K, T, F>::new::<T, _>(&self.cache_factory))
+            })
+            .downcast_mut::<FetcherRequests<K, T, F>>()
             .unwrap();
-        typed_requests.cache_storage.clear();
+        f(typed_requests)
     }
 }
 
@@ -615,6 +1005,392 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_dataloader_with_shared_cache() {
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Clone, Default)]
+        struct CountLoader(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for CountLoader {
+            type Value = i32;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+                self.0.fetch_add(keys.len(), Ordering::SeqCst);
+                Ok(keys.iter().copied().map(|k| (k, k * 10)).collect())
+            }
+        }
+
+        let count = CountLoader::default();
+        let cache = SharedCache::new(Duration::from_secs(60));
+
+        let loader = DataLoader::with_cache(count.clone(), tokio::spawn, cache.clone());
+        assert_eq!(
+            loader.load_many(vec![1, 2, 3]).await.unwrap(),
+            vec![(1, 10), (2, 20), (3, 30)].into_iter().collect()
+        );
+        assert_eq!(count.0.load(Ordering::SeqCst), 3);
+
+        // Another loader uses the records of the shared cache
+        let loader = DataLoader::with_cache(count.clone(), tokio::spawn, cache.clone());
+        assert_eq!(
+            loader.load_many(vec![1, 2, 3, 4]).await.unwrap(),
+            vec![(1, 10), (2, 20), (3, 30), (4, 40)]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(count.0.load(Ordering::SeqCst), 4);
+
+        cache.invalidate::<CountLoader, _>(&1);
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(10));
+        assert_eq!(count.0.load(Ordering::SeqCst), 5);
+
+        cache.invalidate_if::<CountLoader, _, _>(|key, _| *key > 2);
+        assert_eq!(
+            loader.load_many(vec![1, 2, 3, 4]).await.unwrap(),
+            vec![(1, 10), (2, 20), (3, 30), (4, 40)]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(count.0.load(Ordering::SeqCst), 7);
+
+        cache.invalidate_all();
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(10));
+        assert_eq!(count.0.load(Ordering::SeqCst), 8);
+
+        // The records expire after the time to live
+        let cache = SharedCache::new(Duration::from_millis(50));
+        let loader = DataLoader::with_cache(count.clone(), tokio::spawn, cache.clone());
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(10));
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(10));
+        assert_eq!(count.0.load(Ordering::SeqCst), 9);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(loader.load_one(1).await.unwrap(), Some(10));
+        assert_eq!(count.0.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn test_shared_cache_keeps_loaders_apart() {
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Clone, Default)]
+        struct NameLoader(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for NameLoader {
+            type Value = String;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+                self.0.fetch_add(keys.len(), Ordering::SeqCst);
+                Ok(keys.iter().map(|k| (*k, format!("name{}", k))).collect())
+            }
+        }
+
+        #[derive(Clone, Default)]
+        struct EmailLoader(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for EmailLoader {
+            type Value = String;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+                self.0.fetch_add(keys.len(), Ordering::SeqCst);
+                Ok(keys.iter().map(|k| (*k, format!("email{}", k))).collect())
+            }
+        }
+
+        let name_count = NameLoader::default();
+        let email_count = EmailLoader::default();
+        let cache = SharedCache::new(Duration::from_secs(60));
+        let names = DataLoader::with_cache(name_count.clone(), tokio::spawn, cache.clone());
+        let emails = DataLoader::with_cache(email_count.clone(), tokio::spawn, cache.clone());
+
+        // The loaders have the same key and value types, but their records are
+        // not mixed up
+        assert_eq!(names.load_one(1).await.unwrap().as_deref(), Some("name1"));
+        assert_eq!(emails.load_one(1).await.unwrap().as_deref(), Some("email1"));
+        assert_eq!(names.load_one(1).await.unwrap().as_deref(), Some("name1"));
+        assert_eq!(emails.load_one(1).await.unwrap().as_deref(), Some("email1"));
+        assert_eq!(name_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(email_count.0.load(Ordering::SeqCst), 1);
+
+        // Only the records of the specified loader are removed
+        cache.invalidate::<NameLoader, _>(&1);
+        assert_eq!(names.load_one(1).await.unwrap().as_deref(), Some("name1"));
+        assert_eq!(emails.load_one(1).await.unwrap().as_deref(), Some("email1"));
+        assert_eq!(name_count.0.load(Ordering::SeqCst), 2);
+        assert_eq!(email_count.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shared_cache_partial() {
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Clone, Default)]
+        struct PartialCountLoader(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl PartialLoader<i32> for PartialCountLoader {
+            type Value = i32;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> HashMap<i32, Result<Self::Value, Self::Error>> {
+                self.0.fetch_add(keys.len(), Ordering::SeqCst);
+                keys.iter().map(|k| (*k, Ok(*k))).collect()
+            }
+        }
+
+        let count = PartialCountLoader::default();
+        let cache = SharedCache::new(Duration::from_secs(60));
+        let loader = DataLoader::with_cache(count.clone(), tokio::spawn, cache.clone());
+
+        loader.load_many_partial(vec![1, 2, 3]).await;
+        loader.load_many_partial(vec![1, 2, 3]).await;
+        assert_eq!(count.0.load(Ordering::SeqCst), 3);
+
+        cache.invalidate_partial::<PartialCountLoader, _>(&1);
+        loader.load_many_partial(vec![1, 2, 3]).await;
+        assert_eq!(count.0.load(Ordering::SeqCst), 4);
+
+        cache.invalidate_partial_if::<PartialCountLoader, i32, _>(|_, value| *value >= 2);
+        loader.load_many_partial(vec![1, 2, 3]).await;
+        assert_eq!(count.0.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_partial() {
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Debug, PartialEq)]
+        struct OddKey(i32);
+
+        #[derive(Clone, Default)]
+        struct PartialCountLoader(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl PartialLoader<i32> for PartialCountLoader {
+            type Value = i32;
+            type Error = OddKey;
+
+            async fn load(&self, keys: &[i32]) -> HashMap<i32, Result<Self::Value, Self::Error>> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                keys.iter()
+                    .copied()
+                    .filter(|k| *k != 0)
+                    .map(|k| (k, if k % 2 == 0 { Ok(k) } else { Err(OddKey(k)) }))
+                    .collect()
+            }
+        }
+
+        let count = PartialCountLoader::default();
+        let loader = Arc::new(DataLoader::with_cache(
+            count.clone(),
+            tokio::spawn,
+            HashMapCache::default(),
+        ));
+        let mut handles = Vec::new();
+        for i in 0..4 {
+            let loader = loader.clone();
+            handles.push(tokio::spawn(
+                async move { loader.load_one_partial(i).await },
+            ));
+        }
+        let results = futures_util::future::try_join_all(handles).await.unwrap();
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(results[0], Ok(None));
+        assert_eq!(results[1].as_ref().unwrap_err().as_ref(), &OddKey(1));
+        assert_eq!(results[2], Ok(Some(2)));
+        assert_eq!(results[3].as_ref().unwrap_err().as_ref(), &OddKey(3));
+
+        // Successful values are cached, errors are not
+        let values = loader.load_many_partial(vec![2, 3]).await;
+        assert_eq!(count.0.load(Ordering::SeqCst), 2);
+        assert_eq!(values[&2].as_ref().ok(), Some(&2));
+        assert!(values[&3].is_err());
+        assert_eq!(loader.load_one_partial(2).await.unwrap(), Some(2));
+        assert_eq!(count.0.load(Ordering::SeqCst), 2);
+
+        // Fed values are used instead of loading them
+        loader.feed_one_partial(5, 50).await;
+        loader.feed_many_partial(vec![(7, 70)]).await;
+        assert_eq!(loader.load_one_partial(5).await.unwrap(), Some(50));
+        assert_eq!(loader.load_one_partial(7).await.unwrap(), Some(70));
+        assert_eq!(count.0.load(Ordering::SeqCst), 2);
+
+        loader.clear_partial::<i32>();
+        assert_eq!(loader.load_one_partial(2).await.unwrap(), Some(2));
+        assert_eq!(count.0.load(Ordering::SeqCst), 3);
+
+        loader.enable_partial_cache::<i32>(false);
+        assert_eq!(loader.load_one_partial(2).await.unwrap(), Some(2));
+        assert_eq!(count.0.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_observer() {
+        let metrics = MetricsCollector::new();
+        let loader = Arc::new(
+            DataLoader::with_cache(MyLoader, tokio::spawn, HashMapCache::default())
+                .max_batch_size(10)
+                .observer(metrics.clone()),
+        );
+        let mut handles = Vec::new();
+        for i in 0..25 {
+            let loader = loader.clone();
+            handles.push(tokio::spawn(async move { loader.load_one(i).await }));
+        }
+        futures_util::future::try_join_all(handles).await.unwrap();
+        loader.load_many(vec![1, 2, 30]).await.unwrap();
+
+        let stats = &metrics.stats()[std::any::type_name::<MyLoader>()];
+        assert_eq!(stats.batches, 4);
+        assert_eq!(stats.keys, 26);
+        assert_eq!(stats.max_batch_size, 10);
+        assert_eq!(stats.cache_hits, 2);
+        assert_eq!(stats.cache_misses, 26);
+        assert_eq!(stats.errors, 0);
+        assert!(stats.load_time >= stats.max_load_time);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_inline() {
+        let metrics = MetricsCollector::new();
+        let loader = DataLoader::new_inline(MyLoader)
+            .max_batch_size(10)
+            .observer(metrics.clone());
+        assert_eq!(
+            futures_util::future::try_join_all((0..25i32).map(|n| loader.load_one(n)))
+                .await
+                .unwrap(),
+            (0..25).map(Option::Some).collect::<Vec<_>>()
+        );
+
+        let stats = &metrics.stats()[std::any::type_name::<MyLoader>()];
+        assert_eq!(stats.batches, 3);
+        assert_eq!(stats.keys, 25);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_inline_awaiting_sibling() {
+        let metrics = MetricsCollector::new();
+        let loader = DataLoader::new_inline(MyLoader).observer(metrics.clone());
+        let (a, b, c) = futures_util::future::join3(
+            loader.load_one(1),
+            async {
+                // Awaiting a ready future does not leave the batch
+                futures_util::future::ready(()).await;
+                loader.load_one(2).await
+            },
+            async {
+                // Outside of a query, waiting for the executor misses the batch
+                tokio::task::yield_now().await;
+                tokio::task::yield_now().await;
+                loader.load_one(3).await
+            },
+        )
+        .await;
+        assert_eq!((a, b, c), (Ok(Some(1)), Ok(Some(2)), Ok(Some(3))));
+
+        let stats = &metrics.stats()[std::any::type_name::<MyLoader>()];
+        assert_eq!(stats.batches, 2);
+        assert_eq!(stats.keys, 3);
+        assert_eq!(stats.max_batch_size, 2);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_inline_query() {
+        use crate::{value, Context, EmptyMutation, EmptySubscription, Object, Request, Schema};
+
+        struct Item(i32);
+
+        #[Object(internal)]
+        impl Item {
+            async fn value(&self, ctx: &Context<'_>) -> Option<i32> {
+                if self.0 % 2 == 1 {
+                    // A future that is woken while the query is polled does
+                    // not miss the batch
+                    YieldNow::default().await;
+                    YieldNow::default().await;
+                }
+                ctx.data_unchecked::<DataLoader<MyLoader>>()
+                    .load_one(self.0)
+                    .await
+                    .unwrap()
+            }
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn items(&self) -> Vec<Item> {
+                (0..6).map(Item).collect()
+            }
+
+            async fn value(&self, ctx: &Context<'_>) -> Option<i32> {
+                ctx.data_unchecked::<DataLoader<MyLoader>>()
+                    .load_one(100)
+                    .await
+                    .unwrap()
+            }
+        }
+
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let metrics = MetricsCollector::new();
+        let loader = DataLoader::new_inline(MyLoader).observer(metrics.clone());
+        let resp = schema
+            .execute(Request::new("{ items { value } value }").data(loader))
+            .await;
+        assert_eq!(
+            resp.into_result().unwrap().data,
+            value!({
+                "items": [
+                    { "value": 0 },
+                    { "value": 1 },
+                    { "value": 2 },
+                    { "value": 3 },
+                    { "value": 4 },
+                    { "value": 5 },
+                ],
+                "value": 100,
+            })
+        );
+
+        let stats = &metrics.stats()[std::any::type_name::<MyLoader>()];
+        assert_eq!(stats.batches, 1);
+        assert_eq!(stats.keys, 7);
+    }
+
+    #[tokio::test]
+    async fn test_dataloader_inline_cancel() {
+        struct MyDelayLoader;
+
+        #[async_trait::async_trait]
+        impl Loader<i32> for MyDelayLoader {
+            type Value = i32;
+            type Error = ();
+
+            async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Self::Value>, Self::Error> {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(keys.iter().copied().map(|k| (k, k)).collect())
+            }
+        }
+
+        // The future loading the batch is dropped, the other one loads its keys again
+        let loader = DataLoader::new_inline(MyDelayLoader);
+        let (a, b) = futures_util::future::join(
+            loader.load_one(1),
+            tokio::time::timeout(Duration::from_millis(10), loader.load_one(2)),
+        )
+        .await;
+        assert_eq!(a, Ok(Some(1)));
+        assert!(b.is_err());
+    }
+
     #[tokio::test]
     async fn test_dataloader_disable_all_cache() {
         let loader = DataLoader::with_cache(MyLoader, tokio::spawn, HashMapCache::default());
@@ -0,0 +1,193 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Information about a cache lookup of a [`DataLoader`](super::DataLoader).
+#[derive(Debug, Copy, Clone)]
+pub struct CacheLookupInfo {
+    /// The type name of the loader.
+    pub loader: &'static str,
+
+    /// Number of keys found in the cache.
+    pub hits: usize,
+
+    /// Number of keys that need to be loaded.
+    pub misses: usize,
+}
+
+/// Information about a batch loaded by a [`DataLoader`](super::DataLoader).
+#[derive(Debug, Copy, Clone)]
+pub struct BatchInfo {
+    /// The type name of the loader.
+    pub loader: &'static str,
+
+    /// Number of keys in the batch.
+    pub batch_size: usize,
+
+    /// Number of keys that failed to load.
+    pub errors: usize,
+
+    /// The time the first key of the batch waited before the batch was
+    /// loaded.
+    pub wait: Duration,
+
+    /// The time it took to load the batch.
+    pub duration: Duration,
+}
+
+/// An observer of a [`DataLoader`](super::DataLoader), used to collect
+/// metrics.
+///
+/// The methods are called synchronously by the data loader, so they should
+/// return quickly.
+pub trait DataLoaderObserver: Send + Sync + 'static {
+    /// Called when the keys to load are looked up in the cache.
+    fn cache_lookup(&self, info: &CacheLookupInfo) {
+        let _ = info;
+    }
+
+    /// Called when a batch has been loaded.
+    fn batch_loaded(&self, info: &BatchInfo) {
+        let _ = info;
+    }
+}
+
+/// Statistics of a loader collected by [`MetricsCollector`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoaderStats {
+    /// Number of batches loaded.
+    pub batches: usize,
+
+    /// Number of keys loaded.
+    pub keys: usize,
+
+    /// Size of the largest batch.
+    pub max_batch_size: usize,
+
+    /// Number of keys found in the cache.
+    pub cache_hits: usize,
+
+    /// Number of keys not found in the cache.
+    pub cache_misses: usize,
+
+    /// Number of keys that failed to load.
+    pub errors: usize,
+
+    /// Total time the batches waited before they were loaded.
+    pub wait_time: Duration,
+
+    /// Total time it took to load the batches.
+    pub load_time: Duration,
+
+    /// The time it took to load the slowest batch.
+    pub max_load_time: Duration,
+}
+
+impl LoaderStats {
+    /// Returns the average number of keys per batch.
+    pub fn average_batch_size(&self) -> f64 {
+        if self.batches == 0 {
+            return 0.0;
+        }
+        self.keys as f64 / self.batches as f64
+    }
+
+    /// Returns the proportion of keys found in the cache.
+    pub fn cache_hit_ratio(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.cache_hits as f64 / lookups as f64
+    }
+}
+
+/// A [`DataLoaderObserver`] that collects [`LoaderStats`] for each loader
+/// type.
+///
+/// Attach it to the data loaders of a request and put it in the request data,
+/// then the [`DataLoaderMetrics`](crate::extensions::DataLoaderMetrics)
+/// extension reports the statistics in the response extensions.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{collections::HashMap, convert::Infallible};
+///
+/// use async_graphql::{dataloader::*, extensions::DataLoaderMetrics, *};
+///
+/// struct MyLoader;
+///
+/// #[async_trait::async_trait]
+/// impl Loader<i32> for MyLoader {
+///     type Value = i32;
+///     type Error = Infallible;
+///
+///     async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, i32>, Infallible> {
+///         Ok(keys.iter().map(|n| (*n, *n)).collect())
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self, ctx: &Context<'_>, n: i32) -> Option<i32> {
+///         ctx.data_unchecked::<DataLoader<MyLoader>>().load_one(n).await.unwrap()
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(DataLoaderMetrics)
+///     .finish();
+///
+/// // Create a collector for each request
+/// let metrics = MetricsCollector::new();
+/// let loader = DataLoader::new(MyLoader, tokio::spawn).observer(metrics.clone());
+/// let request = Request::new("{ a: value(n: 1) b: value(n: 2) }")
+///     .data(loader)
+///     .data(metrics);
+/// let resp = schema.execute(request).await;
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct MetricsCollector {
+    stats: Arc<Mutex<BTreeMap<&'static str, LoaderStats>>>,
+}
+
+impl MetricsCollector {
+    /// Create a metrics collector.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the statistics collected so far, keyed by the type name of
+    /// the loader.
+    pub fn stats(&self) -> BTreeMap<&'static str, LoaderStats> {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+impl DataLoaderObserver for MetricsCollector {
+    fn cache_lookup(&self, info: &CacheLookupInfo) {
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(info.loader).or_default();
+        stats.cache_hits += info.hits;
+        stats.cache_misses += info.misses;
+    }
+
+    fn batch_loaded(&self, info: &BatchInfo) {
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(info.loader).or_default();
+        stats.batches += 1;
+        stats.keys += info.batch_size;
+        stats.max_batch_size = stats.max_batch_size.max(info.batch_size);
+        stats.errors += info.errors;
+        stats.wait_time += info.wait;
+        stats.load_time += info.duration;
+        stats.max_load_time = stats.max_load_time.max(info.duration);
+    }
+}
@@ -104,7 +104,7 @@ impl SchemaInner {
                         })
                         .flatten(),
                 )?,
-                Type::Scalar(_) | Type::Enum(_) => {}
+                Type::Scalar(_) | Type::Enum(_) | Type::Static(_) => {}
             }
         }
 
@@ -342,7 +342,7 @@ impl SchemaInner {
                 // member types of a Union.
                 for type_name in &union.possible_types {
                     if let Some(ty) = self.types.get(type_name) {
-                        if ty.as_object().is_none() {
+                        if !ty.is_object() {
                             return Err(format!(
                                 "Member \"{}\" of union \"{}\" is not an object",
                                 type_name, union.name
@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 
 use crate::{
     dynamic::{misc::NamedTypeRefBuilder, SchemaError, TypeRef},
-    registry::{Deprecation, MetaEnumValue, MetaType, Registry},
+    registry::{Deprecation, MetaEnumValue, MetaType, MetaVisibleFn, Registry},
 };
 
 /// A GraphQL enum item
@@ -11,6 +11,7 @@ pub struct EnumItem {
     pub(crate) name: String,
     pub(crate) description: Option<String>,
     pub(crate) deprecation: Deprecation,
+    pub(crate) visible: Option<MetaVisibleFn>,
 }
 
 impl<T: Into<String>> From<T> for EnumItem {
@@ -20,16 +21,19 @@ impl<T: Into<String>> From<T> for EnumItem {
             name: name.into(),
             description: None,
             deprecation: Deprecation::NoDeprecated,
+            visible: None,
         }
     }
 }
 
 impl EnumItem {
+    /// Create a new EnumItem
     #[inline]
     pub fn new(name: impl Into<String>) -> Self {
         name.into().into()
     }
 
+    /// Set the description
     #[inline]
     pub fn description(self, description: impl Into<String>) -> Self {
         Self {
@@ -37,6 +41,16 @@ impl EnumItem {
             ..self
         }
     }
+
+    /// Set a function that uses to check if the item should be visible in
+    /// introspection
+    #[inline]
+    pub fn visible(self, visible: MetaVisibleFn) -> Self {
+        Self {
+            visible: Some(visible),
+            ..self
+        }
+    }
 }
 
 /// A GraphQL enum type
@@ -97,9 +111,10 @@ impl Enum {
                     name: item.name.as_str().into(),
                     description: item.description.clone(),
                     deprecation: item.deprecation.clone(),
-                    visible: None,
+                    visible: item.visible,
                     inaccessible: false,
                     tags: vec![],
+                    directive_invocations: Vec::new(),
                 },
             );
         }
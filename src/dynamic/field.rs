@@ -10,8 +10,8 @@ use indexmap::IndexMap;
 
 use crate::{
     dynamic::{InputValue, ObjectAccessor, TypeRef},
-    registry::Deprecation,
-    Context, Error, Result, Value,
+    registry::{CacheControl, ComplexityType, ComputeComplexityFn, Deprecation, MetaVisibleFn},
+    Context, Error, Guard, Result, Value,
 };
 
 /// A value returned from the resolver function
@@ -177,6 +177,25 @@ impl<'a> FieldValue<'a> {
         }
     }
 
+    /// Like `as_value`, but also returns the [`Value`] held by a
+    /// [`FieldValue::WithType`] or by an any value, such as the nested JSON
+    /// objects passed to the resolvers of a schema built from SDL.
+    #[inline]
+    pub fn as_inner_value(&self) -> Option<&Value> {
+        match &self {
+            FieldValue::Value(value) => Some(value),
+            FieldValue::WithType { value, .. } => value.as_inner_value(),
+            _ => self.downcast_ref::<Value>(),
+        }
+    }
+
+    /// Like `as_inner_value`, but returns `Result`.
+    #[inline]
+    pub fn try_to_inner_value(&self) -> Result<&Value> {
+        self.as_inner_value()
+            .ok_or_else(|| Error::new("internal: not a Value"))
+    }
+
     /// Like `as_value`, but returns `Result`.
     #[inline]
     pub fn try_to_value(&self) -> Result<&Value> {
@@ -234,6 +253,8 @@ pub struct ResolverContext<'a> {
     pub args: ObjectAccessor<'a>,
     /// Parent value
     pub parent_value: &'a FieldValue<'a>,
+    /// The name of the parent type
+    pub(crate) parent_type: &'a str,
 }
 
 impl<'a> Deref for ResolverContext<'a> {
@@ -266,6 +287,8 @@ impl<'a> FieldFuture<'a> {
 
 type BoxResolverFn = Box<(dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync)>;
 
+pub(crate) type BoxGuard = Box<dyn Guard + Send + Sync>;
+
 /// A GraphQL field
 pub struct Field {
     pub(crate) name: String,
@@ -274,6 +297,10 @@ pub struct Field {
     pub(crate) ty: TypeRef,
     pub(crate) resolver_fn: BoxResolverFn,
     pub(crate) deprecation: Deprecation,
+    pub(crate) guard: Option<BoxGuard>,
+    pub(crate) visible: Option<MetaVisibleFn>,
+    pub(crate) compute_complexity: Option<ComplexityType>,
+    pub(crate) cache_control: CacheControl,
 }
 
 impl Debug for Field {
@@ -284,6 +311,7 @@ impl Debug for Field {
             .field("arguments", &self.arguments)
             .field("ty", &self.ty)
             .field("deprecation", &self.deprecation)
+            .field("cache_control", &self.cache_control)
             .finish()
     }
 }
@@ -303,6 +331,10 @@ impl Field {
             ty: ty.into(),
             resolver_fn: Box::new(resolver_fn),
             deprecation: Deprecation::NoDeprecated,
+            guard: None,
+            visible: None,
+            compute_complexity: None,
+            cache_control: Default::default(),
         }
     }
 
@@ -321,4 +353,93 @@ impl Field {
         self.arguments.insert(input_value.name.clone(), input_value);
         self
     }
+
+    /// Set the guard, the field resolver is only called if the guard passes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, value, Context, Value};
+    ///
+    /// #[derive(Eq, PartialEq)]
+    /// enum Role {
+    ///     Admin,
+    ///     Guest,
+    /// }
+    ///
+    /// let query = Object::new("Query").field(
+    ///     Field::new("secret", TypeRef::STRING, |_| {
+    ///         FieldFuture::new(async move { Ok(Some(Value::from("abc"))) })
+    ///     })
+    ///     .guard(|ctx: &Context<'_>| {
+    ///         if ctx.data_opt::<Role>() == Some(&Role::Admin) {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("Forbidden".into())
+    ///         }
+    ///     }),
+    /// );
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(query.type_name(), None, None)
+    ///     .register(query)
+    ///     .finish()?;
+    ///
+    /// let resp = schema
+    ///     .execute(async_graphql::Request::new("{ secret }").data(Role::Guest))
+    ///     .await;
+    /// assert_eq!(resp.errors[0].message, "Forbidden");
+    ///
+    /// let resp = schema
+    ///     .execute(async_graphql::Request::new("{ secret }").data(Role::Admin))
+    ///     .await;
+    /// assert_eq!(resp.data, value!({ "secret": "abc" }));
+    /// # Ok::<_, SchemaError>(())
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn guard(self, guard: impl Guard + Send + Sync + 'static) -> Self {
+        Self {
+            guard: Some(Box::new(guard)),
+            ..self
+        }
+    }
+
+    /// Set a function that uses to check if the field should be visible in
+    /// introspection
+    #[inline]
+    pub fn visible(self, visible: MetaVisibleFn) -> Self {
+        Self {
+            visible: Some(visible),
+            ..self
+        }
+    }
+
+    /// Set the complexity of the field
+    #[inline]
+    pub fn complexity(self, complexity: usize) -> Self {
+        Self {
+            compute_complexity: Some(ComplexityType::Const(complexity)),
+            ..self
+        }
+    }
+
+    /// Set a function that computes the complexity of the field from its
+    /// arguments and the complexity of its children
+    #[inline]
+    pub fn complexity_fn(self, compute_complexity: ComputeComplexityFn) -> Self {
+        Self {
+            compute_complexity: Some(ComplexityType::Fn(compute_complexity)),
+            ..self
+        }
+    }
+
+    /// Set the cache control of the field
+    #[inline]
+    pub fn cache_control(self, cache_control: CacheControl) -> Self {
+        Self {
+            cache_control,
+            ..self
+        }
+    }
 }
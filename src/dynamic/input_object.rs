@@ -125,6 +125,7 @@ impl InputObject {
                     inaccessible: false,
                     tags: vec![],
                     is_secret: false,
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -1,4 +1,8 @@
-use crate::{dynamic::TypeRef, registry::MetaInputValue, Value};
+use crate::{
+    dynamic::TypeRef,
+    registry::{MetaInputValue, MetaVisibleFn},
+    Value,
+};
 
 /// A GraphQL input value type
 #[derive(Debug)]
@@ -7,6 +11,7 @@ pub struct InputValue {
     pub(crate) description: Option<String>,
     pub(crate) ty: TypeRef,
     pub(crate) default_value: Option<Value>,
+    pub(crate) visible: Option<MetaVisibleFn>,
 }
 
 impl InputValue {
@@ -18,6 +23,7 @@ impl InputValue {
             description: None,
             ty: ty.into(),
             default_value: None,
+            visible: None,
         }
     }
 
@@ -39,6 +45,16 @@ impl InputValue {
         }
     }
 
+    /// Set a function that uses to check if the input value should be visible
+    /// in introspection
+    #[inline]
+    pub fn visible(self, visible: MetaVisibleFn) -> Self {
+        Self {
+            visible: Some(visible),
+            ..self
+        }
+    }
+
     pub(crate) fn to_meta_input_value(&self) -> MetaInputValue {
         MetaInputValue {
             name: self.name.clone(),
@@ -48,10 +64,11 @@ impl InputValue {
                 .default_value
                 .as_ref()
                 .map(std::string::ToString::to_string),
-            visible: None,
+            visible: self.visible,
             inaccessible: false,
             tags: vec![],
             is_secret: false,
+            directive_invocations: Vec::new(),
         }
     }
 }
@@ -213,7 +213,11 @@ impl Interface {
                     inaccessible: false,
                     tags: vec![],
                     override_from: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
                     compute_complexity: None,
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -227,6 +231,8 @@ impl Interface {
                 possible_types: Default::default(),
                 extends: false,
                 keys: None,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: vec![],
@@ -38,9 +38,13 @@ mod input_object;
 mod input_value;
 mod interface;
 mod object;
+mod property;
+mod remote;
 mod resolve;
 mod scalar;
 mod schema;
+mod sdl;
+mod static_type;
 mod subscription;
 mod r#type;
 mod type_ref;
@@ -53,10 +57,12 @@ pub use input_object::InputObject;
 pub use input_value::InputValue;
 pub use interface::{Interface, InterfaceField};
 pub use object::Object;
-pub use r#enum::Enum;
+pub use r#enum::{Enum, EnumItem};
 pub use r#type::Type;
+pub use remote::{RemoteSchema, RemoteTransport};
 pub use scalar::Scalar;
 pub use schema::{Schema, SchemaBuilder};
+pub use sdl::Resolvers;
 pub use subscription::{Subscription, SubscriptionField, SubscriptionFieldFuture};
 pub use type_ref::TypeRef;
 pub use union::Union;
@@ -64,5 +70,8 @@ pub use value_accessor::{ListAccessor, ObjectAccessor, ValueAccessor};
 
 /// Other types
 pub mod misc {
-    pub use super::type_ref::{ListTypeRefBuilder, NamedTypeRefBuilder};
+    pub use super::{
+        static_type::StaticType,
+        type_ref::{ListTypeRefBuilder, NamedTypeRefBuilder},
+    };
 }
@@ -119,16 +119,20 @@ impl Object {
                     args,
                     ty: field.ty.to_string(),
                     deprecation: field.deprecation.clone(),
-                    cache_control: Default::default(),
+                    cache_control: field.cache_control,
                     external: false,
                     requires: None,
                     provides: None,
-                    visible: None,
+                    visible: field.visible,
                     shareable: false,
                     inaccessible: false,
                     tags: vec![],
                     override_from: None,
-                    compute_complexity: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
+                    compute_complexity: field.compute_complexity.clone(),
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -143,11 +147,16 @@ impl Object {
                 extends: false,
                 shareable: false,
                 keys: None,
+                resolvable: true,
+                interface_object: false,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: vec![],
                 is_subscription: false,
                 rust_typename: None,
+                directive_invocations: Vec::new(),
             },
         );
 
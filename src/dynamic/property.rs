@@ -0,0 +1,352 @@
+use crate::{
+    dynamic::{Field, FieldFuture, FieldValue, Object, TypeRef},
+    registry::MetaType,
+    Error, Value,
+};
+
+impl Field {
+    /// Create a GraphQL field that reads the property with the same name from
+    /// the parent value
+    ///
+    /// The parent value must be a JSON value, that is a [`Value`] or a
+    /// [`serde_json::Value`] returned with [`FieldValue::value`],
+    /// [`FieldValue::owned_any`] or [`FieldValue::borrowed_any`]. The objects
+    /// and lists of the property are wrapped recursively, so the fields of a
+    /// nested object type can be created with this function too, and an object
+    /// that has a `__typename` property can be the value of an interface or
+    /// union.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, value};
+    ///
+    /// let user = Object::new("User")
+    ///     .field(Field::property("name", TypeRef::STRING.non_null()))
+    ///     .field(Field::property_path("city", TypeRef::STRING, ["address", "city"]))
+    ///     .field(Field::property("friends", TypeRef::named("User").non_null().list().non_null()));
+    ///
+    /// let query = Object::new("Query").field(Field::new("user", user.type_ref(), |_| {
+    ///     FieldFuture::new(async move {
+    ///         Ok(Some(FieldValue::owned_any(serde_json::json!({
+    ///             "name": "Alice",
+    ///             "address": { "city": "Paris" },
+    ///             "friends": [{ "name": "Bob", "address": null, "friends": [] }],
+    ///         }))))
+    ///     })
+    /// }));
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(query.type_name(), None, None)
+    ///     .register(user)
+    ///     .register(query)
+    ///     .finish()?;
+    ///
+    /// assert_eq!(
+    ///     schema
+    ///         .execute("{ user { name city friends { name city } } }")
+    ///         .await
+    ///         .into_result()
+    ///         .unwrap()
+    ///         .data,
+    ///     value!({
+    ///         "user": {
+    ///             "name": "Alice",
+    ///             "city": "Paris",
+    ///             "friends": [{ "name": "Bob", "city": null }],
+    ///         }
+    ///     })
+    /// );
+    /// # Ok::<_, SchemaError>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn property(name: impl Into<String>, ty: impl Into<TypeRef>) -> Self {
+        let name = name.into();
+        let path = vec![name.clone()];
+        Self::property_path(name, ty, path)
+    }
+
+    /// Create a GraphQL field that reads the property at the key path `path`
+    /// from the parent value
+    ///
+    /// See also [`Field::property`].
+    pub fn property_path<I, K>(name: impl Into<String>, ty: impl Into<TypeRef>, path: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let ty = ty.into();
+        let type_name = ty.type_name().to_string();
+        let path = path.into_iter().map(Into::into).collect::<Vec<String>>();
+        Self::new(name, ty, move |ctx| {
+            let is_leaf = matches!(
+                ctx.schema_env.registry.types.get(&type_name),
+                Some(MetaType::Scalar { .. } | MetaType::Enum { .. })
+            );
+            let value = match JsonValue::from_field_value(ctx.parent_value) {
+                Some(parent) => Ok(parent
+                    .get_path(&path)
+                    .and_then(|value| value.into_field_value(is_leaf))),
+                None => Err(Error::new(format!(
+                    "The field \"{}.{}\" has no resolver, and its parent value is not a JSON value",
+                    ctx.parent_type, ctx.ctx.item.node.name.node
+                ))),
+            };
+            FieldFuture::new(async move { value })
+        })
+    }
+}
+
+impl Object {
+    /// Add a field that reads the property with the same name from the parent
+    /// value, see [`Field::property`]
+    #[inline]
+    pub fn property(self, name: impl Into<String>, ty: impl Into<TypeRef>) -> Self {
+        self.field(Field::property(name, ty))
+    }
+}
+
+/// A borrowed JSON value
+#[derive(Copy, Clone)]
+enum JsonValue<'a> {
+    Value(&'a Value),
+    Json(&'a serde_json::Value),
+}
+
+impl<'a> JsonValue<'a> {
+    fn from_field_value(value: &'a FieldValue<'a>) -> Option<Self> {
+        match value {
+            FieldValue::Value(value) => Some(JsonValue::Value(value)),
+            FieldValue::WithType { value, .. } => Self::from_field_value(value),
+            _ => value
+                .downcast_ref::<Value>()
+                .map(JsonValue::Value)
+                .or_else(|| {
+                    value
+                        .downcast_ref::<serde_json::Value>()
+                        .map(JsonValue::Json)
+                }),
+        }
+    }
+
+    fn get(self, key: &str) -> Option<Self> {
+        match self {
+            JsonValue::Value(Value::Object(object)) => object.get(key).map(JsonValue::Value),
+            JsonValue::Json(serde_json::Value::Object(object)) => {
+                object.get(key).map(JsonValue::Json)
+            }
+            _ => None,
+        }
+    }
+
+    fn get_path(self, path: &[String]) -> Option<Self> {
+        path.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    /// Wraps the lists and objects of a JSON value, the leaf values are
+    /// converted to [`FieldValue::Value`].
+    fn into_field_value(self, is_leaf: bool) -> Option<FieldValue<'a>> {
+        match self {
+            JsonValue::Value(value) => to_field_value(value, is_leaf),
+            JsonValue::Json(serde_json::Value::Null) => None,
+            JsonValue::Json(serde_json::Value::Array(items)) => Some(FieldValue::List(
+                items
+                    .iter()
+                    .map(|item| {
+                        JsonValue::Json(item)
+                            .into_field_value(is_leaf)
+                            .unwrap_or(FieldValue::NULL)
+                    })
+                    .collect(),
+            )),
+            JsonValue::Json(value @ serde_json::Value::Object(object)) if !is_leaf => {
+                let field_value = FieldValue::borrowed_any(value);
+                Some(match object.get("__typename") {
+                    Some(serde_json::Value::String(ty)) => {
+                        FieldValue::with_type(field_value, ty.clone())
+                    }
+                    _ => field_value,
+                })
+            }
+            JsonValue::Json(value) => Value::from_json(value.clone()).ok().map(FieldValue::Value),
+        }
+    }
+}
+
+/// Wraps the lists and objects of a [`Value`], the leaf values are converted
+/// to [`FieldValue::Value`].
+pub(crate) fn to_field_value(value: &Value, is_leaf: bool) -> Option<FieldValue<'_>> {
+    match value {
+        Value::Null => None,
+        Value::List(items) => Some(FieldValue::List(
+            items
+                .iter()
+                .map(|item| to_field_value(item, is_leaf).unwrap_or(FieldValue::NULL))
+                .collect(),
+        )),
+        value if is_leaf => Some(FieldValue::Value(value.clone())),
+        Value::Object(object) => {
+            let value = FieldValue::borrowed_any(value);
+            Some(match object.get("__typename") {
+                Some(Value::String(ty)) => FieldValue::with_type(value, ty.clone()),
+                _ => value,
+            })
+        }
+        value => Some(FieldValue::Value(value.clone())),
+    }
+}
+
+/// Like [`to_field_value`], but takes the ownership of the value.
+pub(crate) fn into_field_value(value: Value, is_leaf: bool) -> Option<FieldValue<'static>> {
+    match value {
+        Value::Null => None,
+        Value::List(items) => Some(FieldValue::List(
+            items
+                .into_iter()
+                .map(|item| into_field_value(item, is_leaf).unwrap_or(FieldValue::NULL))
+                .collect(),
+        )),
+        value if is_leaf => Some(FieldValue::Value(value)),
+        Value::Object(object) => {
+            let ty = match object.get("__typename") {
+                Some(Value::String(ty)) => Some(ty.clone()),
+                _ => None,
+            };
+            let value = FieldValue::owned_any(Value::Object(object));
+            Some(match ty {
+                Some(ty) => FieldValue::with_type(value, ty),
+                None => value,
+            })
+        }
+        value => Some(FieldValue::Value(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dynamic::*, value, Value};
+
+    fn schema(
+        root: impl for<'a> Fn(&ResolverContext<'a>) -> FieldValue<'a> + Send + Sync + 'static,
+    ) -> Schema {
+        let user = Object::new("User")
+            .property("name", TypeRef::STRING.non_null())
+            .property("role", TypeRef::named("Role").non_null())
+            .field(Field::property_path(
+                "city",
+                TypeRef::STRING,
+                ["address", "city"],
+            ))
+            .property("tags", TypeRef::STRING.non_null().list().non_null())
+            .property("friends", TypeRef::named("User").non_null().list());
+        let bot = Object::new("Bot").property("id", TypeRef::INT.non_null());
+        let account = Union::new("Account")
+            .possible_type("User")
+            .possible_type("Bot");
+        let role = Enum::new("Role").item("ADMIN").item("USER");
+        let root_object = Object::new("Root")
+            .property("user", TypeRef::named("User"))
+            .property("accounts", TypeRef::named("Account").list());
+        let query = Object::new("Query").field(Field::new(
+            "root",
+            TypeRef::named("Root").non_null(),
+            move |ctx| {
+                let value = root(&ctx);
+                FieldFuture::new(async move { Ok(Some(value)) })
+            },
+        ));
+        Schema::build("Query", None, None)
+            .register(user)
+            .register(bot)
+            .register(account)
+            .register(role)
+            .register(root_object)
+            .register(query)
+            .finish()
+            .unwrap()
+    }
+
+    const QUERY: &str = r#"{
+        root {
+            user { name role city tags friends { name city } }
+            accounts {
+                __typename
+                ... on User { name }
+                ... on Bot { id }
+            }
+        }
+    }"#;
+
+    fn expected() -> Value {
+        value!({
+            "root": {
+                "user": {
+                    "name": "Alice",
+                    "role": "ADMIN",
+                    "city": "Paris",
+                    "tags": ["a", "b"],
+                    "friends": [{ "name": "Bob", "city": null }],
+                },
+                "accounts": [
+                    { "__typename": "User", "name": "Bob" },
+                    { "__typename": "Bot", "id": 1 },
+                ],
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn value_parent() {
+        let schema = schema(|_| {
+            FieldValue::value(value!({
+                "user": {
+                    "name": "Alice",
+                    "role": "ADMIN",
+                    "address": { "city": "Paris" },
+                    "tags": ["a", "b"],
+                    "friends": [{ "name": "Bob" }],
+                },
+                "accounts": [
+                    { "__typename": "User", "name": "Bob" },
+                    { "__typename": "Bot", "id": 1 },
+                ],
+            }))
+        });
+        assert_eq!(
+            schema.execute(QUERY).await.into_result().unwrap().data,
+            expected()
+        );
+    }
+
+    #[tokio::test]
+    async fn serde_json_parent() {
+        let schema = schema(|_| {
+            FieldValue::owned_any(serde_json::json!({
+                "user": {
+                    "name": "Alice",
+                    "role": "ADMIN",
+                    "address": { "city": "Paris" },
+                    "tags": ["a", "b"],
+                    "friends": [{ "name": "Bob", "address": null }],
+                },
+                "accounts": [
+                    { "__typename": "User", "name": "Bob" },
+                    { "__typename": "Bot", "id": 1 },
+                ],
+            }))
+        });
+        assert_eq!(
+            schema.execute(QUERY).await.into_result().unwrap().data,
+            expected()
+        );
+    }
+
+    #[tokio::test]
+    async fn parent_value_is_not_json() {
+        let schema = schema(|_| FieldValue::owned_any(1i32));
+        assert_eq!(
+            schema.execute("{ root { user { name } } }").await.errors[0].message,
+            "The field \"Root.user\" has no resolver, and its parent value is not a JSON value"
+        );
+    }
+}
@@ -0,0 +1,934 @@
+use std::{fmt::Write, sync::Arc};
+
+use crate::{
+    dynamic::{
+        property::{into_field_value, to_field_value},
+        sdl::{convert_type_ref, create_field, SdlDocument, BUILTIN_SCALARS},
+        Field, FieldFuture, FieldValue, SchemaError, Type,
+    },
+    parser::types::{Selection, SelectionSet, TypeDefinition, TypeKind},
+    Context, Error, Executor, Name, PathSegment, QueryPathSegment, Request, Response, Result,
+    ServerResult, Value,
+};
+
+/// The query used by [`RemoteSchema::introspect`] to fetch the types of a
+/// remote service.
+const INTROSPECTION_QUERY: &str = r#"
+query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { ...InputValue }
+        type { ...TypeRef }
+        isDeprecated
+        deprecationReason
+      }
+      inputFields { ...InputValue }
+      interfaces { ...TypeRef }
+      enumValues(includeDeprecated: true) {
+        name
+        description
+        isDeprecated
+        deprecationReason
+      }
+      possibleTypes { ...TypeRef }
+    }
+  }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType { kind name }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// The transport used by a [`RemoteSchema`] to send requests to the remote
+/// service.
+///
+/// It is implemented for all the [`Executor`]s, so another schema can be used
+/// as an in-process remote service.
+#[async_trait::async_trait]
+pub trait RemoteTransport: Send + Sync + 'static {
+    /// Sends a request to the remote service.
+    async fn execute(&self, request: Request) -> Response;
+}
+
+#[async_trait::async_trait]
+impl<E: Executor> RemoteTransport for E {
+    async fn execute(&self, request: Request) -> Response {
+        Executor::execute(self, request).await
+    }
+}
+
+struct RemoteSchemaInner {
+    transport: Box<dyn RemoteTransport>,
+    doc: SdlDocument,
+}
+
+/// The types of a remote GraphQL service
+///
+/// A remote schema is imported from the SDL or the introspection result of
+/// another service, and creates the dynamic types of that service. The root
+/// fields returned by [`RemoteSchema::query_fields`] and
+/// [`RemoteSchema::mutation_fields`] send their arguments and selection set
+/// to the service through a [`RemoteTransport`], and the errors of the
+/// response are remapped into the path of the local response.
+///
+/// Subscriptions of the remote service are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::{dynamic::*, value, Value};
+///
+/// let remote = {
+///     let query = Object::new("Query").field(
+///         Field::new("hello", TypeRef::STRING.non_null(), |ctx| {
+///             FieldFuture::new(async move {
+///                 let name = ctx.args.try_get("name")?;
+///                 Ok(Some(Value::from(format!("Hello, {}!", name.string()?))))
+///             })
+///         })
+///         .argument(InputValue::new("name", TypeRef::STRING.non_null())),
+///     );
+///     Schema::build("Query", None, None).register(query).finish()?
+/// };
+///
+/// let remote = RemoteSchema::from_sdl(&remote.sdl(), remote)?;
+/// let mut query = Object::new("Query");
+/// for field in remote.query_fields() {
+///     query = query.field(field);
+/// }
+/// let mut builder = Schema::build("Query", None, None).register(query);
+/// for ty in remote.types() {
+///     builder = builder.register(ty);
+/// }
+/// let schema = builder.finish()?;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// assert_eq!(
+///     schema
+///         .execute(r#"{ hello(name: "world") }"#)
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .data,
+///     value!({ "hello": "Hello, world!" })
+/// );
+/// # });
+/// # Ok::<_, SchemaError>(())
+/// ```
+#[derive(Clone)]
+pub struct RemoteSchema(Arc<RemoteSchemaInner>);
+
+impl RemoteSchema {
+    /// Imports the types of a remote service from its SDL.
+    pub fn from_sdl(sdl: &str, transport: impl RemoteTransport) -> Result<Self, SchemaError> {
+        Ok(Self(Arc::new(RemoteSchemaInner {
+            transport: Box::new(transport),
+            doc: SdlDocument::parse(sdl)?,
+        })))
+    }
+
+    /// Imports the types of a remote service from the result of an
+    /// introspection query.
+    ///
+    /// `introspection` is the data of the response, which contains the
+    /// `__schema` field.
+    pub fn from_introspection(
+        introspection: &Value,
+        transport: impl RemoteTransport,
+    ) -> Result<Self, SchemaError> {
+        let sdl = introspection_to_sdl(introspection)
+            .ok_or_else(|| SchemaError::from("Invalid introspection result"))?;
+        Self::from_sdl(&sdl, transport)
+    }
+
+    /// Imports the types of a remote service by sending it an introspection
+    /// query.
+    pub async fn introspect(transport: impl RemoteTransport) -> Result<Self, SchemaError> {
+        let resp = transport.execute(Request::new(INTROSPECTION_QUERY)).await;
+        if let Some(err) = resp.errors.first() {
+            return Err(format!("Failed to introspect the remote schema: {}", err.message).into());
+        }
+        Self::from_introspection(&resp.data, transport)
+    }
+
+    /// Returns the dynamic types of the remote service, except its root
+    /// types.
+    pub fn types(&self) -> Vec<Type> {
+        self.0
+            .doc
+            .types
+            .values()
+            .filter(|ty| !self.is_root_type(ty.name.node.as_str()))
+            .map(|ty| self.create_type(ty))
+            .collect()
+    }
+
+    /// Returns the fields of the query type of the remote service, which
+    /// delegate to the remote service.
+    pub fn query_fields(&self) -> Vec<Field> {
+        self.root_fields(&self.0.doc.query_type, "query")
+    }
+
+    /// Returns the fields of the mutation type of the remote service, which
+    /// delegate to the remote service.
+    pub fn mutation_fields(&self) -> Vec<Field> {
+        match &self.0.doc.mutation_type {
+            Some(mutation_type) => self.root_fields(mutation_type, "mutation"),
+            None => Vec::new(),
+        }
+    }
+
+    fn is_root_type(&self, name: &str) -> bool {
+        name == self.0.doc.query_type || self.0.doc.mutation_type.as_deref() == Some(name)
+    }
+
+    fn root_fields(&self, type_name: &str, operation_type: &'static str) -> Vec<Field> {
+        let fields = match self.0.doc.types.get(type_name).map(|ty| &ty.kind) {
+            Some(TypeKind::Object(object)) => &object.fields,
+            _ => return Vec::new(),
+        };
+        fields
+            .iter()
+            .map(|field| {
+                let field = &field.node;
+                let remote = self.clone();
+                let is_leaf = self.0.doc.is_leaf(&field.ty.node.base);
+                let resolver = Field::new(
+                    field.name.node.as_str(),
+                    convert_type_ref(&field.ty.node),
+                    move |ctx| {
+                        let remote = remote.clone();
+                        FieldFuture::new(async move {
+                            remote
+                                .execute_root_field(ctx.ctx, operation_type, is_leaf)
+                                .await
+                        })
+                    },
+                );
+                create_field(resolver, field)
+            })
+            .collect()
+    }
+
+    async fn execute_root_field(
+        &self,
+        ctx: &Context<'_>,
+        operation_type: &str,
+        is_leaf: bool,
+    ) -> Result<Option<FieldValue<'static>>> {
+        let field = ctx.item;
+        let mut query = format!("{} {{", operation_type);
+        write_field(ctx, &field.node, &mut query).map_err(|err| Error::new(err.message))?;
+        query.push_str(" }");
+
+        let resp = self.0.transport.execute(Request::new(query)).await;
+
+        let response_key = field.node.response_key().node.clone();
+        let mut prefix = Vec::new();
+        if let Some(parent) = ctx.path_node.as_ref().and_then(|node| node.parent) {
+            parent.for_each(|segment| {
+                prefix.push(match segment {
+                    QueryPathSegment::Name(name) => PathSegment::Field(name.to_string()),
+                    QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+                })
+            });
+        }
+        let mut errors = resp.errors.into_iter().map(|mut err| {
+            let path = if err.path.is_empty() {
+                vec![PathSegment::Field(response_key.to_string())]
+            } else {
+                err.path
+            };
+            err.path = prefix.iter().cloned().chain(path).collect();
+            err.locations = vec![field.pos];
+            err
+        });
+
+        let value = match resp.data {
+            Value::Object(mut data) => data.remove(&response_key).unwrap_or_default(),
+            _ => Value::Null,
+        };
+        if value == Value::Null {
+            if let Some(err) = errors.next() {
+                errors.for_each(|err| ctx.add_error(err));
+                return Err(Error {
+                    message: err.message,
+                    source: None,
+                    extensions: err.extensions,
+                });
+            }
+        }
+        errors.for_each(|err| ctx.add_error(err));
+        Ok(into_field_value(value, is_leaf))
+    }
+
+    fn create_type(&self, ty: &TypeDefinition) -> Type {
+        let created = self.0.doc.create_type(ty, |field, is_leaf| {
+            Ok(Field::new(
+                field.name.node.as_str(),
+                convert_type_ref(&field.ty.node),
+                move |ctx| {
+                    FieldFuture::new(async move {
+                        let parent = ctx.parent_value.try_to_inner_value()?;
+                        let response_key = &ctx.ctx.item.node.response_key().node;
+                        Ok(match parent {
+                            Value::Object(parent) => parent
+                                .get(response_key)
+                                .and_then(|value| to_field_value(value, is_leaf)),
+                            _ => None,
+                        })
+                    })
+                },
+            ))
+        });
+        created.expect("the remote fields are always created")
+    }
+}
+
+/// Writes a field of the local query, whose variables are replaced by their
+/// values and fragment spreads are inlined, to the query sent to the remote
+/// service.
+fn write_field(
+    ctx: &Context<'_>,
+    field: &crate::parser::types::Field,
+    out: &mut String,
+) -> ServerResult<()> {
+    out.push(' ');
+    if let Some(alias) = &field.alias {
+        write!(out, "{}: ", alias.node).unwrap();
+    }
+    out.push_str(&field.name.node);
+    if !field.arguments.is_empty() {
+        out.push('(');
+        for (idx, (name, value)) in field.arguments.iter().enumerate() {
+            if idx > 0 {
+                out.push_str(", ");
+            }
+            let value = ctx.resolve_input_value(value.clone())?;
+            write!(out, "{}: {}", name.node, value).unwrap();
+        }
+        out.push(')');
+    }
+    if !field.selection_set.node.items.is_empty() {
+        out.push_str(" { __typename");
+        write_selection_set(ctx, &field.selection_set.node, out)?;
+        out.push_str(" }");
+    }
+    Ok(())
+}
+
+fn write_selection_set(
+    ctx: &Context<'_>,
+    selection_set: &SelectionSet,
+    out: &mut String,
+) -> ServerResult<()> {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => write_field(ctx, &field.node, out)?,
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = ctx.query_env.fragments.get(&spread.node.fragment_name.node)
+                {
+                    write_fragment(
+                        ctx,
+                        Some(&fragment.node.type_condition.node.on.node),
+                        &fragment.node.selection_set.node,
+                        out,
+                    )?;
+                }
+            }
+            Selection::InlineFragment(fragment) => write_fragment(
+                ctx,
+                fragment
+                    .node
+                    .type_condition
+                    .as_ref()
+                    .map(|type_condition| &type_condition.node.on.node),
+                &fragment.node.selection_set.node,
+                out,
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes a fragment as an inline fragment, the fragments whose selections
+/// are all skipped are left out since an empty selection set is invalid.
+fn write_fragment(
+    ctx: &Context<'_>,
+    type_condition: Option<&Name>,
+    selection_set: &SelectionSet,
+    out: &mut String,
+) -> ServerResult<()> {
+    let mut selections = String::new();
+    write_selection_set(ctx, selection_set, &mut selections)?;
+    if selections.is_empty() {
+        return Ok(());
+    }
+    out.push_str(" ...");
+    if let Some(type_condition) = type_condition {
+        write!(out, " on {}", type_condition).unwrap();
+    }
+    write!(out, " {{{} }}", selections).unwrap();
+    Ok(())
+}
+
+/// Prints the types of an introspection result as SDL.
+fn introspection_to_sdl(introspection: &Value) -> Option<String> {
+    fn get<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+        match value {
+            Value::Object(object) => object.get(name).filter(|value| **value != Value::Null),
+            _ => None,
+        }
+    }
+
+    fn string<'a>(value: &'a Value, name: &str) -> Option<&'a str> {
+        match get(value, name) {
+            Some(Value::String(s)) => Some(s),
+            Some(Value::Enum(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn list<'a>(value: &'a Value, name: &str) -> &'a [Value] {
+        match get(value, name) {
+            Some(Value::List(items)) => items,
+            _ => &[],
+        }
+    }
+
+    fn type_ref(value: &Value) -> Option<String> {
+        match string(value, "kind")? {
+            "NON_NULL" => Some(format!("{}!", type_ref(get(value, "ofType")?)?)),
+            "LIST" => Some(format!("[{}]", type_ref(get(value, "ofType")?)?)),
+            _ => string(value, "name").map(ToString::to_string),
+        }
+    }
+
+    fn description(out: &mut String, value: &Value, indent: &str) {
+        if let Some(description) = string(value, "description") {
+            writeln!(out, "{}{}", indent, Value::String(description.to_string())).unwrap();
+        }
+    }
+
+    fn deprecated(out: &mut String, value: &Value) {
+        if get(value, "isDeprecated") == Some(&Value::Boolean(true)) {
+            match string(value, "deprecationReason") {
+                Some(reason) => write!(
+                    out,
+                    " @deprecated(reason: {})",
+                    Value::String(reason.to_string())
+                )
+                .unwrap(),
+                None => out.push_str(" @deprecated"),
+            }
+        }
+    }
+
+    fn input_value(out: &mut String, value: &Value) -> Option<()> {
+        write!(
+            out,
+            "{}: {}",
+            string(value, "name")?,
+            type_ref(get(value, "type")?)?
+        )
+        .unwrap();
+        if let Some(default_value) = string(value, "defaultValue") {
+            write!(out, " = {}", default_value).unwrap();
+        }
+        Some(())
+    }
+
+    let schema = get(introspection, "__schema")
+        .or_else(|| get(introspection, "data").and_then(|data| get(data, "__schema")))?;
+    let mut sdl = String::new();
+
+    for ty in list(schema, "types") {
+        let name = string(ty, "name")?;
+        if name.starts_with("__") || BUILTIN_SCALARS.contains(&name) {
+            continue;
+        }
+        description(&mut sdl, ty, "");
+        match string(ty, "kind")? {
+            "SCALAR" => writeln!(sdl, "scalar {}", name).unwrap(),
+            kind @ ("OBJECT" | "INTERFACE") => {
+                let keyword = if kind == "OBJECT" {
+                    "type"
+                } else {
+                    "interface"
+                };
+                write!(sdl, "{} {}", keyword, name).unwrap();
+                let interfaces = list(ty, "interfaces")
+                    .iter()
+                    .filter_map(|interface| string(interface, "name"))
+                    .collect::<Vec<_>>();
+                if !interfaces.is_empty() {
+                    write!(sdl, " implements {}", interfaces.join(" & ")).unwrap();
+                }
+                sdl.push_str(" {\n");
+                for field in list(ty, "fields") {
+                    description(&mut sdl, field, "\t");
+                    write!(sdl, "\t{}", string(field, "name")?).unwrap();
+                    let args = list(field, "args");
+                    if !args.is_empty() {
+                        sdl.push('(');
+                        for (idx, arg) in args.iter().enumerate() {
+                            if idx > 0 {
+                                sdl.push_str(", ");
+                            }
+                            input_value(&mut sdl, arg)?;
+                        }
+                        sdl.push(')');
+                    }
+                    write!(sdl, ": {}", type_ref(get(field, "type")?)?).unwrap();
+                    deprecated(&mut sdl, field);
+                    sdl.push('\n');
+                }
+                sdl.push_str("}\n");
+            }
+            "UNION" => {
+                let members = list(ty, "possibleTypes")
+                    .iter()
+                    .filter_map(|member| string(member, "name"))
+                    .collect::<Vec<_>>();
+                writeln!(sdl, "union {} = {}", name, members.join(" | ")).unwrap();
+            }
+            "ENUM" => {
+                writeln!(sdl, "enum {} {{", name).unwrap();
+                for value in list(ty, "enumValues") {
+                    description(&mut sdl, value, "\t");
+                    write!(sdl, "\t{}", string(value, "name")?).unwrap();
+                    deprecated(&mut sdl, value);
+                    sdl.push('\n');
+                }
+                sdl.push_str("}\n");
+            }
+            "INPUT_OBJECT" => {
+                writeln!(sdl, "input {} {{", name).unwrap();
+                for field in list(ty, "inputFields") {
+                    description(&mut sdl, field, "\t");
+                    sdl.push('\t');
+                    input_value(&mut sdl, field)?;
+                    sdl.push('\n');
+                }
+                sdl.push_str("}\n");
+            }
+            _ => return None,
+        }
+    }
+
+    write!(
+        sdl,
+        "schema {{ query: {}",
+        string(get(schema, "queryType")?, "name")?
+    )
+    .unwrap();
+    if let Some(mutation_type) = get(schema, "mutationType") {
+        write!(sdl, " mutation: {}", string(mutation_type, "name")?).unwrap();
+    }
+    sdl.push_str(" }\n");
+    Some(sdl)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{dynamic::*, value, EmptySubscription, ServerError, Variables, ID};
+
+    #[derive(crate::SimpleObject)]
+    #[graphql(internal)]
+    struct Dog {
+        name: String,
+    }
+
+    #[derive(crate::SimpleObject)]
+    #[graphql(internal)]
+    struct Cat {
+        lives: i32,
+    }
+
+    #[derive(crate::Union)]
+    #[graphql(internal)]
+    enum Pet {
+        Dog(Dog),
+        Cat(Cat),
+    }
+
+    #[derive(crate::Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Role {
+        Admin,
+        Guest,
+    }
+
+    struct User {
+        id: i32,
+    }
+
+    #[crate::Object(internal)]
+    impl User {
+        async fn id(&self) -> ID {
+            self.id.into()
+        }
+
+        async fn role(&self) -> Role {
+            if self.id == 1 {
+                Role::Admin
+            } else {
+                Role::Guest
+            }
+        }
+
+        async fn friends(&self, limit: i32) -> Vec<User> {
+            (1..=limit).map(|id| User { id: self.id + id }).collect()
+        }
+
+        async fn pet(&self) -> Pet {
+            if self.id % 2 == 0 {
+                Pet::Dog(Dog {
+                    name: format!("dog{}", self.id),
+                })
+            } else {
+                Pet::Cat(Cat { lives: 9 })
+            }
+        }
+
+        async fn secret(&self) -> crate::Result<Option<String>> {
+            Err("forbidden".into())
+        }
+    }
+
+    struct RemoteQuery;
+
+    #[crate::Object(internal)]
+    impl RemoteQuery {
+        async fn user(&self, id: i32) -> crate::Result<User> {
+            if id > 0 {
+                Ok(User { id })
+            } else {
+                Err("user not found".into())
+            }
+        }
+    }
+
+    struct RemoteMutation;
+
+    #[crate::Object(internal)]
+    impl RemoteMutation {
+        async fn create_user(&self, id: i32) -> User {
+            User { id }
+        }
+    }
+
+    type RemoteService = crate::Schema<RemoteQuery, RemoteMutation, EmptySubscription>;
+
+    #[derive(Clone)]
+    struct MockTransport {
+        schema: RemoteService,
+        queries: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl RemoteTransport for MockTransport {
+        async fn execute(&self, request: Request) -> Response {
+            self.queries.lock().unwrap().push(request.query.clone());
+            self.schema.execute(request).await
+        }
+    }
+
+    fn mock_transport() -> MockTransport {
+        MockTransport {
+            schema: crate::Schema::new(RemoteQuery, RemoteMutation, EmptySubscription),
+            queries: Default::default(),
+        }
+    }
+
+    fn stitched_schema(remote: &RemoteSchema) -> Schema {
+        let mut query = Object::new("Query").field(Field::new("local", TypeRef::INT, |_| {
+            FieldFuture::new(async { Ok(Some(Value::from(1))) })
+        }));
+        for field in remote.query_fields() {
+            query = query.field(field);
+        }
+        let mut mutation = Object::new("Mutation");
+        for field in remote.mutation_fields() {
+            mutation = mutation.field(field);
+        }
+        let mut builder = Schema::build("Query", Some("Mutation"), None)
+            .register(query)
+            .register(mutation);
+        for ty in remote.types() {
+            builder = builder.register(ty);
+        }
+        builder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remote_query() {
+        let transport = mock_transport();
+        let remote = RemoteSchema::from_sdl(&transport.schema.sdl(), transport.clone()).unwrap();
+        let schema = stitched_schema(&remote);
+
+        let query = r#"
+            query($limit: Int!) {
+                local
+                me: user(id: 1) {
+                    ...UserFields
+                    friends(limit: $limit) {
+                        id
+                        pet {
+                            __typename
+                            ... on Dog { name }
+                            ... on Cat { lives }
+                        }
+                    }
+                }
+            }
+
+            fragment UserFields on User { id role }
+        "#;
+        let resp = schema
+            .execute(
+                Request::new(query).variables(crate::Variables::from_value(value!({
+                    "limit": 2,
+                }))),
+            )
+            .await;
+        assert_eq!(
+            resp.into_result().unwrap().data,
+            value!({
+                "local": 1,
+                "me": {
+                    "id": "1",
+                    "role": "ADMIN",
+                    "friends": [
+                        { "id": "2", "pet": { "__typename": "Dog", "name": "dog2" } },
+                        { "id": "3", "pet": { "__typename": "Cat", "lives": 9 } },
+                    ],
+                },
+            })
+        );
+        assert_eq!(
+            transport.queries.lock().unwrap().as_slice(),
+            &["query { me: user(id: 1) { __typename ... on User { id role } friends(limit: 2) { __typename id pet { __typename __typename ... on Dog { name } ... on Cat { lives } } } } }".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_mutation() {
+        let transport = mock_transport();
+        let remote = RemoteSchema::from_sdl(&transport.schema.sdl(), transport).unwrap();
+        let schema = stitched_schema(&remote);
+
+        assert_eq!(
+            schema
+                .execute("mutation { createUser(id: 4) { id role } }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "createUser": { "id": "4", "role": "GUEST" } })
+        );
+    }
+
+    struct CannedTransport(Value, Vec<ServerError>);
+
+    #[async_trait::async_trait]
+    impl RemoteTransport for CannedTransport {
+        async fn execute(&self, _request: Request) -> Response {
+            let mut resp = Response::new(self.0.clone());
+            resp.errors = self.1.clone();
+            resp
+        }
+    }
+
+    fn namespaced_schema(transport: CannedTransport) -> Schema {
+        let sdl = mock_transport().schema.sdl();
+        let remote = RemoteSchema::from_sdl(&sdl, transport).unwrap();
+
+        let mut namespace = Object::new("Remote");
+        for field in remote.query_fields() {
+            namespace = namespace.field(field);
+        }
+        let query = Object::new("Query").field(Field::new(
+            "remote",
+            TypeRef::named("Remote").non_null(),
+            |_| FieldFuture::new(async { Ok(Some(FieldValue::NULL)) }),
+        ));
+        let mut builder = Schema::build("Query", None, None)
+            .register(query)
+            .register(namespace);
+        for ty in remote.types() {
+            builder = builder.register(ty);
+        }
+        builder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remote_errors() {
+        let mut err = ServerError::new(
+            "forbidden",
+            Some(crate::Pos {
+                line: 1,
+                column: 40,
+            }),
+        );
+        err.path = vec![
+            PathSegment::Field("user".to_string()),
+            PathSegment::Field("friends".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Field("secret".to_string()),
+        ];
+        let schema = namespaced_schema(CannedTransport(
+            value!({
+                "user": {
+                    "__typename": "User",
+                    "id": "2",
+                    "friends": [{ "__typename": "User", "secret": null }],
+                },
+            }),
+            vec![err],
+        ));
+
+        let resp = schema
+            .execute("{ remote { user(id: 2) { id friends(limit: 1) { secret } } } }")
+            .await;
+        assert_eq!(
+            resp.data,
+            value!({
+                "remote": { "user": { "id": "2", "friends": [{ "secret": null }] } },
+            })
+        );
+        assert_eq!(
+            resp.errors,
+            vec![ServerError {
+                message: "forbidden".to_string(),
+                source: None,
+                locations: vec![crate::Pos {
+                    line: 1,
+                    column: 12
+                }],
+                path: vec![
+                    PathSegment::Field("remote".to_string()),
+                    PathSegment::Field("user".to_string()),
+                    PathSegment::Field("friends".to_string()),
+                    PathSegment::Index(0),
+                    PathSegment::Field("secret".to_string()),
+                ],
+                extensions: None,
+            }]
+        );
+
+        let schema = namespaced_schema(CannedTransport(
+            value!({ "user": null }),
+            vec![ServerError::new("user not found", None)],
+        ));
+        let resp = schema.execute("{ remote { user(id: 0) { id } } }").await;
+        assert_eq!(resp.data, Value::Null);
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(resp.errors[0].message, "user not found");
+    }
+
+    #[tokio::test]
+    async fn test_remote_skipped_fields() {
+        let transport = mock_transport();
+        let remote = RemoteSchema::from_sdl(&transport.schema.sdl(), transport.clone()).unwrap();
+        let schema = stitched_schema(&remote);
+
+        // The skipped fields are not fetched, so their remote errors are not
+        // reported
+        for (query, variables) in [
+            (
+                "{ user(id: 2) { id secret @skip(if: true) } }",
+                value!({}),
+            ),
+            (
+                "query($secret: Boolean!) { user(id: 2) { id ... @include(if: $secret) { secret } } }",
+                value!({ "secret": false }),
+            ),
+            (
+                "query($skip: Boolean = true) { user(id: 2) { id ...Secret } } fragment Secret on User { secret @skip(if: $skip) }",
+                value!({}),
+            ),
+        ] {
+            let resp = schema
+                .execute(Request::new(query).variables(Variables::from_value(variables)))
+                .await;
+            assert_eq!(
+                resp.into_result().unwrap().data,
+                value!({ "user": { "id": "2" } })
+            );
+        }
+        assert!(transport
+            .queries
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|query| !query.contains("secret")));
+    }
+
+    #[tokio::test]
+    async fn test_remote_introspection() {
+        let transport = mock_transport();
+        let remote = RemoteSchema::introspect(transport).await.unwrap();
+        let schema = stitched_schema(&remote);
+
+        assert_eq!(
+            schema
+                .execute("{ user(id: 2) { role pet { ... on Dog { name } } } }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "user": { "role": "GUEST", "pet": { "name": "dog2" } } })
+        );
+
+        let sdl = schema.sdl();
+        assert!(sdl.contains("union Pet = Dog | Cat"));
+        assert!(sdl.contains("enum Role {"));
+        assert!(sdl.contains("\tfriends(limit: Int!): [User!]!"));
+    }
+}
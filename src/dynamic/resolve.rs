@@ -10,7 +10,8 @@ use crate::{
     extensions::ResolveInfo,
     parser::types::Selection,
     resolver_utils::create_value_object,
-    Context, ContextSelectionSet, Error, IntrospectionMode, Name, ServerError, ServerResult, Value,
+    Context, ContextBase, ContextSelectionSet, Error, IntrospectionMode, Name, ServerError,
+    ServerResult, Value,
 };
 
 type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
@@ -25,15 +26,20 @@ pub(crate) async fn resolve_container(
     let mut fields = Vec::new();
     collect_fields(&mut fields, schema, object, ctx, parent_value)?;
 
-    let res = if serial {
-        futures_util::future::try_join_all(fields).await?
-    } else {
-        let mut results = Vec::with_capacity(fields.len());
-        for field in fields {
-            results.push(field.await?);
+    let fut = async move {
+        if serial {
+            futures_util::future::try_join_all(fields).await
+        } else {
+            let mut results = Vec::with_capacity(fields.len());
+            for field in fields {
+                results.push(field.await?);
+            }
+            Ok(results)
         }
-        results
     };
+    #[cfg(feature = "dataloader")]
+    let fut = crate::dataloader::drive_batches(fut);
+    let res = fut.await?;
 
     Ok(Some(create_value_object(res)))
 }
@@ -184,10 +190,18 @@ fn collect_fields<'a>(
                             };
 
                             let resolve_fut = async {
+                                if let Some(guard) = &field_def.guard {
+                                    guard
+                                        .check(&ctx_field)
+                                        .await
+                                        .map_err(|err| err.into_server_error(field.pos))?;
+                                }
+
                                 let field_value = (field_def.resolver_fn)(ResolverContext {
                                     ctx: &ctx_field,
                                     args: arguments,
                                     parent_value,
+                                    parent_type: &object.name,
                                 })
                                 .0
                                 .await
@@ -201,7 +215,34 @@ fn collect_fields<'a>(
                                 .await?;
                                 Ok(value)
                             };
-                            futures_util::pin_mut!(resolve_fut);
+                            let mut resolve_fut = resolve_fut.boxed();
+
+                            for directive in &field.node.directives {
+                                if let Some(directive_factory) = ctx
+                                    .schema_env
+                                    .custom_directives
+                                    .get(directive.node.name.node.as_str())
+                                {
+                                    let ctx_directive = ContextBase {
+                                        path_node: ctx_field.path_node,
+                                        is_for_introspection: false,
+                                        item: directive,
+                                        schema_env: ctx_field.schema_env,
+                                        query_env: ctx_field.query_env,
+                                        batch_scope: ctx_field.batch_scope,
+                                    };
+                                    let directive_instance = directive_factory
+                                        .create(&ctx_directive, &directive.node)?;
+                                    resolve_fut = Box::pin({
+                                        let ctx_field = ctx_field.clone();
+                                        async move {
+                                            directive_instance
+                                                .resolve_field(&ctx_field, &mut resolve_fut)
+                                                .await
+                                        }
+                                    });
+                                }
+                            }
 
                             let res_value = ctx_field
                                 .query_env
@@ -411,32 +452,7 @@ async fn resolve_value(
                 ));
             }
 
-            let object_type = schema
-                .0
-                .types
-                .get(ty.as_ref())
-                .ok_or_else(|| {
-                    ctx.set_error_path(
-                        Error::new(format!("internal: object \"{}\" does not registered", ty))
-                            .into_server_error(ctx.item.pos),
-                    )
-                })?
-                .as_object()
-                .ok_or_else(|| {
-                    ctx.set_error_path(
-                        Error::new(format!("internal: type \"{}\" is not object", ty))
-                            .into_server_error(ctx.item.pos),
-                    )
-                })?;
-
-            resolve_container(
-                schema,
-                object_type,
-                &ctx.with_selection_set(&ctx.item.node.selection_set),
-                value,
-                true,
-            )
-            .await
+            resolve_object_type(schema, ctx, ty, value).await
         }
         (Type::Interface(interface), _) => Err(ctx.set_error_path(
             Error::new(format!(
@@ -457,32 +473,7 @@ async fn resolve_value(
                 ));
             }
 
-            let object_type = schema
-                .0
-                .types
-                .get(ty.as_ref())
-                .ok_or_else(|| {
-                    ctx.set_error_path(
-                        Error::new(format!("internal: object \"{}\" does not registered", ty))
-                            .into_server_error(ctx.item.pos),
-                    )
-                })?
-                .as_object()
-                .ok_or_else(|| {
-                    ctx.set_error_path(
-                        Error::new(format!("internal: type \"{}\" is not object", ty))
-                            .into_server_error(ctx.item.pos),
-                    )
-                })?;
-
-            resolve_container(
-                schema,
-                object_type,
-                &ctx.with_selection_set(&ctx.item.node.selection_set),
-                value,
-                true,
-            )
-            .await
+            resolve_object_type(schema, ctx, ty, value).await
         }
         (Type::Union(union), _) => Err(ctx.set_error_path(
             Error::new(format!(
@@ -499,5 +490,38 @@ async fn resolve_value(
             ))
             .into_server_error(ctx.item.pos),
         )),
+
+        (Type::Static(ty), value) => ty.resolve(ctx, value).await,
+    }
+}
+
+async fn resolve_object_type(
+    schema: &Schema,
+    ctx: &Context<'_>,
+    ty: &str,
+    value: &FieldValue<'_>,
+) -> ServerResult<Option<Value>> {
+    match schema.0.types.get(ty) {
+        Some(Type::Object(object_type)) => {
+            resolve_container(
+                schema,
+                object_type,
+                &ctx.with_selection_set(&ctx.item.node.selection_set),
+                value,
+                true,
+            )
+            .await
+        }
+        Some(Type::Static(static_type)) if static_type.is_object() => {
+            static_type.resolve(ctx, value).await
+        }
+        Some(_) => Err(ctx.set_error_path(
+            Error::new(format!("internal: type \"{}\" is not object", ty))
+                .into_server_error(ctx.item.pos),
+        )),
+        None => Err(ctx.set_error_path(
+            Error::new(format!("internal: object \"{}\" does not registered", ty))
+                .into_server_error(ctx.item.pos),
+        )),
     }
 }
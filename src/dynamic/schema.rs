@@ -1,18 +1,24 @@
-use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
 
 use async_graphql_parser::types::OperationType;
 use futures_util::{stream::BoxStream, Stream, StreamExt, TryFutureExt};
 
 use crate::{
     dynamic::{
-        r#type::Type, resolve::resolve_container, FieldValue, Object, Scalar, SchemaError,
-        Subscription,
+        r#type::Type, resolve::resolve_container, static_type::StaticType, FieldValue, Object,
+        Scalar, SchemaError, Subscription,
     },
     extensions::{ExtensionFactory, Extensions},
     registry::{MetaType, Registry},
     schema::{prepare_request, SchemaEnvInner},
-    Data, Executor, IntrospectionMode, QueryEnv, Request, Response, SDLExportOptions, SchemaEnv,
-    ServerError, ServerResult, ValidationMode,
+    CustomDirectiveFactory, Data, Executor, InputType, IntrospectionMode, OutputType, QueryEnv,
+    Request, Response, SDLExportOptions, SchemaEnv, ServerError, ServerResult,
+    SubscriptionBufferPolicy, ValidationMode,
 };
 
 /// Dynamic schema builder
@@ -23,12 +29,14 @@ pub struct SchemaBuilder {
     types: HashMap<String, Type>,
     data: Data,
     extensions: Vec<Box<dyn ExtensionFactory>>,
+    custom_directives: HashMap<String, Box<dyn CustomDirectiveFactory>>,
     validation_mode: ValidationMode,
     recursive_depth: usize,
     complexity: Option<usize>,
     depth: Option<usize>,
     enable_suggestions: bool,
     introspection_mode: IntrospectionMode,
+    subscription_buffer_policy: SubscriptionBufferPolicy,
 }
 
 impl SchemaBuilder {
@@ -40,6 +48,64 @@ impl SchemaBuilder {
         self
     }
 
+    /// Register a type defined with the static API, such as
+    /// `#[derive(SimpleObject)]` or `#[derive(Enum)]`, as an output type.
+    ///
+    /// The types it references are registered too, and values of this type
+    /// returned with [`FieldValue::owned_any`] or [`FieldValue::borrowed_any`]
+    /// are resolved with its [`OutputType::resolve`](crate::OutputType::resolve)
+    /// implementation. A dynamic field can return a referenced scalar or enum
+    /// as a [`FieldValue::value`], but a referenced object must be registered
+    /// with this method to be returned by a dynamic field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, value, SimpleObject};
+    ///
+    /// #[derive(SimpleObject)]
+    /// struct MyObj {
+    ///     a: i32,
+    ///     b: String,
+    /// }
+    ///
+    /// let query = Object::new("Query").field(Field::new("obj", TypeRef::named("MyObj"), |_| {
+    ///     FieldFuture::new(async move {
+    ///         Ok(Some(FieldValue::owned_any(MyObj {
+    ///             a: 10,
+    ///             b: "abc".to_string(),
+    ///         })))
+    ///     })
+    /// }));
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// let schema = Schema::build(query.type_name(), None, None)
+    ///     .register(query)
+    ///     .register_output_type::<MyObj>()
+    ///     .finish()?;
+    ///
+    /// assert_eq!(
+    ///     schema.execute("{ obj { a b } }").await.into_result().unwrap().data,
+    ///     value!({ "obj": { "a": 10, "b": "abc" } })
+    /// );
+    /// # Ok::<_, SchemaError>(())
+    /// # }).unwrap();
+    /// ```
+    #[must_use]
+    pub fn register_output_type<T: OutputType + 'static>(self) -> Self {
+        self.register(Type::Static(StaticType::output::<T>()))
+    }
+
+    /// Register a type defined with the static API, such as
+    /// `#[derive(InputObject)]`, as an input type.
+    ///
+    /// Use [`ValueAccessor::parse`](crate::dynamic::ValueAccessor::parse) to
+    /// parse the argument values of this type.
+    #[must_use]
+    pub fn register_input_type<T: InputType>(self) -> Self {
+        self.register(Type::Static(StaticType::input::<T>()))
+    }
+
     /// Add a global data that can be accessed in the `Schema`. You access it
     /// with `Context::data`.
     #[must_use]
@@ -55,6 +121,28 @@ impl SchemaBuilder {
         self
     }
 
+    /// Register a custom directive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directive with the same name is already registered.
+    #[must_use]
+    pub fn directive<T: CustomDirectiveFactory>(mut self, directive: T) -> Self {
+        let name = directive.name();
+
+        if name == "skip"
+            || name == "include"
+            || self
+                .custom_directives
+                .insert(name.clone().into(), Box::new(directive))
+                .is_some()
+        {
+            panic!("Directive `{}` already exists", name);
+        }
+
+        self
+    }
+
     /// Set the maximum complexity a query can have. By default, there is no
     /// limit.
     #[must_use]
@@ -87,6 +175,19 @@ impl SchemaBuilder {
         self
     }
 
+    /// Enable uploading files, this registers the `Upload` scalar.
+    ///
+    /// Use [`TypeRef::UPLOAD`] as the type of the arguments, and
+    /// [`ValueAccessor::upload`](crate::dynamic::ValueAccessor::upload) to get
+    /// the uploaded files.
+    #[must_use]
+    pub fn enable_uploading(self) -> Self {
+        self.register(
+            Scalar::new("Upload")
+                .specified_by_url("https://github.com/jaydenseric/graphql-multipart-request-spec"),
+        )
+    }
+
     /// Disable field suggestions.
     #[must_use]
     pub fn disable_suggestions(mut self) -> Self {
@@ -109,6 +210,18 @@ impl SchemaBuilder {
         self
     }
 
+    /// Set the buffering policy of subscription fields, default is
+    /// `SubscriptionBufferPolicy::Unbounded`.
+    ///
+    /// A policy specified with
+    /// [`SubscriptionField::buffer_policy`](crate::dynamic::SubscriptionField::buffer_policy)
+    /// takes precedence over this one.
+    #[must_use]
+    pub fn subscription_buffer_policy(mut self, policy: SubscriptionBufferPolicy) -> Self {
+        self.subscription_buffer_policy = policy;
+        self
+    }
+
     /// Consumes this builder and returns a schema.
     pub fn finish(mut self) -> Result<Schema, SchemaError> {
         let mut registry = Registry {
@@ -123,9 +236,35 @@ impl SchemaBuilder {
             federation_subscription: false,
             ignore_name_conflicts: Default::default(),
             enable_suggestions: self.enable_suggestions,
+            node_fetchers: Default::default(),
+            federation_links: Default::default(),
+            compose_directives: Default::default(),
         };
         registry.add_system_types();
 
+        for directive in self.custom_directives.values() {
+            directive.register(&mut registry);
+        }
+
+        // register the static types first, so that a dynamic type cannot silently
+        // take the name of a type they reference
+        let known_types = registry.types.keys().cloned().collect::<HashSet<_>>();
+        for ty in self.types.values_mut() {
+            if let Type::Static(ty) = ty {
+                ty.register(&mut registry);
+            }
+        }
+
+        // the types referenced by the static types are static types too
+        for meta in registry.types.values() {
+            if !known_types.contains(meta.name()) && !self.types.contains_key(meta.name()) {
+                self.types.insert(
+                    meta.name().to_string(),
+                    Type::Static(StaticType::referenced(meta)),
+                );
+            }
+        }
+
         for ty in self.types.values() {
             ty.register(&mut registry)?;
         }
@@ -149,7 +288,8 @@ impl SchemaBuilder {
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry,
                 data: self.data,
-                custom_directives: Default::default(),
+                custom_directives: self.custom_directives,
+                subscription_buffer_policy: self.subscription_buffer_policy,
             })),
             extensions: self.extensions,
             types: self.types,
@@ -195,12 +335,14 @@ impl Schema {
             types: Default::default(),
             data: Default::default(),
             extensions: Default::default(),
+            custom_directives: Default::default(),
             validation_mode: ValidationMode::Strict,
             recursive_depth: 32,
             complexity: None,
             depth: None,
             enable_suggestions: true,
             introspection_mode: IntrospectionMode::Enabled,
+            subscription_buffer_policy: Default::default(),
         }
     }
 
@@ -897,4 +1039,299 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn upload() {
+        use futures_util::{io::Cursor, TryStreamExt};
+
+        use crate::http::{receive_body, receive_body_streaming, MultipartOptions};
+
+        let query = Object::new("Query").field(Field::new("value", TypeRef::INT, |_| {
+            FieldFuture::new(async { Ok(Some(Value::from(100))) })
+        }));
+        let mutation = Object::new("Mutation")
+            .field(
+                Field::new("upload", TypeRef::STRING.non_null(), |ctx| {
+                    FieldFuture::new(async move {
+                        let file = ctx.args.try_get("file")?.upload(&ctx)?;
+                        Ok(Some(Value::from(file.filename)))
+                    })
+                })
+                .argument(InputValue::new("file", TypeRef::UPLOAD.non_null())),
+            )
+            .field(
+                Field::new("uploadStream", TypeRef::STRING.non_null(), |ctx| {
+                    FieldFuture::new(async move {
+                        let stream = ctx.args.try_get("file")?.upload_stream(&ctx).await?;
+                        let chunks: Vec<_> = stream.try_collect().await?;
+                        Ok(Some(Value::from(String::from_utf8(chunks.concat())?)))
+                    })
+                })
+                .argument(InputValue::new("file", TypeRef::UPLOAD.non_null())),
+            )
+            .field(
+                Field::new("notUpload", TypeRef::STRING.non_null(), |ctx| {
+                    FieldFuture::new(async move {
+                        let file = ctx.args.try_get("file")?.upload(&ctx)?;
+                        Ok(Some(Value::from(file.filename)))
+                    })
+                })
+                .argument(InputValue::new("file", TypeRef::STRING.non_null())),
+            );
+        let schema = Schema::build("Query", Some("Mutation"), None)
+            .register(query)
+            .register(mutation)
+            .enable_uploading()
+            .finish()
+            .unwrap();
+
+        let body = |query: &str| {
+            format!(
+                "--boundary\r\n\
+                 Content-Disposition: form-data; name=\"operations\"\r\n\r\n\
+                 {{ \"query\": \"{}\", \"variables\": {{ \"file\": null }} }}\r\n\
+                 --boundary\r\n\
+                 Content-Disposition: form-data; name=\"map\"\r\n\r\n\
+                 {{ \"0\": [\"variables.file\"] }}\r\n\
+                 --boundary\r\n\
+                 Content-Disposition: form-data; name=\"0\"; filename=\"a.txt\"\r\n\r\n\
+                 abc\r\n\
+                 --boundary--\r\n",
+                query
+            )
+            .into_bytes()
+        };
+        let content_type = Some("multipart/form-data; boundary=boundary");
+
+        let request = receive_body(
+            content_type,
+            Cursor::new(body("mutation ($file: Upload!) { upload(file: $file) }")),
+            MultipartOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap().data,
+            value!({ "upload": "a.txt" })
+        );
+
+        let request = receive_body_streaming(
+            content_type,
+            Cursor::new(body(
+                "mutation ($file: Upload!) { uploadStream(file: $file) }",
+            )),
+            MultipartOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap().data,
+            value!({ "uploadStream": "abc" })
+        );
+
+        assert_eq!(
+            schema
+                .execute(r#"mutation { notUpload(file: "a.txt") }"#)
+                .await
+                .errors[0]
+                .message,
+            "The value is not an upload"
+        );
+    }
+
+    #[tokio::test]
+    async fn guard() {
+        let query = Object::new("Query")
+            .field(
+                Field::new("value", TypeRef::INT, |_| {
+                    FieldFuture::new(async { Ok(Some(Value::from(100))) })
+                })
+                .guard(|ctx: &crate::Context<'_>| match ctx.data_opt::<i32>() {
+                    Some(1) => Ok(()),
+                    _ => Err("Forbidden".into()),
+                }),
+            )
+            .field(Field::new("other", TypeRef::INT, |_| {
+                FieldFuture::new(async { Ok(Some(Value::from(200))) })
+            }));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute(Request::new("{ value }").data(1i32))
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "value": 100 })
+        );
+        assert_eq!(
+            schema
+                .execute(Request::new("{ value other }").data(2i32))
+                .await
+                .errors,
+            vec![ServerError {
+                message: "Forbidden".to_string(),
+                source: None,
+                locations: vec![Pos { line: 1, column: 3 }],
+                path: vec![],
+                extensions: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_directive() {
+        struct Concat {
+            value: String,
+        }
+
+        #[async_trait::async_trait]
+        impl crate::CustomDirective for Concat {
+            async fn resolve_field(
+                &self,
+                _ctx: &crate::Context<'_>,
+                resolve: crate::ResolveFut<'_>,
+            ) -> ServerResult<Option<Value>> {
+                resolve.await.map(|value| {
+                    value.map(|value| match value {
+                        Value::String(str) => Value::String(str + &self.value),
+                        _ => value,
+                    })
+                })
+            }
+        }
+
+        #[crate::Directive(location = "field", internal)]
+        fn concat(value: String) -> impl crate::CustomDirective {
+            Concat { value }
+        }
+
+        let query = Object::new("Query").field(Field::new("value", TypeRef::STRING, |_| {
+            FieldFuture::new(async { Ok(Some(Value::from("abc"))) })
+        }));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .directive(concat)
+            .finish()
+            .unwrap();
+
+        assert!(schema.sdl().contains("value: String"));
+        assert_eq!(
+            schema
+                .execute(r#"{ value @concat(value: "def") }"#)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "value": "abcdef" })
+        );
+    }
+
+    #[tokio::test]
+    async fn visible() {
+        let my_enum = Enum::new("MyEnum")
+            .item(EnumItem::new("A"))
+            .item(EnumItem::new("B").visible(|_| false));
+        let query = Object::new("Query")
+            .field(
+                Field::new("a", TypeRef::named("MyEnum"), |_| {
+                    FieldFuture::new(async { Ok(Some(Value::from("A"))) })
+                })
+                .argument(InputValue::new("x", TypeRef::INT))
+                .argument(InputValue::new("y", TypeRef::INT).visible(|_| false)),
+            )
+            .field(
+                Field::new("b", TypeRef::INT, |_| {
+                    FieldFuture::new(async { Ok(Some(Value::from(100))) })
+                })
+                .visible(|ctx| ctx.data_opt::<bool>() == Some(&true)),
+            );
+        let schema = Schema::build("Query", None, None)
+            .register(my_enum)
+            .register(query)
+            .finish()
+            .unwrap();
+
+        let query = r#"{
+            query: __type(name: "Query") { fields { name args { name } } }
+            myEnum: __type(name: "MyEnum") { enumValues { name } }
+        }"#;
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap().data,
+            value!({
+                "query": { "fields": [{ "name": "a", "args": [{ "name": "x" }] }] },
+                "myEnum": { "enumValues": [{ "name": "A" }] },
+            })
+        );
+        assert_eq!(
+            schema
+                .execute(Request::new(query).data(true))
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "query": {
+                    "fields": [
+                        { "name": "a", "args": [{ "name": "x" }] },
+                        { "name": "b", "args": [] },
+                    ]
+                },
+                "myEnum": { "enumValues": [{ "name": "A" }] },
+            })
+        );
+        assert_eq!(
+            schema.execute("{ b }").await.into_result().unwrap().data,
+            value!({ "b": 100 })
+        );
+    }
+
+    #[tokio::test]
+    async fn complexity_and_cache_control() {
+        let query = Object::new("Query")
+            .field(
+                Field::new("a", TypeRef::INT, |_| {
+                    FieldFuture::new(async { Ok(Some(Value::from(1))) })
+                })
+                .complexity(5)
+                .cache_control(crate::CacheControl {
+                    public: true,
+                    max_age: 30,
+                }),
+            )
+            .field(
+                Field::new("b", TypeRef::INT, |_| {
+                    FieldFuture::new(async { Ok(Some(Value::from(2))) })
+                })
+                .argument(InputValue::new("count", TypeRef::INT))
+                .complexity_fn(|ctx, variables, field, child_complexity| {
+                    let count =
+                        ctx.param_value::<Option<usize>>(variables, field, "count", None)?;
+                    Ok(count.unwrap_or(1) * 10 + child_complexity)
+                }),
+            );
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .limit_complexity(20)
+            .finish()
+            .unwrap();
+
+        let resp = schema.execute("{ a b }").await;
+        assert!(resp.is_ok());
+        assert_eq!(
+            resp.cache_control,
+            crate::CacheControl {
+                public: true,
+                max_age: 30,
+            }
+        );
+        assert_eq!(
+            schema.execute("{ a b(count: 2) }").await.errors[0].message,
+            "Query is too complex."
+        );
+    }
 }
@@ -0,0 +1,666 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use crate::{
+    dynamic::{
+        r#enum::EnumItem, type_ref::TypeRefInner, Enum, Field, FieldFuture, InputObject,
+        InputValue, Interface, InterfaceField, Object, ResolverContext, Scalar, Schema,
+        SchemaBuilder, SchemaError, Subscription, SubscriptionField, SubscriptionFieldFuture, Type,
+        TypeRef,
+    },
+    parser::{
+        parse_schema,
+        types::{
+            BaseType, ConstDirective, FieldDefinition, InputValueDefinition, TypeDefinition,
+            TypeKind, TypeSystemDefinition,
+        },
+        Positioned,
+    },
+    registry::Deprecation,
+    Value,
+};
+
+pub(crate) const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+type BoxResolverFn = Box<dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync>;
+
+type BoxSubscriptionResolverFn =
+    Box<dyn for<'a> Fn(ResolverContext<'a>) -> SubscriptionFieldFuture<'a> + Send + Sync>;
+
+/// The type definitions of a SDL document
+pub(crate) struct SdlDocument {
+    pub(crate) types: IndexMap<String, TypeDefinition>,
+    pub(crate) query_type: String,
+    pub(crate) mutation_type: Option<String>,
+    pub(crate) subscription_type: Option<String>,
+    leaf_types: HashSet<String>,
+}
+
+impl SdlDocument {
+    pub(crate) fn parse(sdl: &str) -> Result<Self, SchemaError> {
+        let doc = parse_schema(sdl).map_err(|err| SchemaError(err.to_string()))?;
+        let mut types: IndexMap<String, TypeDefinition> = IndexMap::new();
+        let mut query_type = None;
+        let mut mutation_type = None;
+        let mut subscription_type = None;
+
+        for definition in doc.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(schema) => {
+                    let schema = schema.node;
+                    if let Some(query) = schema.query {
+                        query_type = Some(query.node.to_string());
+                    }
+                    if let Some(mutation) = schema.mutation {
+                        mutation_type = Some(mutation.node.to_string());
+                    }
+                    if let Some(subscription) = schema.subscription {
+                        subscription_type = Some(subscription.node.to_string());
+                    }
+                }
+                TypeSystemDefinition::Type(ty) => {
+                    let ty = ty.node;
+                    match types.get_mut(ty.name.node.as_str()) {
+                        Some(existing) => merge_type_definition(existing, ty)?,
+                        None => {
+                            types.insert(ty.name.node.to_string(), ty);
+                        }
+                    }
+                }
+                TypeSystemDefinition::Directive(_) => {}
+            }
+        }
+
+        let query_type = query_type.unwrap_or_else(|| "Query".to_string());
+        if !types.contains_key(&query_type) {
+            return Err(format!("The schema has no query type \"{}\"", query_type).into());
+        }
+        let mutation_type =
+            mutation_type.or_else(|| types.contains_key("Mutation").then(|| "Mutation".into()));
+        let subscription_type = subscription_type.or_else(|| {
+            types
+                .contains_key("Subscription")
+                .then(|| "Subscription".into())
+        });
+        let leaf_types = types
+            .values()
+            .filter(|ty| matches!(ty.kind, TypeKind::Scalar | TypeKind::Enum(_)))
+            .map(|ty| ty.name.node.to_string())
+            .chain(BUILTIN_SCALARS.iter().map(ToString::to_string))
+            .collect();
+
+        Ok(Self {
+            types,
+            query_type,
+            mutation_type,
+            subscription_type,
+            leaf_types,
+        })
+    }
+
+    pub(crate) fn is_leaf(&self, ty: &BaseType) -> bool {
+        match ty {
+            BaseType::Named(name) => self.leaf_types.contains(name.as_str()),
+            BaseType::List(ty) => self.is_leaf(&ty.base),
+        }
+    }
+
+    /// Creates the dynamic type of a type definition, `resolver` creates the
+    /// field with the resolver of each field of an object.
+    pub(crate) fn create_type(
+        &self,
+        ty: &TypeDefinition,
+        mut resolver: impl FnMut(&FieldDefinition, bool) -> Result<Field, SchemaError>,
+    ) -> Result<Type, SchemaError> {
+        let name = ty.name.node.as_str();
+        let description = ty.description.as_ref().map(|desc| desc.node.clone());
+        Ok(match &ty.kind {
+            TypeKind::Scalar => {
+                let mut scalar = Scalar::new(name);
+                scalar.description = description;
+                Type::Scalar(scalar)
+            }
+            TypeKind::Object(object_type) => {
+                let mut object = Object::new(name);
+                object.description = description;
+                for implement in &object_type.implements {
+                    object = object.implement(implement.node.as_str());
+                }
+                for field in &object_type.fields {
+                    let field = &field.node;
+                    let is_leaf = self.is_leaf(&field.ty.node.base);
+                    object = object.field(create_field(resolver(field, is_leaf)?, field));
+                }
+                Type::Object(object)
+            }
+            TypeKind::Interface(interface_type) => {
+                let mut interface = Interface::new(name);
+                interface.description = description;
+                for implement in &interface_type.implements {
+                    interface = interface.implement(implement.node.as_str());
+                }
+                for field in &interface_type.fields {
+                    let field = &field.node;
+                    let mut interface_field = InterfaceField::new(
+                        field.name.node.as_str(),
+                        convert_type_ref(&field.ty.node),
+                    );
+                    interface_field.description =
+                        field.description.as_ref().map(|desc| desc.node.clone());
+                    for argument in &field.arguments {
+                        interface_field =
+                            interface_field.argument(create_input_value(&argument.node));
+                    }
+                    interface = interface.field(interface_field);
+                }
+                Type::Interface(interface)
+            }
+            TypeKind::Union(union_type) => {
+                let mut union = crate::dynamic::Union::new(name);
+                union.description = description;
+                for member in &union_type.members {
+                    union = union.possible_type(member.node.as_str());
+                }
+                Type::Union(union)
+            }
+            TypeKind::Enum(enum_type) => {
+                let mut enum_ = Enum::new(name);
+                enum_.description = description;
+                for value in &enum_type.values {
+                    let value = &value.node;
+                    let mut item = EnumItem::new(value.value.node.as_str());
+                    item.description = value.description.as_ref().map(|desc| desc.node.clone());
+                    item.deprecation = deprecation(&value.directives);
+                    enum_ = enum_.item(item);
+                }
+                Type::Enum(enum_)
+            }
+            TypeKind::InputObject(input_object_type) => {
+                let mut input_object = InputObject::new(name);
+                input_object.description = description;
+                for field in &input_object_type.fields {
+                    input_object = input_object.field(create_input_value(&field.node));
+                }
+                Type::InputObject(input_object)
+            }
+        })
+    }
+}
+
+fn merge_type_definition(
+    existing: &mut TypeDefinition,
+    ty: TypeDefinition,
+) -> Result<(), SchemaError> {
+    match (&mut existing.kind, ty.kind) {
+        (TypeKind::Object(existing), TypeKind::Object(object)) => {
+            existing.implements.extend(object.implements);
+            existing.fields.extend(object.fields);
+        }
+        (TypeKind::Interface(existing), TypeKind::Interface(interface)) => {
+            existing.implements.extend(interface.implements);
+            existing.fields.extend(interface.fields);
+        }
+        (TypeKind::Union(existing), TypeKind::Union(union)) => {
+            existing.members.extend(union.members);
+        }
+        (TypeKind::Enum(existing), TypeKind::Enum(enum_type)) => {
+            existing.values.extend(enum_type.values);
+        }
+        (TypeKind::InputObject(existing), TypeKind::InputObject(input_object)) => {
+            existing.fields.extend(input_object.fields);
+        }
+        (TypeKind::Scalar, TypeKind::Scalar) => {}
+        _ => {
+            return Err(format!("Type \"{}\" is defined with different kinds", ty.name.node).into())
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn convert_type_ref(ty: &crate::parser::types::Type) -> TypeRef {
+    fn convert(ty: &crate::parser::types::Type) -> TypeRefInner {
+        let inner = match &ty.base {
+            BaseType::Named(name) => TypeRefInner::Named(name.to_string().into()),
+            BaseType::List(ty) => TypeRefInner::List(Box::new(convert(ty))),
+        };
+        if ty.nullable {
+            inner
+        } else {
+            TypeRefInner::NonNull(Box::new(inner))
+        }
+    }
+    TypeRef(convert(ty))
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Deprecation {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node == "deprecated")
+        .map(|directive| Deprecation::Deprecated {
+            reason: match directive.node.get_argument("reason") {
+                Some(Positioned {
+                    node: Value::String(reason),
+                    ..
+                }) => Some(reason.clone()),
+                _ => None,
+            },
+        })
+        .unwrap_or(Deprecation::NoDeprecated)
+}
+
+pub(crate) fn create_field(mut field: Field, definition: &FieldDefinition) -> Field {
+    field.description = definition
+        .description
+        .as_ref()
+        .map(|desc| desc.node.clone());
+    field.deprecation = deprecation(&definition.directives);
+    for argument in &definition.arguments {
+        field = field.argument(create_input_value(&argument.node));
+    }
+    field
+}
+
+fn create_input_value(definition: &InputValueDefinition) -> InputValue {
+    let mut input_value = InputValue::new(
+        definition.name.node.as_str(),
+        convert_type_ref(&definition.ty.node),
+    );
+    if let Some(description) = &definition.description {
+        input_value = input_value.description(description.node.clone());
+    }
+    if let Some(default_value) = &definition.default_value {
+        input_value = input_value.default_value(default_value.node.clone());
+    }
+    input_value
+}
+
+/// The resolvers of a schema built with [`Schema::from_sdl`]
+///
+/// The resolvers are bound to the fields by the `Type.field` name.
+#[derive(Default)]
+pub struct Resolvers {
+    fields: HashMap<String, BoxResolverFn>,
+    subscription_fields: HashMap<String, BoxSubscriptionResolverFn>,
+}
+
+impl Resolvers {
+    /// Create an empty set of resolvers
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Bind a resolver to a field, `path` is the `Type.field` name of the
+    /// field
+    #[must_use]
+    pub fn field<F>(mut self, path: impl Into<String>, resolver_fn: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        self.fields.insert(path.into(), Box::new(resolver_fn));
+        self
+    }
+
+    /// Bind a resolver to a field of the subscription type, `path` is the
+    /// `Type.field` name of the field
+    #[must_use]
+    pub fn subscription_field<F>(mut self, path: impl Into<String>, resolver_fn: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> SubscriptionFieldFuture<'a> + Send + Sync + 'static,
+    {
+        self.subscription_fields
+            .insert(path.into(), Box::new(resolver_fn));
+        self
+    }
+}
+
+impl Schema {
+    /// Create a schema builder from the SDL(Schema Definition Language) and
+    /// the resolvers bound to its fields
+    ///
+    /// The fields of the query and mutation types, and of the subscription
+    /// type, must have a resolver. The fields of the other object types
+    /// without a resolver read the property with the same name from the JSON
+    /// parent value, see [`Field::property`]. The bound resolvers read the
+    /// JSON parent value, at any level of the query, with
+    /// [`FieldValue::as_inner_value`](crate::dynamic::FieldValue::as_inner_value).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::{dynamic::*, value, Value};
+    ///
+    /// let sdl = r#"
+    ///     type User {
+    ///         name: String!
+    ///         age: Int!
+    ///         greeting: String!
+    ///         friends: [User!]!
+    ///     }
+    ///
+    ///     type Query {
+    ///         user: User
+    ///     }
+    /// "#;
+    ///
+    /// let resolvers = Resolvers::new()
+    ///     .field("Query.user", |_| {
+    ///         FieldFuture::new(async move {
+    ///             Ok(Some(FieldValue::value(value!({
+    ///                 "name": "Alice",
+    ///                 "age": 30,
+    ///                 "friends": [{ "name": "Bob", "age": 25, "friends": [] }],
+    ///             }))))
+    ///         })
+    ///     })
+    ///     .field("User.greeting", |ctx| {
+    ///         FieldFuture::new(async move {
+    ///             let user = ctx.parent_value.try_to_inner_value()?.clone().into_json()?;
+    ///             let name = user["name"].as_str().unwrap_or_default();
+    ///             Ok(Some(Value::from(format!("Hello, {}!", name))))
+    ///         })
+    ///     });
+    ///
+    /// let schema = Schema::from_sdl(sdl, resolvers)?.finish()?;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+    /// assert_eq!(
+    ///     schema
+    ///         .execute("{ user { name age greeting friends { greeting } } }")
+    ///         .await
+    ///         .into_result()
+    ///         .unwrap()
+    ///         .data,
+    ///     value!({
+    ///         "user": {
+    ///             "name": "Alice",
+    ///             "age": 30,
+    ///             "greeting": "Hello, Alice!",
+    ///             "friends": [{ "greeting": "Hello, Bob!" }],
+    ///         }
+    ///     })
+    /// );
+    /// # });
+    /// # Ok::<_, SchemaError>(())
+    /// ```
+    pub fn from_sdl(sdl: &str, mut resolvers: Resolvers) -> Result<SchemaBuilder, SchemaError> {
+        let doc = SdlDocument::parse(sdl)?;
+        let mut builder = Schema::build(
+            &doc.query_type,
+            doc.mutation_type.as_deref(),
+            doc.subscription_type.as_deref(),
+        );
+
+        for ty in doc.types.values() {
+            let type_name = ty.name.node.as_str();
+
+            if doc.subscription_type.as_deref() == Some(type_name) {
+                let fields = match &ty.kind {
+                    TypeKind::Object(object_type) => &object_type.fields,
+                    _ => {
+                        return Err(format!(
+                            "The subscription type \"{}\" is not an object",
+                            type_name
+                        )
+                        .into())
+                    }
+                };
+                let mut subscription = Subscription::new(type_name);
+                subscription.description = ty.description.as_ref().map(|desc| desc.node.clone());
+                for field in fields {
+                    let field = &field.node;
+                    let path = format!("{}.{}", type_name, field.name.node);
+                    let resolver_fn = resolvers
+                        .subscription_fields
+                        .remove(&path)
+                        .ok_or_else(|| format!("The field \"{}\" has no resolver", path))?;
+                    let mut subscription_field = SubscriptionField::new(
+                        field.name.node.as_str(),
+                        convert_type_ref(&field.ty.node),
+                        resolver_fn,
+                    );
+                    subscription_field.description =
+                        field.description.as_ref().map(|desc| desc.node.clone());
+                    subscription_field.deprecation = deprecation(&field.directives);
+                    for argument in &field.arguments {
+                        subscription_field =
+                            subscription_field.argument(create_input_value(&argument.node));
+                    }
+                    subscription = subscription.field(subscription_field);
+                }
+                builder = builder.register(subscription);
+                continue;
+            }
+
+            let is_root_type =
+                doc.query_type == type_name || doc.mutation_type.as_deref() == Some(type_name);
+            builder = builder.register(doc.create_type(ty, |field, _| {
+                let path = format!("{}.{}", type_name, field.name.node);
+                match resolvers.fields.remove(&path) {
+                    Some(resolver_fn) => Ok(Field::new(
+                        field.name.node.as_str(),
+                        convert_type_ref(&field.ty.node),
+                        resolver_fn,
+                    )),
+                    None if is_root_type => {
+                        Err(format!("The field \"{}\" has no resolver", path).into())
+                    }
+                    None => Ok(Field::property(
+                        field.name.node.as_str(),
+                        convert_type_ref(&field.ty.node),
+                    )),
+                }
+            })?);
+        }
+
+        if let Some(path) = resolvers
+            .fields
+            .keys()
+            .chain(resolvers.subscription_fields.keys())
+            .min()
+        {
+            return Err(format!("The resolver \"{}\" does not match any field", path).into());
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use crate::{dynamic::*, value, Request, Value};
+
+    const SDL: &str = r#"
+        enum Role {
+            ADMIN
+            USER
+        }
+
+        type User {
+            id: ID!
+            name: String!
+            role: Role!
+            friends: [User!]!
+            pet: Pet
+        }
+
+        type Dog {
+            name: String!
+            barks: Boolean!
+        }
+
+        type Cat {
+            name: String!
+        }
+
+        union Pet = Dog | Cat
+
+        type Query {
+            user(id: ID!): User
+        }
+
+        type Mutation {
+            rename(name: String!): String!
+        }
+    "#;
+
+    fn resolvers() -> Resolvers {
+        Resolvers::new()
+            .field("Query.user", |ctx| {
+                FieldFuture::new(async move {
+                    let id = ctx.args.try_get("id")?.string()?.to_string();
+                    Ok(Some(FieldValue::value(value!({
+                        "id": id,
+                        "name": "Alice",
+                        "role": "ADMIN",
+                        "friends": [{ "id": "2", "name": "Bob", "role": "USER", "friends": [] }],
+                        "pet": { "__typename": "Dog", "name": "Rex", "barks": true },
+                    }))))
+                })
+            })
+            .field("Mutation.rename", |ctx| {
+                FieldFuture::new(async move {
+                    Ok(Some(Value::from(ctx.args.try_get("name")?.string()?)))
+                })
+            })
+    }
+
+    #[tokio::test]
+    async fn from_sdl() {
+        let schema = Schema::from_sdl(SDL, resolvers())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute(
+                    r#"{
+                        user(id: "1") {
+                            id name role
+                            friends { name role friends { name } }
+                            pet { __typename ... on Dog { name barks } ... on Cat { name } }
+                        }
+                    }"#
+                )
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "user": {
+                    "id": "1",
+                    "name": "Alice",
+                    "role": "ADMIN",
+                    "friends": [{ "name": "Bob", "role": "USER", "friends": [] }],
+                    "pet": { "__typename": "Dog", "name": "Rex", "barks": true },
+                }
+            })
+        );
+        assert_eq!(
+            schema
+                .execute(r#"mutation { rename(name: "Carol") }"#)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "rename": "Carol" })
+        );
+    }
+
+    #[tokio::test]
+    async fn bound_resolver_overrides_default() {
+        let resolvers = resolvers().field("User.name", |ctx| {
+            FieldFuture::new(async move {
+                let user = ctx.parent_value.try_to_inner_value()?.clone().into_json()?;
+                Ok(Some(Value::from(
+                    user["name"].as_str().unwrap_or_default().to_uppercase(),
+                )))
+            })
+        });
+        let schema = Schema::from_sdl(SDL, resolvers).unwrap().finish().unwrap();
+
+        assert_eq!(
+            schema
+                .execute(r#"{ user(id: "1") { name friends { name } } }"#)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "user": { "name": "ALICE", "friends": [{ "name": "BOB" }] } })
+        );
+    }
+
+    #[tokio::test]
+    async fn parent_value_is_not_json() {
+        let resolvers = Resolvers::new()
+            .field("Query.user", |_| {
+                FieldFuture::new(async move { Ok(Some(FieldValue::owned_any(1i32))) })
+            })
+            .field("Mutation.rename", |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(""))) })
+            });
+        let schema = Schema::from_sdl(SDL, resolvers).unwrap().finish().unwrap();
+
+        assert_eq!(
+            schema.execute(r#"{ user(id: "1") { name } }"#).await.errors[0].message,
+            "The field \"User.name\" has no resolver, and its parent value is not a JSON value"
+        );
+    }
+
+    #[test]
+    fn missing_resolver() {
+        let resolvers = Resolvers::new().field("Query.user", |_| {
+            FieldFuture::new(async move { Ok(FieldValue::NONE) })
+        });
+        assert_eq!(
+            Schema::from_sdl(SDL, resolvers).err().unwrap().0,
+            "The field \"Mutation.rename\" has no resolver"
+        );
+    }
+
+    #[test]
+    fn unknown_resolver() {
+        let resolvers = resolvers().field("User.age", |_| {
+            FieldFuture::new(async move { Ok(FieldValue::NONE) })
+        });
+        assert_eq!(
+            Schema::from_sdl(SDL, resolvers).err().unwrap().0,
+            "The resolver \"User.age\" does not match any field"
+        );
+    }
+
+    #[tokio::test]
+    async fn subscription() {
+        let sdl = r#"
+            type Query {
+                value: Int!
+            }
+
+            type Subscription {
+                count(to: Int!): Int!
+            }
+        "#;
+        let resolvers = Resolvers::new()
+            .field("Query.value", |_| {
+                FieldFuture::new(async move { Ok(Some(Value::from(1))) })
+            })
+            .subscription_field("Subscription.count", |ctx| {
+                SubscriptionFieldFuture::new(async move {
+                    let to = ctx.args.try_get("to")?.i64()?;
+                    Ok(futures_util::stream::iter(1..=to).map(|n| Ok(FieldValue::value(n))))
+                })
+            });
+        let schema = Schema::from_sdl(sdl, resolvers).unwrap().finish().unwrap();
+
+        let values = schema
+            .execute_stream(Request::new("subscription { count(to: 2) }"))
+            .map(|resp| resp.into_result().unwrap().data)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(values, vec![value!({ "count": 1 }), value!({ "count": 2 })]);
+    }
+}
@@ -0,0 +1,371 @@
+use std::fmt::{self, Debug};
+
+use futures_util::{future::BoxFuture, FutureExt};
+
+use crate::{
+    dynamic::FieldValue,
+    registry::{MetaType, MetaTypeId, Registry},
+    Context, Error, InputType, OutputType, ServerResult, Value,
+};
+
+type StaticRegisterFn = fn(&mut Registry) -> String;
+
+type StaticResolveFn = for<'a> fn(
+    &'a Context<'a>,
+    &'a FieldValue<'a>,
+) -> Option<BoxFuture<'a, ServerResult<Option<Value>>>>;
+
+/// A type defined with the static API, such as `#[derive(SimpleObject)]`,
+/// that is registered in a dynamic schema
+///
+/// Use [`SchemaBuilder::register_output_type`](crate::dynamic::SchemaBuilder::register_output_type)
+/// and [`SchemaBuilder::register_input_type`](crate::dynamic::SchemaBuilder::register_input_type)
+/// to register it.
+pub struct StaticType {
+    pub(crate) name: String,
+    pub(crate) rust_typename: &'static str,
+    pub(crate) type_id: Option<MetaTypeId>,
+    register_fn: StaticRegisterFn,
+    resolve_fn: Option<StaticResolveFn>,
+}
+
+impl Debug for StaticType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticType")
+            .field("name", &self.name)
+            .field("rust_typename", &self.rust_typename)
+            .finish()
+    }
+}
+
+fn resolve_output_type<'a, T: OutputType + 'static>(
+    ctx: &'a Context<'a>,
+    value: &'a FieldValue<'a>,
+) -> Option<BoxFuture<'a, ServerResult<Option<Value>>>> {
+    let value = value.downcast_ref::<T>()?;
+    Some(
+        async move {
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            OutputType::resolve(value, &ctx_obj, ctx.item)
+                .await
+                .map(Some)
+        }
+        .boxed(),
+    )
+}
+
+impl StaticType {
+    pub(crate) fn output<T: OutputType + 'static>() -> Self {
+        Self {
+            name: T::type_name().into_owned(),
+            rust_typename: std::any::type_name::<T>(),
+            type_id: None,
+            register_fn: <T as OutputType>::create_type_info,
+            resolve_fn: Some(resolve_output_type::<T>),
+        }
+    }
+
+    pub(crate) fn input<T: InputType>() -> Self {
+        Self {
+            name: T::type_name().into_owned(),
+            rust_typename: std::any::type_name::<T>(),
+            type_id: None,
+            register_fn: <T as InputType>::create_type_info,
+            resolve_fn: None,
+        }
+    }
+
+    /// A type that is not registered directly, but is referenced by a
+    /// registered static type.
+    pub(crate) fn referenced(meta: &MetaType) -> Self {
+        Self {
+            name: meta.name().to_string(),
+            rust_typename: meta.rust_typename().unwrap_or_default(),
+            type_id: Some(meta.type_id()),
+            register_fn: |_| String::new(),
+            resolve_fn: None,
+        }
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn register(&mut self, registry: &mut Registry) {
+        (self.register_fn)(registry);
+        self.type_id = registry.types.get(&self.name).map(MetaType::type_id);
+    }
+
+    #[inline]
+    pub(crate) fn is_object(&self) -> bool {
+        self.type_id == Some(MetaTypeId::Object)
+    }
+
+    #[inline]
+    pub(crate) fn is_output_type(&self) -> bool {
+        self.resolve_fn.is_some()
+            || matches!(self.type_id, Some(MetaTypeId::Scalar | MetaTypeId::Enum))
+    }
+
+    #[inline]
+    pub(crate) fn is_input_type(&self) -> bool {
+        matches!(
+            self.type_id,
+            Some(MetaTypeId::Scalar | MetaTypeId::Enum | MetaTypeId::InputObject)
+        )
+    }
+
+    pub(crate) async fn resolve(
+        &self,
+        ctx: &Context<'_>,
+        value: &FieldValue<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if let Some(fut) = self
+            .resolve_fn
+            .and_then(|resolve_fn| resolve_fn(ctx, value))
+        {
+            return fut.await;
+        }
+
+        match value {
+            FieldValue::Value(value)
+                if matches!(self.type_id, Some(MetaTypeId::Scalar | MetaTypeId::Enum)) =>
+            {
+                Ok(Some(value.clone()))
+            }
+            _ => Err(ctx.set_error_path(
+                Error::new(format!(
+                    "internal: invalid value for type \"{}\", expected a value of \"{}\"",
+                    self.name, self.rust_typename
+                ))
+                .into_server_error(ctx.item.pos),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dynamic::*, value, Enum, InputObject, Name, SimpleObject, Value};
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    #[graphql(internal)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(internal)]
+    struct Item {
+        name: String,
+        color: Color,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(internal)]
+    struct ItemInput {
+        name: String,
+        color: Color,
+    }
+
+    #[tokio::test]
+    async fn output_type() {
+        let query = Object::new("Query")
+            .field(Field::new(
+                "item",
+                TypeRef::named("Item").non_null(),
+                |_| {
+                    FieldFuture::new(async move {
+                        Ok(Some(FieldValue::owned_any(Item {
+                            name: "a".to_string(),
+                            color: Color::Red,
+                        })))
+                    })
+                },
+            ))
+            .field(Field::new(
+                "items",
+                TypeRef::named("Item").non_null().list().non_null(),
+                |ctx| {
+                    FieldFuture::new(async move {
+                        let items = ctx.data::<Vec<Item>>()?;
+                        Ok(Some(FieldValue::list(
+                            items.iter().map(FieldValue::borrowed_any),
+                        )))
+                    })
+                },
+            ))
+            .field(Field::new(
+                "color",
+                TypeRef::named("Color").non_null(),
+                |_| FieldFuture::new(async move { Ok(Some(Value::Enum(Name::new("GREEN")))) }),
+            ));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .register_output_type::<Item>()
+            .data(vec![
+                Item {
+                    name: "b".to_string(),
+                    color: Color::Green,
+                },
+                Item {
+                    name: "c".to_string(),
+                    color: Color::Red,
+                },
+            ])
+            .finish()
+            .unwrap();
+
+        assert!(schema.sdl().contains("type Item {"));
+        assert_eq!(
+            schema
+                .execute("{ item { name color } items { name } color }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({
+                "item": { "name": "a", "color": "RED" },
+                "items": [{ "name": "b" }, { "name": "c" }],
+                "color": "GREEN",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn input_type() {
+        let query = Object::new("Query").field(
+            Field::new("name", TypeRef::STRING.non_null(), |ctx| {
+                FieldFuture::new(async move {
+                    let input = ctx.args.try_get("input")?.parse::<ItemInput>()?;
+                    Ok(Some(Value::from(format!(
+                        "{}:{}",
+                        input.name,
+                        input.color == Color::Green
+                    ))))
+                })
+            })
+            .argument(InputValue::new(
+                "input",
+                TypeRef::named("ItemInput").non_null(),
+            )),
+        );
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .register_input_type::<ItemInput>()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute(r#"{ name(input: { name: "a", color: GREEN }) }"#)
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "name": "a:true" })
+        );
+    }
+
+    #[tokio::test]
+    async fn union_member() {
+        let other = Object::new("Other").field(Field::new("value", TypeRef::INT, |_| {
+            FieldFuture::new(async { Ok(Some(Value::from(1))) })
+        }));
+        let union = Union::new("ItemOrOther")
+            .possible_type("Item")
+            .possible_type("Other");
+        let query =
+            Object::new("Query").field(Field::new("value", TypeRef::named("ItemOrOther"), |_| {
+                FieldFuture::new(async move {
+                    Ok(Some(FieldValue::with_type(
+                        FieldValue::owned_any(Item {
+                            name: "a".to_string(),
+                            color: Color::Red,
+                        }),
+                        "Item",
+                    )))
+                })
+            }));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .register(other)
+            .register(union)
+            .register_output_type::<Item>()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema
+                .execute("{ value { __typename ... on Item { name } } }")
+                .await
+                .into_result()
+                .unwrap()
+                .data,
+            value!({ "value": { "__typename": "Item", "name": "a" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_value() {
+        let query = Object::new("Query").field(Field::new("item", TypeRef::named("Item"), |_| {
+            FieldFuture::new(async move { Ok(Some(FieldValue::owned_any(1i32))) })
+        }));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .register_output_type::<Item>()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            schema.execute("{ item { name } }").await.errors[0].message,
+            format!(
+                "internal: invalid value for type \"Item\", expected a value of \"{}\"",
+                std::any::type_name::<Item>()
+            )
+        );
+    }
+
+    #[test]
+    fn referenced_object() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct Wrapper {
+            item: Item,
+        }
+
+        let query = Object::new("Query").field(Field::new("item", TypeRef::named("Item"), |_| {
+            FieldFuture::new(async move { Ok(FieldValue::NONE) })
+        }));
+        assert_eq!(
+            Schema::build("Query", None, None)
+                .register(query)
+                .register_output_type::<Wrapper>()
+                .finish()
+                .unwrap_err()
+                .0,
+            "Field \"Query.item\" must return a output type"
+        );
+    }
+
+    #[test]
+    fn name_conflict() {
+        let query = Object::new("Query").field(Field::new("item", TypeRef::named("Item"), |_| {
+            FieldFuture::new(async move { Ok(FieldValue::NONE) })
+        }));
+        let color = Enum::new("Color").item("RED");
+        assert_eq!(
+            Schema::build("Query", None, None)
+                .register(query)
+                .register(color)
+                .register_output_type::<Item>()
+                .finish()
+                .unwrap_err()
+                .0,
+            "Type \"Color\" already exists"
+        );
+    }
+}
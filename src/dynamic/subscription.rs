@@ -13,9 +13,9 @@ use crate::{
     extensions::ResolveInfo,
     parser::types::Selection,
     registry::{Deprecation, MetaField, MetaType, Registry},
-    subscription::BoxFieldStream,
+    subscription::{BoxFieldStream, SubscriptionBuffer},
     ContextSelectionSet, Name, QueryPathNode, QueryPathSegment, Response, Result, ServerResult,
-    Value,
+    SubscriptionBufferPolicy, Value,
 };
 
 type BoxResolveFut<'a> = BoxFuture<'a, Result<BoxStream<'a, Result<FieldValue<'a>>>>>;
@@ -52,6 +52,7 @@ pub struct SubscriptionField {
     pub(crate) ty: TypeRef,
     pub(crate) resolver_fn: BoxResolverFn,
     pub(crate) deprecation: Deprecation,
+    pub(crate) buffer_policy: Option<SubscriptionBufferPolicy>,
 }
 
 impl SubscriptionField {
@@ -69,6 +70,7 @@ impl SubscriptionField {
             ty: ty.into(),
             resolver_fn: Arc::new(resolver_fn),
             deprecation: Deprecation::NoDeprecated,
+            buffer_policy: None,
         }
     }
 
@@ -87,6 +89,16 @@ impl SubscriptionField {
         self.arguments.insert(input_value.name.clone(), input_value);
         self
     }
+
+    /// Set the buffering policy of the subscription field, the policy of the
+    /// schema is used by default
+    #[inline]
+    pub fn buffer_policy(self, policy: SubscriptionBufferPolicy) -> Self {
+        Self {
+            buffer_policy: Some(policy),
+            ..self
+        }
+    }
 }
 
 impl Debug for SubscriptionField {
@@ -97,6 +109,7 @@ impl Debug for SubscriptionField {
             .field("arguments", &self.arguments)
             .field("ty", &self.ty)
             .field("deprecation", &self.deprecation)
+            .field("buffer_policy", &self.buffer_policy)
             .finish()
     }
 }
@@ -180,7 +193,11 @@ impl Subscription {
                     inaccessible: false,
                     tags: vec![],
                     override_from: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
                     compute_complexity: None,
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -195,11 +212,16 @@ impl Subscription {
                 extends: false,
                 shareable: false,
                 keys: None,
+                resolvable: true,
+                interface_object: false,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: vec![],
                 is_subscription: true,
                 rust_typename: None,
+                directive_invocations: Vec::new(),
             },
         );
 
@@ -216,8 +238,12 @@ impl Subscription {
             if let Selection::Field(field) = &selection.node {
                 if let Some(field_def) = self.fields.get(field.node.name.node.as_str()) {
                     let schema = schema.clone();
+                    let type_name = self.name.clone();
                     let field_type = field_def.ty.clone();
                     let resolver_fn = field_def.resolver_fn.clone();
+                    let buffer_policy = field_def
+                        .buffer_policy
+                        .unwrap_or(ctx.schema_env.subscription_buffer_policy);
                     let ctx = ctx.clone();
 
                     streams.push(
@@ -237,16 +263,19 @@ impl Subscription {
                                     .collect::<ServerResult<IndexMap<Name, Value>>>()?,
                             ));
 
-                            let mut stream = resolver_fn(ResolverContext {
+                            let stream = resolver_fn(ResolverContext {
                                 ctx: &ctx_field,
                                 args: arguments,
                                 parent_value: &FieldValue::NULL,
+                                parent_type: &type_name,
                             })
                             .0
                             .await
                             .map_err(|err| ctx_field.set_error_path(err.into_server_error(ctx_field.item.pos)))?;
+                            let stream = SubscriptionBuffer::new(stream, buffer_policy, &ctx_field);
+                            futures_util::pin_mut!(stream);
 
-                            while let Some(value) = stream.next().await.transpose().map_err(|err| ctx_field.set_error_path(err.into_server_error(ctx_field.item.pos)))? {
+                            while let Some(value) = stream.next().await.transpose()?.transpose().map_err(|err| ctx_field.set_error_path(err.into_server_error(ctx_field.item.pos)))? {
                                 let execute_fut = async {
                                     let ri = ResolveInfo {
                                         path_node: &QueryPathNode {
@@ -297,7 +326,7 @@ mod tests {
 
     use futures_util::StreamExt;
 
-    use crate::{dynamic::*, value, Value};
+    use crate::{dynamic::*, value, SubscriptionBufferPolicy, Value};
 
     #[tokio::test]
     async fn subscription() {
@@ -398,4 +427,50 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn buffer_policy() {
+        let query = Object::new("Query").field(Field::new("value", TypeRef::INT, |_| {
+            FieldFuture::new(async { Ok(FieldValue::NONE) })
+        }));
+
+        let values = |name| {
+            SubscriptionField::new(name, TypeRef::INT.non_null(), |_| {
+                SubscriptionFieldFuture::new(async {
+                    Ok(futures_util::stream::iter(0..10).map(|value| Ok(Value::from(value))))
+                })
+            })
+        };
+        let subscription = Subscription::new("Subscription")
+            .field(values("values"))
+            .field(values("dropOldest").buffer_policy(SubscriptionBufferPolicy::DropOldest(3)))
+            .field(values("unbounded").buffer_policy(SubscriptionBufferPolicy::Unbounded));
+
+        let schema = Schema::build("Query", None, Some(subscription.type_name()))
+            .register(query)
+            .register(subscription)
+            .subscription_buffer_policy(SubscriptionBufferPolicy::CoalesceLatest)
+            .finish()
+            .unwrap();
+
+        let collect = |query| {
+            schema
+                .execute_stream(query)
+                .map(|resp| resp.into_result().unwrap().data)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            collect("subscription { values }").await,
+            vec![value!({ "values": 9 })]
+        );
+        assert_eq!(
+            collect("subscription { dropOldest }").await,
+            vec![
+                value!({ "dropOldest": 7 }),
+                value!({ "dropOldest": 8 }),
+                value!({ "dropOldest": 9 }),
+            ]
+        );
+        assert_eq!(collect("subscription { unbounded }").await.len(), 10);
+    }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    dynamic::{Enum, InputObject, Interface, Object, Scalar, SchemaError, Subscription, Union},
+    dynamic::{
+        static_type::StaticType, Enum, InputObject, Interface, Object, Scalar, SchemaError,
+        Subscription, Union,
+    },
     registry::Registry,
 };
 
@@ -20,6 +23,8 @@ pub enum Type {
     Union(Union),
     /// Subscription
     Subscription(Subscription),
+    /// A type defined with the static API
+    Static(StaticType),
 }
 
 impl Type {
@@ -32,6 +37,7 @@ impl Type {
             Type::Interface(interface) => &interface.name,
             Type::Union(union) => &union.name,
             Type::Subscription(subscription) => &subscription.name,
+            Type::Static(ty) => &ty.name,
         }
     }
 
@@ -44,6 +50,15 @@ impl Type {
         }
     }
 
+    #[inline]
+    pub(crate) fn is_object(&self) -> bool {
+        match self {
+            Type::Object(_) => true,
+            Type::Static(ty) => ty.is_object(),
+            _ => false,
+        }
+    }
+
     #[inline]
     pub(crate) fn as_interface(&self) -> Option<&Interface> {
         if let Type::Interface(interface) = self {
@@ -80,6 +95,7 @@ impl Type {
             Type::Interface(_) => true,
             Type::Union(_) => true,
             Type::Subscription(_) => false,
+            Type::Static(ty) => ty.is_output_type(),
         }
     }
 
@@ -92,10 +108,16 @@ impl Type {
             Type::Interface(_) => false,
             Type::Union(_) => false,
             Type::Subscription(_) => false,
+            Type::Static(ty) => ty.is_input_type(),
         }
     }
 
     pub(crate) fn register(&self, registry: &mut Registry) -> Result<(), SchemaError> {
+        if matches!(self, Type::Static(_)) {
+            // registered with `StaticType::register`
+            return Ok(());
+        }
+
         if registry.types.contains_key(self.name()) {
             return Err(format!("Type \"{0}\" already exists", self.name()).into());
         }
@@ -108,6 +130,7 @@ impl Type {
             Type::Interface(interface) => interface.register(registry),
             Type::Union(union) => union.register(registry),
             Type::Subscription(subscription) => subscription.register(registry),
+            Type::Static(_) => unreachable!(),
         }
     }
 }
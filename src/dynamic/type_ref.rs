@@ -139,6 +139,11 @@ impl TypeRef {
         name: Cow::Borrowed("ID"),
         non_null: false,
     };
+    /// A Upload scalar type, see [`SchemaBuilder::enable_uploading`](crate::dynamic::SchemaBuilder::enable_uploading)
+    pub const UPLOAD: NamedTypeRefBuilder = NamedTypeRefBuilder {
+        name: Cow::Borrowed("Upload"),
+        non_null: false,
+    };
 
     /// Create a named type reference
     #[inline(always)]
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use indexmap::IndexMap;
 use serde::de::DeserializeOwned;
 
-use crate::{Error, Name, Result, Value};
+use crate::{Context, Error, InputType, Name, Result, Upload, UploadStream, UploadValue, Value};
 
 /// A value accessor
 pub struct ValueAccessor<'a>(&'a Value);
@@ -73,6 +73,41 @@ impl<'a> ValueAccessor<'a> {
         }
     }
 
+    /// Returns the upload value
+    ///
+    /// Returns an error for the files of the requests received by
+    /// [`receive_batch_body_streaming`](crate::http::receive_batch_body_streaming),
+    /// use [`ValueAccessor::upload_stream`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::dynamic::*;
+    ///
+    /// let mutation = Object::new("Mutation").field(
+    ///     Field::new("upload", TypeRef::STRING.non_null(), |ctx| {
+    ///         FieldFuture::new(async move {
+    ///             let file = ctx.args.try_get("file")?.upload(&ctx)?;
+    ///             Ok(Some(async_graphql::Value::from(file.filename)))
+    ///         })
+    ///     })
+    ///     .argument(InputValue::new("file", TypeRef::UPLOAD.non_null())),
+    /// );
+    /// ```
+    pub fn upload(&self, ctx: &Context<'_>) -> Result<UploadValue> {
+        Ok(self.upload_arg()?.value(ctx)?)
+    }
+
+    /// Returns a stream of the content of the uploaded file, see
+    /// [`Upload::stream`]
+    pub async fn upload_stream(&self, ctx: &Context<'_>) -> Result<UploadStream> {
+        Ok(self.upload_arg()?.stream(ctx).await?)
+    }
+
+    fn upload_arg(&self) -> Result<Upload> {
+        Upload::parse(Some(self.0.clone())).map_err(|_| Error::new("The value is not an upload"))
+    }
+
     /// Returns the object accessor
     pub fn object(&self) -> Result<ObjectAccessor<'_>> {
         if let Value::Object(obj) = self.0 {
@@ -91,6 +126,13 @@ impl<'a> ValueAccessor<'a> {
         }
     }
 
+    /// Parse the value to an input type defined with the static API, such as
+    /// `#[derive(InputObject)]`
+    pub fn parse<T: InputType>(&self) -> Result<T> {
+        T::parse(Some(self.0.clone()))
+            .map_err(|err| Error::new(err.into_server_error(Default::default()).message))
+    }
+
     /// Deserialize the value to `T`
     pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
         T::deserialize(self.0.clone()).map_err(|err| format!("internal: {}", err).into())
@@ -0,0 +1,482 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+    },
+    PathSegment, QueryPathSegment, Response, ServerError, ServerResult, Value,
+};
+
+/// The header sent by a federation gateway to ask a subgraph for a trace.
+pub(crate) const INCLUDE_TRACE_HEADER: &str = "apollo-federation-include-trace";
+
+/// Request data that enables the [`ApolloFederatedTracing`] extension for a
+/// request.
+///
+/// A federation gateway asks a subgraph for a trace with the
+/// `apollo-federation-include-trace: ftv1` header.
+/// [`http::apply_headers`](crate::http::apply_headers), which is used by the
+/// integrations, adds this to the data of the GraphQL request when the header
+/// is present. Otherwise use [`IncludeFederatedTrace::from_headers`] to check
+/// the headers of the HTTP request.
+#[cfg_attr(docsrs, doc(cfg(feature = "apollo_tracing")))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IncludeFederatedTrace;
+
+impl IncludeFederatedTrace {
+    /// Returns `Some` if the headers contain
+    /// `apollo-federation-include-trace: ftv1`.
+    pub fn from_headers(headers: &http::HeaderMap) -> Option<Self> {
+        headers
+            .get_all(INCLUDE_TRACE_HEADER)
+            .iter()
+            .find_map(|value| Self::from_header_value(value.as_bytes()))
+    }
+
+    pub(crate) fn from_header_value(value: &[u8]) -> Option<Self> {
+        if value.eq_ignore_ascii_case(b"ftv1") {
+            Some(IncludeFederatedTrace)
+        } else {
+            None
+        }
+    }
+}
+
+/// Apollo federated tracing extension
+///
+/// Adds the protobuf `ftv1` trace used by Apollo federation gateways,
+/// encoded in base64, to the `ftv1` field of the extensions of the response.
+/// The trace contains the timing and the errors of every resolved field.
+///
+/// Tracing is only enabled for the requests whose data contains
+/// [`IncludeFederatedTrace`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{
+///     extensions::{ApolloFederatedTracing, IncludeFederatedTrace},
+///     *,
+/// };
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(ApolloFederatedTracing)
+///     .finish();
+///
+/// let mut headers = ::http::HeaderMap::new();
+/// headers.insert("apollo-federation-include-trace", "ftv1".parse().unwrap());
+///
+/// let mut request = Request::new("{ value }");
+/// if let Some(include_trace) = IncludeFederatedTrace::from_headers(&headers) {
+///     request = request.data(include_trace);
+/// }
+/// let resp = schema.execute(request).await;
+/// assert!(resp.extensions.contains_key("ftv1"));
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "apollo_tracing")))]
+pub struct ApolloFederatedTracing;
+
+impl ExtensionFactory for ApolloFederatedTracing {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ApolloFederatedTracingExtension {
+            start_time: SystemTime::now(),
+            start_instant: Instant::now(),
+            fields: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum NodeId {
+    ResponseName(String),
+    Index(u32),
+}
+
+struct FieldState {
+    path: Vec<NodeId>,
+    original_field_name: String,
+    ty: String,
+    parent_type: String,
+    start_time: u64,
+    end_time: u64,
+}
+
+struct ApolloFederatedTracingExtension {
+    start_time: SystemTime,
+    start_instant: Instant,
+    fields: Mutex<Vec<FieldState>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for ApolloFederatedTracingExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if ctx.data_opt::<IncludeFederatedTrace>().is_none() {
+            return next.run(ctx, operation_name).await;
+        }
+
+        let resp = next.run(ctx, operation_name).await;
+        let end_time = SystemTime::now();
+        let duration = nanos(self.start_instant.elapsed());
+
+        let fields = std::mem::take(&mut *self.fields.lock().unwrap());
+        let mut root = Node::default();
+        for field in fields {
+            let node = root.child_at(&field.path);
+            node.original_field_name = field.original_field_name;
+            node.ty = field.ty;
+            node.parent_type = field.parent_type;
+            node.start_time = field.start_time;
+            node.end_time = field.end_time;
+        }
+        for err in &resp.errors {
+            let path = err
+                .path
+                .iter()
+                .map(|segment| match segment {
+                    PathSegment::Field(name) => NodeId::ResponseName(name.clone()),
+                    PathSegment::Index(idx) => NodeId::Index(*idx as u32),
+                })
+                .collect::<Vec<_>>();
+            root.child_at(&path).errors.push(err.clone());
+        }
+
+        let mut trace = Vec::new();
+        write_message(&mut trace, 4, &timestamp(self.start_time));
+        write_message(&mut trace, 3, &timestamp(end_time));
+        write_uint(&mut trace, 11, duration);
+        write_message(&mut trace, 14, &root.encode());
+
+        resp.extension("ftv1", Value::String(base64::encode(trace)))
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if ctx.data_opt::<IncludeFederatedTrace>().is_none() {
+            return next.run(ctx, info).await;
+        }
+
+        let mut path = Vec::new();
+        info.path_node.for_each(|segment| {
+            path.push(match segment {
+                QueryPathSegment::Name(name) => NodeId::ResponseName((*name).to_string()),
+                QueryPathSegment::Index(idx) => NodeId::Index(*idx as u32),
+            })
+        });
+        let original_field_name = info.name.to_string();
+        let ty = info.return_type.to_string();
+        let parent_type = info.parent_type.to_string();
+        let start_time = nanos(self.start_instant.elapsed());
+
+        let res = next.run(ctx, info).await;
+
+        let end_time = nanos(self.start_instant.elapsed());
+        self.fields.lock().unwrap().push(FieldState {
+            path,
+            original_field_name,
+            ty,
+            parent_type,
+            start_time,
+            end_time,
+        });
+        res
+    }
+}
+
+/// A node of the trace tree, the root node has no id.
+#[derive(Default)]
+struct Node {
+    id: Option<NodeId>,
+    original_field_name: String,
+    ty: String,
+    parent_type: String,
+    errors: Vec<ServerError>,
+    start_time: u64,
+    end_time: u64,
+    children: Vec<Node>,
+    /// The indexes of the children by id.
+    child_indexes: HashMap<NodeId, usize>,
+}
+
+impl Node {
+    fn child_at(&mut self, path: &[NodeId]) -> &mut Node {
+        match path.split_first() {
+            Some((id, rest)) => {
+                let children = &mut self.children;
+                let idx = *self.child_indexes.entry(id.clone()).or_insert_with(|| {
+                    children.push(Node {
+                        id: Some(id.clone()),
+                        ..Node::default()
+                    });
+                    children.len() - 1
+                });
+                self.children[idx].child_at(rest)
+            }
+            None => self,
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match &self.id {
+            Some(NodeId::ResponseName(name)) => {
+                write_key(&mut buf, 1, 2);
+                write_bytes(&mut buf, name.as_bytes());
+            }
+            Some(NodeId::Index(idx)) => {
+                write_key(&mut buf, 2, 0);
+                write_varint(&mut buf, *idx as u64);
+            }
+            None => {}
+        }
+        write_string(&mut buf, 3, &self.ty);
+        write_uint(&mut buf, 8, self.start_time);
+        write_uint(&mut buf, 9, self.end_time);
+        for err in &self.errors {
+            write_message(&mut buf, 11, &encode_error(err));
+        }
+        for child in &self.children {
+            write_message(&mut buf, 12, &child.encode());
+        }
+        write_string(&mut buf, 13, &self.parent_type);
+        if let Some(NodeId::ResponseName(name)) = &self.id {
+            if *name != self.original_field_name {
+                write_string(&mut buf, 14, &self.original_field_name);
+            }
+        }
+        buf
+    }
+}
+
+fn encode_error(err: &ServerError) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_string(&mut buf, 1, &err.message);
+    for pos in &err.locations {
+        let mut location = Vec::new();
+        write_uint(&mut location, 1, pos.line as u64);
+        write_uint(&mut location, 2, pos.column as u64);
+        write_message(&mut buf, 2, &location);
+    }
+    write_string(&mut buf, 4, &serde_json::to_string(err).unwrap_or_default());
+    buf
+}
+
+fn timestamp(time: SystemTime) -> Vec<u8> {
+    let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let mut buf = Vec::new();
+    write_uint(&mut buf, 1, duration.as_secs());
+    write_uint(&mut buf, 2, duration.subsec_nanos() as u64);
+    buf
+}
+
+fn nanos(duration: std::time::Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_key(buf: &mut Vec<u8>, field: u32, wire_type: u8) {
+    write_varint(buf, ((field as u64) << 3) | wire_type as u64);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_uint(buf: &mut Vec<u8>, field: u32, value: u64) {
+    if value != 0 {
+        write_key(buf, field, 0);
+        write_varint(buf, value);
+    }
+}
+
+fn write_string(buf: &mut Vec<u8>, field: u32, value: &str) {
+    if !value.is_empty() {
+        write_key(buf, field, 2);
+        write_bytes(buf, value.as_bytes());
+    }
+}
+
+fn write_message(buf: &mut Vec<u8>, field: u32, message: &[u8]) {
+    write_key(buf, field, 2);
+    write_bytes(buf, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    enum Field {
+        Varint(u64),
+        Bytes(Vec<u8>),
+    }
+
+    fn read_varint(buf: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = buf[0];
+            *buf = &buf[1..];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+            shift += 7;
+        }
+    }
+
+    fn decode(mut buf: &[u8]) -> Vec<(u64, Field)> {
+        let mut fields = Vec::new();
+        while !buf.is_empty() {
+            let key = read_varint(&mut buf);
+            let field = match key & 7 {
+                0 => Field::Varint(read_varint(&mut buf)),
+                2 => {
+                    let len = read_varint(&mut buf) as usize;
+                    let bytes = buf[..len].to_vec();
+                    buf = &buf[len..];
+                    Field::Bytes(bytes)
+                }
+                _ => unreachable!(),
+            };
+            fields.push((key >> 3, field));
+        }
+        fields
+    }
+
+    fn bytes(fields: &[(u64, Field)], tag: u64) -> Vec<&[u8]> {
+        fields
+            .iter()
+            .filter_map(|(field, value)| match value {
+                Field::Bytes(bytes) if *field == tag => Some(bytes.as_slice()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn string(fields: &[(u64, Field)], tag: u64) -> Option<String> {
+        bytes(fields, tag)
+            .first()
+            .map(|bytes| String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn varint(fields: &[(u64, Field)], tag: u64) -> Option<u64> {
+        fields.iter().find_map(|(field, value)| match value {
+            Field::Varint(value) if *field == tag => Some(*value),
+            _ => None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_federated_tracing() {
+        #[derive(SimpleObject)]
+        #[graphql(internal)]
+        struct Item {
+            id: i32,
+            name: String,
+        }
+
+        struct Query;
+
+        #[Object(internal)]
+        impl Query {
+            async fn items(&self) -> Vec<Item> {
+                vec![
+                    Item {
+                        id: 1,
+                        name: "a".to_string(),
+                    },
+                    Item {
+                        id: 2,
+                        name: "b".to_string(),
+                    },
+                ]
+            }
+
+            async fn fail(&self) -> Result<i32> {
+                Err("bad".into())
+            }
+        }
+
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ApolloFederatedTracing)
+            .finish();
+
+        let resp = schema.execute("{ items { id } }").await;
+        assert!(!resp.extensions.contains_key("ftv1"));
+
+        let resp = schema
+            .execute(Request::new("{ list: items { id } fail }").data(IncludeFederatedTrace))
+            .await;
+        let trace = match resp.extensions.get("ftv1") {
+            Some(Value::String(trace)) => base64::decode(trace).unwrap(),
+            _ => panic!("missing trace"),
+        };
+        let trace = decode(&trace);
+        assert!(varint(&trace, 11).is_some());
+        assert_eq!(bytes(&trace, 3).len(), 1);
+        assert_eq!(bytes(&trace, 4).len(), 1);
+
+        let root = decode(bytes(&trace, 14)[0]);
+        let children = bytes(&root, 12).into_iter().map(decode).collect::<Vec<_>>();
+        assert_eq!(children.len(), 2);
+
+        let list = children
+            .iter()
+            .find(|node| string(node, 1).as_deref() == Some("list"))
+            .unwrap();
+        assert_eq!(string(list, 14).as_deref(), Some("items"));
+        assert_eq!(string(list, 3).as_deref(), Some("[Item!]!"));
+        assert_eq!(string(list, 13).as_deref(), Some("Query"));
+        let elements = bytes(list, 12).into_iter().map(decode).collect::<Vec<_>>();
+        assert_eq!(elements.len(), 2);
+        assert!(elements.iter().any(|node| varint(node, 2) == Some(0)));
+        assert!(elements.iter().any(|node| varint(node, 2) == Some(1)));
+        let id = decode(bytes(&elements[0], 12)[0]);
+        assert_eq!(string(&id, 1).as_deref(), Some("id"));
+        assert_eq!(string(&id, 3).as_deref(), Some("Int!"));
+        assert_eq!(string(&id, 13).as_deref(), Some("Item"));
+        assert!(varint(&id, 9) >= varint(&id, 8));
+
+        let fail = children
+            .iter()
+            .find(|node| string(node, 1).as_deref() == Some("fail"))
+            .unwrap();
+        let error = decode(bytes(fail, 11)[0]);
+        assert_eq!(string(&error, 1).as_deref(), Some("bad"));
+        let location = decode(bytes(&error, 2)[0]);
+        assert_eq!(varint(&location, 1), Some(1));
+        assert_eq!(varint(&location, 2), Some(22));
+    }
+}
@@ -0,0 +1,163 @@
+use std::{any::TypeId, sync::Arc};
+
+use crate::{
+    dataloader::MetricsCollector,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute},
+    value, Name, Response, Value,
+};
+
+/// DataLoader metrics extension
+///
+/// This extension will output the `dataloader` field containing the
+/// statistics of each loader in the response extension of each query.
+///
+/// The statistics are read from the
+/// [`MetricsCollector`](crate::dataloader::MetricsCollector) in the request
+/// data, which must be attached to the data loaders with
+/// [`DataLoader::observer`](crate::dataloader::DataLoader::observer). The
+/// durations are in nanoseconds.
+///
+/// Create a new collector for each request, the statistics of a collector
+/// accumulate for its whole lifetime. A collector in the schema data or the
+/// session data is ignored.
+#[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
+pub struct DataLoaderMetrics;
+
+impl ExtensionFactory for DataLoaderMetrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(DataLoaderMetricsExtension)
+    }
+}
+
+struct DataLoaderMetricsExtension;
+
+#[async_trait::async_trait]
+impl Extension for DataLoaderMetricsExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let resp = next.run(ctx, operation_name).await;
+        let collector = match ctx
+            .query_data
+            .and_then(|data| data.get(&TypeId::of::<MetricsCollector>()))
+            .and_then(|collector| collector.downcast_ref::<MetricsCollector>())
+        {
+            Some(collector) => collector,
+            None => return resp,
+        };
+
+        let stats = collector
+            .stats()
+            .into_iter()
+            .map(|(loader, stats)| {
+                (
+                    Name::new(loader),
+                    value!({
+                        "batches": stats.batches,
+                        "keys": stats.keys,
+                        "maxBatchSize": stats.max_batch_size,
+                        "averageBatchSize": stats.average_batch_size(),
+                        "cacheHits": stats.cache_hits,
+                        "cacheMisses": stats.cache_misses,
+                        "cacheHitRatio": stats.cache_hit_ratio(),
+                        "errors": stats.errors,
+                        "waitTime": stats.wait_time.as_nanos() as u64,
+                        "loadTime": stats.load_time.as_nanos() as u64,
+                        "maxLoadTime": stats.max_load_time.as_nanos() as u64,
+                    }),
+                )
+            })
+            .collect();
+        resp.extension("dataloader", Value::Object(stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{dataloader::*, *};
+
+    struct ValueLoader;
+
+    #[async_trait::async_trait]
+    impl Loader<i32> for ValueLoader {
+        type Value = i32;
+        type Error = ();
+
+        async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, i32>, ()> {
+            Ok(keys.iter().map(|n| (*n, *n)).collect())
+        }
+    }
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn value(&self, ctx: &Context<'_>, n: i32) -> Option<i32> {
+            ctx.data_unchecked::<DataLoader<ValueLoader, HashMapCache>>()
+                .load_one(n)
+                .await
+                .unwrap()
+        }
+    }
+
+    fn request(query: &str) -> Request {
+        let metrics = MetricsCollector::new();
+        let loader = DataLoader::with_cache(ValueLoader, tokio::spawn, HashMapCache::default())
+            .observer(metrics.clone());
+        Request::new(query).data(loader).data(metrics)
+    }
+
+    #[tokio::test]
+    async fn dataloader_metrics() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::DataLoaderMetrics)
+            .finish();
+
+        for _ in 0..2 {
+            let resp = schema
+                .execute(request("{ a: value(n: 1) b: value(n: 2) c: value(n: 3) }"))
+                .await;
+            assert!(resp.errors.is_empty());
+
+            // Each request reports the statistics of its own collector
+            let stats = match &resp.extensions["dataloader"] {
+                Value::Object(stats) => match &stats[std::any::type_name::<ValueLoader>()] {
+                    Value::Object(stats) => stats.clone(),
+                    _ => panic!("expected the statistics of the loader"),
+                },
+                _ => panic!("expected an object"),
+            };
+            assert_eq!(stats["batches"], value!(1));
+            assert_eq!(stats["keys"], value!(3));
+            assert_eq!(stats["maxBatchSize"], value!(3));
+            assert_eq!(stats["cacheMisses"], value!(3));
+            assert_eq!(stats["errors"], value!(0));
+        }
+    }
+
+    #[tokio::test]
+    async fn dataloader_metrics_ignores_schema_data() {
+        let metrics = MetricsCollector::new();
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(extensions::DataLoaderMetrics)
+            .data(metrics.clone())
+            .data(
+                DataLoader::with_cache(ValueLoader, tokio::spawn, HashMapCache::default())
+                    .observer(metrics.clone()),
+            )
+            .finish();
+
+        let resp = schema.execute("{ value(n: 1) }").await;
+        assert_eq!(resp.data, value!({ "value": 1 }));
+        assert!(!resp.extensions.contains_key("dataloader"));
+        assert_eq!(
+            metrics.stats()[std::any::type_name::<ValueLoader>()].batches,
+            1
+        );
+    }
+}
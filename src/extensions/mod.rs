@@ -1,10 +1,14 @@
 //! Extensions for schema
 
 mod analyzer;
+#[cfg(feature = "apollo_tracing")]
+mod apollo_federated_tracing;
 #[cfg(feature = "apollo_persisted_queries")]
 pub mod apollo_persisted_queries;
 #[cfg(feature = "apollo_tracing")]
 mod apollo_tracing;
+#[cfg(feature = "dataloader")]
+mod dataloader_metrics;
 #[cfg(feature = "log")]
 mod logger;
 #[cfg(feature = "opentelemetry")]
@@ -22,7 +26,13 @@ use futures_util::stream::BoxStream;
 
 pub use self::analyzer::Analyzer;
 #[cfg(feature = "apollo_tracing")]
+pub(crate) use self::apollo_federated_tracing::INCLUDE_TRACE_HEADER;
+#[cfg(feature = "apollo_tracing")]
+pub use self::apollo_federated_tracing::{ApolloFederatedTracing, IncludeFederatedTrace};
+#[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
+#[cfg(feature = "dataloader")]
+pub use self::dataloader_metrics::DataLoaderMetrics;
 #[cfg(feature = "log")]
 pub use self::logger::Logger;
 #[cfg(feature = "opentelemetry")]
@@ -31,7 +41,8 @@ pub use self::opentelemetry::OpenTelemetry;
 pub use self::tracing::Tracing;
 use crate::{
     parser::types::ExecutableDocument, Data, DataContext, Error, QueryPathNode, Request, Response,
-    Result, SchemaEnv, ServerError, ServerResult, ValidationResult, Value, Variables,
+    Result, SchemaEnv, ServerError, ServerResult, SubscriptionBufferPolicy, ValidationResult,
+    Value, Variables,
 };
 
 /// Context for extension
@@ -130,6 +141,19 @@ pub struct ResolveInfo<'a> {
     pub is_for_introspection: bool,
 }
 
+/// Parameters for `Extension::subscription_events_dropped`
+#[derive(Debug, Copy, Clone)]
+pub struct DroppedEventsInfo<'a> {
+    /// The response key of the subscription field.
+    pub field_name: &'a str,
+
+    /// The buffering policy of the subscription field.
+    pub policy: SubscriptionBufferPolicy,
+
+    /// The number of dropped events.
+    pub count: usize,
+}
+
 type RequestFut<'a> = &'a mut (dyn Future<Output = Response> + Send + Unpin);
 
 type ParseFut<'a> = &'a mut (dyn Future<Output = ServerResult<ExecutableDocument>> + Send + Unpin);
@@ -388,6 +412,15 @@ pub trait Extension: Sync + Send + 'static {
     ) -> ServerResult<Option<Value>> {
         next.run(ctx, info).await
     }
+
+    /// Called when a subscription stream drops events because of its
+    /// [`SubscriptionBufferPolicy`].
+    fn subscription_events_dropped(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        _info: DroppedEventsInfo<'_>,
+    ) {
+    }
 }
 
 /// Extension factory
@@ -510,4 +543,11 @@ impl Extensions {
         };
         next.run(&self.create_context(), info).await
     }
+
+    pub fn subscription_events_dropped(&self, info: DroppedEventsInfo<'_>) {
+        let ctx = self.create_context();
+        for extension in &self.extensions {
+            extension.subscription_events_dropped(&ctx, info);
+        }
+    }
 }
@@ -0,0 +1,780 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use async_graphql_value::ConstValue;
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    parser::{
+        self,
+        types::{
+            BaseType, ConstDirective, DocumentOperations, InputValueDefinition, Selection,
+            SelectionSet, ServiceDocument, Type, TypeKind, TypeSystemDefinition,
+        },
+    },
+    Positioned,
+};
+
+/// Federation types that are not part of the composed schema.
+const FEDERATION_TYPES: &[&str] = &[
+    "_Any",
+    "_Entity",
+    "_Service",
+    "_FieldSet",
+    "FieldSet",
+    "link__Import",
+    "link__Purpose",
+];
+
+const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// A field of a `@key`, `@requires` or `@provides` field set.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct KeyField {
+    pub(crate) name: String,
+    pub(crate) children: Vec<KeyField>,
+}
+
+pub(crate) fn parse_field_set(fields: &str) -> Result<Vec<KeyField>, String> {
+    fn convert(selection_set: &SelectionSet) -> Option<Vec<KeyField>> {
+        selection_set
+            .items
+            .iter()
+            .map(|selection| match &selection.node {
+                Selection::Field(field) => Some(KeyField {
+                    name: field.node.name.node.to_string(),
+                    children: convert(&field.node.selection_set.node)?,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    let invalid = || format!("Invalid field set \"{}\"", fields);
+    let doc = parser::parse_query(format!("{{{}}}", fields)).map_err(|_| invalid())?;
+    match &doc.operations {
+        DocumentOperations::Single(operation) => {
+            convert(&operation.node.selection_set.node).ok_or_else(invalid)
+        }
+        DocumentOperations::Multiple(_) => Err(invalid()),
+    }
+}
+
+pub(crate) fn write_field_set(out: &mut String, fields: &[KeyField]) {
+    for field in fields {
+        write!(out, " {}", field.name).ok();
+        if !field.children.is_empty() {
+            out.push_str(" {");
+            write_field_set(out, &field.children);
+            out.push_str(" }");
+        }
+    }
+}
+
+/// Returns the name of the named type of a type.
+pub(crate) fn named_type(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name,
+        BaseType::List(ty) => named_type(ty),
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ComposedKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+impl ComposedKind {
+    fn of(kind: &TypeKind) -> Self {
+        match kind {
+            TypeKind::Scalar => ComposedKind::Scalar,
+            TypeKind::Object(_) => ComposedKind::Object,
+            TypeKind::Interface(_) => ComposedKind::Interface,
+            TypeKind::Union(_) => ComposedKind::Union,
+            TypeKind::Enum(_) => ComposedKind::Enum,
+            TypeKind::InputObject(_) => ComposedKind::InputObject,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ComposedKind::Scalar => "a scalar",
+            ComposedKind::Object => "an object",
+            ComposedKind::Interface => "an interface",
+            ComposedKind::Union => "a union",
+            ComposedKind::Enum => "an enum",
+            ComposedKind::InputObject => "an input object",
+        }
+    }
+}
+
+/// A `@key` of an entity in a subgraph.
+pub(crate) struct Key {
+    pub(crate) fields: Vec<KeyField>,
+    pub(crate) resolvable: bool,
+}
+
+/// The definition of a field in a subgraph.
+struct FieldSource {
+    subgraph: usize,
+    external: bool,
+    shareable: bool,
+    override_from: Option<String>,
+}
+
+pub(crate) struct ComposedField {
+    pub(crate) name: String,
+    description: Option<String>,
+    arguments: Vec<InputValueDefinition>,
+    pub(crate) ty: Type,
+    deprecation: Option<Option<String>>,
+    inaccessible: bool,
+    sources: Vec<FieldSource>,
+    /// The subgraphs that can resolve this field.
+    pub(crate) resolvable_in: Vec<usize>,
+    /// The fields required by `@requires` in each subgraph.
+    pub(crate) requires: HashMap<usize, Vec<KeyField>>,
+}
+
+pub(crate) struct ComposedType {
+    pub(crate) name: String,
+    pub(crate) kind: ComposedKind,
+    description: Option<String>,
+    inaccessible: bool,
+    implements: IndexSet<String>,
+    pub(crate) fields: IndexMap<String, ComposedField>,
+    input_fields: IndexMap<String, InputValueDefinition>,
+    members: IndexSet<String>,
+    enum_values: IndexSet<String>,
+    pub(crate) keys: HashMap<usize, Vec<Key>>,
+    subgraphs: Vec<usize>,
+}
+
+/// The schema composed from all the subgraphs.
+pub(crate) struct ComposedSchema {
+    pub(crate) types: IndexMap<String, ComposedType>,
+    pub(crate) mutation_type: Option<String>,
+}
+
+fn directive<'a>(
+    directives: &'a [Positioned<ConstDirective>],
+    name: &str,
+) -> Option<&'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .find(|directive| directive.name.node == name)
+}
+
+fn string_argument<'a>(directive: &'a ConstDirective, name: &str) -> Option<&'a str> {
+    match directive.get_argument(name).map(|value| &value.node) {
+        Some(ConstValue::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+impl ComposedSchema {
+    /// Composes the SDL of the subgraphs, returning all the composition and
+    /// validation errors.
+    pub(crate) fn compose(subgraphs: &[(String, ServiceDocument)]) -> Result<Self, Vec<String>> {
+        let mut schema = ComposedSchema {
+            types: IndexMap::new(),
+            mutation_type: None,
+        };
+        let mut errors = Vec::new();
+
+        for (idx, (subgraph_name, doc)) in subgraphs.iter().enumerate() {
+            schema.merge_subgraph(idx, subgraph_name, doc, &mut errors);
+        }
+
+        if !schema.types.contains_key("Query") {
+            errors.push("The supergraph has no Query type".to_string());
+        }
+        if schema.types.contains_key("Mutation") {
+            schema.mutation_type = Some("Mutation".to_string());
+        }
+
+        schema.resolve_fields();
+        schema.validate(subgraphs, &mut errors);
+
+        if errors.is_empty() {
+            Ok(schema)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn merge_subgraph(
+        &mut self,
+        subgraph: usize,
+        subgraph_name: &str,
+        doc: &ServiceDocument,
+        errors: &mut Vec<String>,
+    ) {
+        let mut roots = HashMap::new();
+        let mut fed2 = false;
+
+        for definition in &doc.definitions {
+            if let TypeSystemDefinition::Schema(schema) = definition {
+                let schema = &schema.node;
+                if let Some(query) = &schema.query {
+                    roots.insert(query.node.to_string(), "Query");
+                }
+                if let Some(mutation) = &schema.mutation {
+                    roots.insert(mutation.node.to_string(), "Mutation");
+                }
+                fed2 |= schema.directives.iter().any(|directive| {
+                    directive.node.name.node == "link"
+                        && string_argument(&directive.node, "url").map_or(false, |url| {
+                            url.starts_with("https://specs.apollo.dev/federation/v2")
+                        })
+                });
+            }
+        }
+
+        for definition in &doc.definitions {
+            let definition = match definition {
+                TypeSystemDefinition::Type(definition) => &definition.node,
+                _ => continue,
+            };
+            let original_name = definition.name.node.as_str();
+            if FEDERATION_TYPES.contains(&original_name)
+                || original_name.starts_with("federation__")
+                || original_name.starts_with("__")
+            {
+                continue;
+            }
+            let name = roots
+                .get(original_name)
+                .copied()
+                .unwrap_or(original_name)
+                .to_string();
+
+            if directive(&definition.directives, "interfaceObject").is_some() {
+                errors.push(format!(
+                    "Type \"{}\" in subgraph \"{}\" uses @interfaceObject, which is not supported by the supergraph",
+                    name, subgraph_name
+                ));
+                continue;
+            }
+
+            let kind = ComposedKind::of(&definition.kind);
+            let ty = self
+                .types
+                .entry(name.clone())
+                .or_insert_with(|| ComposedType {
+                    name: name.clone(),
+                    kind,
+                    description: None,
+                    inaccessible: false,
+                    implements: IndexSet::new(),
+                    fields: IndexMap::new(),
+                    input_fields: IndexMap::new(),
+                    members: IndexSet::new(),
+                    enum_values: IndexSet::new(),
+                    keys: HashMap::new(),
+                    subgraphs: Vec::new(),
+                });
+            if ty.kind != kind {
+                errors.push(format!(
+                    "Type \"{}\" is defined as {} in subgraph \"{}\", but as {} in another subgraph",
+                    name,
+                    kind.as_str(),
+                    subgraph_name,
+                    ty.kind.as_str()
+                ));
+                continue;
+            }
+            if !ty.subgraphs.contains(&subgraph) {
+                ty.subgraphs.push(subgraph);
+            }
+            if ty.description.is_none() {
+                ty.description = definition
+                    .description
+                    .as_ref()
+                    .map(|description| description.node.clone());
+            }
+            ty.inaccessible |= directive(&definition.directives, "inaccessible").is_some();
+
+            for key in definition
+                .directives
+                .iter()
+                .filter(|directive| directive.node.name.node == "key")
+            {
+                let fields = match string_argument(&key.node, "fields")
+                    .ok_or_else(|| "Invalid field set".to_string())
+                    .and_then(parse_field_set)
+                {
+                    Ok(fields) => fields,
+                    Err(err) => {
+                        errors.push(format!(
+                            "{} in @key of type \"{}\" in subgraph \"{}\"",
+                            err, name, subgraph_name
+                        ));
+                        continue;
+                    }
+                };
+                let resolvable = !matches!(
+                    key.node.get_argument("resolvable").map(|value| &value.node),
+                    Some(ConstValue::Boolean(false))
+                );
+                ty.keys
+                    .entry(subgraph)
+                    .or_default()
+                    .push(Key { fields, resolvable });
+            }
+
+            let type_shareable = !fed2 || directive(&definition.directives, "shareable").is_some();
+            let fields = match &definition.kind {
+                TypeKind::Object(object) => {
+                    ty.implements
+                        .extend(object.implements.iter().map(|name| name.node.to_string()));
+                    &object.fields
+                }
+                TypeKind::Interface(interface) => {
+                    ty.implements.extend(
+                        interface
+                            .implements
+                            .iter()
+                            .map(|name| name.node.to_string()),
+                    );
+                    &interface.fields
+                }
+                TypeKind::Union(union) => {
+                    ty.members
+                        .extend(union.members.iter().map(|name| name.node.to_string()));
+                    continue;
+                }
+                TypeKind::Enum(enum_type) => {
+                    ty.enum_values.extend(
+                        enum_type
+                            .values
+                            .iter()
+                            .map(|value| value.node.value.node.to_string()),
+                    );
+                    continue;
+                }
+                TypeKind::InputObject(input_object) => {
+                    for field in &input_object.fields {
+                        ty.input_fields
+                            .entry(field.node.name.node.to_string())
+                            .or_insert_with(|| field.node.clone());
+                    }
+                    continue;
+                }
+                TypeKind::Scalar => continue,
+            };
+
+            for field in fields {
+                let field = &field.node;
+                let field_name = field.name.node.to_string();
+                if name == "Query" && matches!(field_name.as_str(), "_service" | "_entities") {
+                    continue;
+                }
+
+                let composed_field =
+                    ty.fields
+                        .entry(field_name.clone())
+                        .or_insert_with(|| ComposedField {
+                            name: field_name.clone(),
+                            description: None,
+                            arguments: Vec::new(),
+                            ty: field.ty.node.clone(),
+                            deprecation: None,
+                            inaccessible: false,
+                            sources: Vec::new(),
+                            resolvable_in: Vec::new(),
+                            requires: HashMap::new(),
+                        });
+                if composed_field.ty != field.ty.node {
+                    errors.push(format!(
+                        "Field \"{}.{}\" has type \"{}\" in subgraph \"{}\", but \"{}\" in another subgraph",
+                        name, field_name, field.ty.node, subgraph_name, composed_field.ty
+                    ));
+                    continue;
+                }
+                if composed_field.description.is_none() {
+                    composed_field.description = field
+                        .description
+                        .as_ref()
+                        .map(|description| description.node.clone());
+                }
+                for argument in &field.arguments {
+                    if !composed_field
+                        .arguments
+                        .iter()
+                        .any(|arg| arg.name.node == argument.node.name.node)
+                    {
+                        composed_field.arguments.push(argument.node.clone());
+                    }
+                }
+                if let Some(deprecated) = directive(&field.directives, "deprecated") {
+                    composed_field.deprecation =
+                        Some(string_argument(deprecated, "reason").map(ToString::to_string));
+                }
+                composed_field.inaccessible |=
+                    directive(&field.directives, "inaccessible").is_some();
+
+                if let Some(requires) = directive(&field.directives, "requires") {
+                    match string_argument(requires, "fields")
+                        .ok_or_else(|| "Invalid field set".to_string())
+                        .and_then(parse_field_set)
+                    {
+                        Ok(fields) => {
+                            composed_field.requires.insert(subgraph, fields);
+                        }
+                        Err(err) => errors.push(format!(
+                            "{} in @requires of field \"{}.{}\" in subgraph \"{}\"",
+                            err, name, field_name, subgraph_name
+                        )),
+                    }
+                }
+
+                composed_field.sources.push(FieldSource {
+                    subgraph,
+                    external: directive(&field.directives, "external").is_some(),
+                    shareable: type_shareable
+                        || directive(&field.directives, "shareable").is_some(),
+                    override_from: directive(&field.directives, "override")
+                        .and_then(|directive| string_argument(directive, "from"))
+                        .map(ToString::to_string),
+                });
+            }
+        }
+    }
+
+    /// Returns `true` if the field is part of a `@key` of the type in the
+    /// subgraph.
+    fn is_key_field(ty: &ComposedType, subgraph: usize, field_name: &str) -> bool {
+        ty.keys.get(&subgraph).map_or(false, |keys| {
+            keys.iter()
+                .any(|key| key.fields.iter().any(|field| field.name == field_name))
+        })
+    }
+
+    fn resolve_fields(&mut self) {
+        for ty in self.types.values_mut() {
+            let mut fields = std::mem::take(&mut ty.fields);
+            for field in fields.values_mut() {
+                let overridden = field
+                    .sources
+                    .iter()
+                    .filter_map(|source| source.override_from.as_deref())
+                    .collect::<HashSet<_>>();
+                field.resolvable_in = field
+                    .sources
+                    .iter()
+                    .filter(|source| {
+                        !source.external || Self::is_key_field(ty, source.subgraph, &field.name)
+                    })
+                    .map(|source| source.subgraph)
+                    .collect();
+                if !overridden.is_empty() {
+                    let overriding = field
+                        .sources
+                        .iter()
+                        .filter(|source| source.override_from.is_some())
+                        .map(|source| source.subgraph)
+                        .collect::<Vec<_>>();
+                    field
+                        .resolvable_in
+                        .retain(|subgraph| overriding.contains(subgraph));
+                    field
+                        .sources
+                        .retain(|source| overriding.contains(&source.subgraph));
+                }
+            }
+            ty.fields = fields;
+        }
+    }
+
+    fn validate(&self, subgraphs: &[(String, ServiceDocument)], errors: &mut Vec<String>) {
+        let subgraph_name = |idx: usize| subgraphs[idx].0.as_str();
+
+        for ty in self.types.values() {
+            for (subgraph, keys) in &ty.keys {
+                for key in keys {
+                    if let Err(field) = self.check_field_set(&ty.name, &key.fields, *subgraph) {
+                        errors.push(format!(
+                            "@key of type \"{}\" in subgraph \"{}\" references unknown field \"{}\"",
+                            ty.name,
+                            subgraph_name(*subgraph),
+                            field
+                        ));
+                    }
+                }
+            }
+
+            if ty.kind != ComposedKind::Object {
+                continue;
+            }
+
+            for field in ty.fields.values() {
+                for (subgraph, requires) in &field.requires {
+                    if let Err(name) = self.check_field_set(&ty.name, requires, *subgraph) {
+                        errors.push(format!(
+                            "@requires of field \"{}.{}\" in subgraph \"{}\" references unknown field \"{}\"",
+                            ty.name,
+                            field.name,
+                            subgraph_name(*subgraph),
+                            name
+                        ));
+                    }
+                }
+
+                if field.resolvable_in.is_empty() {
+                    errors.push(format!(
+                        "Field \"{}.{}\" is external in every subgraph",
+                        ty.name, field.name
+                    ));
+                    continue;
+                }
+
+                let owners = field
+                    .sources
+                    .iter()
+                    .filter(|source| {
+                        !source.external && !Self::is_key_field(ty, source.subgraph, &field.name)
+                    })
+                    .collect::<Vec<_>>();
+                if owners.len() > 1 && owners.iter().any(|source| !source.shareable) {
+                    errors.push(format!(
+                        "Non-shareable field \"{}.{}\" is resolved from multiple subgraphs: {}",
+                        ty.name,
+                        field.name,
+                        owners
+                            .iter()
+                            .map(|source| format!("\"{}\"", subgraph_name(source.subgraph)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+
+            if ty.subgraphs.len() < 2 || ty.name == "Query" || ty.name == "Mutation" {
+                continue;
+            }
+            for subgraph in &ty.subgraphs {
+                let contributes = ty.fields.values().any(|field| {
+                    field.resolvable_in.contains(subgraph)
+                        && !ty
+                            .subgraphs
+                            .iter()
+                            .all(|other| field.resolvable_in.contains(other))
+                });
+                let has_key = ty
+                    .keys
+                    .get(subgraph)
+                    .map_or(false, |keys| keys.iter().any(|key| key.resolvable));
+                if contributes && !has_key {
+                    errors.push(format!(
+                        "Type \"{}\" has fields that can only be resolved by subgraph \"{}\", but it defines no resolvable @key for it",
+                        ty.name,
+                        subgraph_name(*subgraph)
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Checks that all the fields of a field set are defined in a subgraph,
+    /// and returns the first unknown field otherwise.
+    fn check_field_set(
+        &self,
+        type_name: &str,
+        fields: &[KeyField],
+        subgraph: usize,
+    ) -> Result<(), String> {
+        for key_field in fields {
+            let field = self
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(&key_field.name))
+                .filter(|field| {
+                    field
+                        .sources
+                        .iter()
+                        .any(|source| source.subgraph == subgraph)
+                })
+                .ok_or_else(|| key_field.name.clone())?;
+            self.check_field_set(named_type(&field.ty), &key_field.children, subgraph)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if all the fields of a field set can be resolved by a
+    /// subgraph.
+    pub(crate) fn is_field_set_resolvable(
+        &self,
+        type_name: &str,
+        fields: &[KeyField],
+        subgraph: usize,
+    ) -> bool {
+        fields.iter().all(|key_field| {
+            match self
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(&key_field.name))
+            {
+                Some(field) => {
+                    field.resolvable_in.contains(&subgraph)
+                        && self.is_field_set_resolvable(
+                            named_type(&field.ty),
+                            &key_field.children,
+                            subgraph,
+                        )
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Returns `true` if an object of type `object_type` is a `type_name`.
+    pub(crate) fn is_possible_type(&self, type_name: &str, object_type: &str) -> bool {
+        if type_name == object_type {
+            return true;
+        }
+        match self.types.get(type_name) {
+            Some(ty) if ty.kind == ComposedKind::Union => ty.members.contains(object_type),
+            Some(ty) if ty.kind == ComposedKind::Interface => self
+                .types
+                .get(object_type)
+                .map_or(false, |object| object.implements.contains(type_name)),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if an object can be both a `a` and a `b`.
+    pub(crate) fn types_overlap(&self, a: &str, b: &str) -> bool {
+        self.types.values().any(|ty| {
+            ty.kind == ComposedKind::Object
+                && self.is_possible_type(a, &ty.name)
+                && self.is_possible_type(b, &ty.name)
+        })
+    }
+
+    /// Exports the composed schema as SDL.
+    pub(crate) fn sdl(&self) -> String {
+        let mut sdl = String::new();
+
+        for ty in self.types.values() {
+            if ty.inaccessible || SYSTEM_SCALARS.contains(&ty.name.as_str()) {
+                continue;
+            }
+            if let Some(description) = &ty.description {
+                writeln!(sdl, "{}", ConstValue::String(description.clone())).ok();
+            }
+            match ty.kind {
+                ComposedKind::Scalar => {
+                    writeln!(sdl, "scalar {}", ty.name).ok();
+                }
+                ComposedKind::Object | ComposedKind::Interface => {
+                    let keyword = if ty.kind == ComposedKind::Object {
+                        "type"
+                    } else {
+                        "interface"
+                    };
+                    write!(sdl, "{} {}", keyword, ty.name).ok();
+                    if !ty.implements.is_empty() {
+                        write!(
+                            sdl,
+                            " implements {}",
+                            ty.implements
+                                .iter()
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(" & ")
+                        )
+                        .ok();
+                    }
+                    writeln!(sdl, " {{").ok();
+                    for field in ty.fields.values().filter(|field| !field.inaccessible) {
+                        if let Some(description) = &field.description {
+                            writeln!(sdl, "\t{}", ConstValue::String(description.clone())).ok();
+                        }
+                        write!(sdl, "\t{}", field.name).ok();
+                        if !field.arguments.is_empty() {
+                            write!(
+                                sdl,
+                                "({})",
+                                field
+                                    .arguments
+                                    .iter()
+                                    .map(export_input_value)
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            )
+                            .ok();
+                        }
+                        write!(sdl, ": {}", field.ty).ok();
+                        match &field.deprecation {
+                            Some(Some(reason)) => {
+                                write!(
+                                    sdl,
+                                    " @deprecated(reason: {})",
+                                    ConstValue::String(reason.clone())
+                                )
+                                .ok();
+                            }
+                            Some(None) => {
+                                write!(sdl, " @deprecated").ok();
+                            }
+                            None => {}
+                        }
+                        writeln!(sdl).ok();
+                    }
+                    writeln!(sdl, "}}").ok();
+                }
+                ComposedKind::Union => {
+                    writeln!(
+                        sdl,
+                        "union {} = {}",
+                        ty.name,
+                        ty.members.iter().cloned().collect::<Vec<_>>().join(" | ")
+                    )
+                    .ok();
+                }
+                ComposedKind::Enum => {
+                    writeln!(sdl, "enum {} {{", ty.name).ok();
+                    for value in &ty.enum_values {
+                        writeln!(sdl, "\t{}", value).ok();
+                    }
+                    writeln!(sdl, "}}").ok();
+                }
+                ComposedKind::InputObject => {
+                    writeln!(sdl, "input {} {{", ty.name).ok();
+                    for field in ty.input_fields.values() {
+                        writeln!(sdl, "\t{}", export_input_value(field)).ok();
+                    }
+                    writeln!(sdl, "}}").ok();
+                }
+            }
+            writeln!(sdl).ok();
+        }
+
+        writeln!(sdl, "schema {{").ok();
+        writeln!(sdl, "\tquery: Query").ok();
+        if let Some(mutation_type) = &self.mutation_type {
+            writeln!(sdl, "\tmutation: {}", mutation_type).ok();
+        }
+        writeln!(sdl, "}}").ok();
+        sdl
+    }
+}
+
+fn export_input_value(input_value: &InputValueDefinition) -> String {
+    match &input_value.default_value {
+        Some(default_value) => format!(
+            "{}: {} = {}",
+            input_value.name.node, input_value.ty.node, default_value.node
+        ),
+        None => format!("{}: {}", input_value.name.node, input_value.ty.node),
+    }
+}
@@ -0,0 +1,12 @@
+//! In-process federation support
+//!
+//! A [`Supergraph`] composes several subgraphs into a single schema, and
+//! executes queries by fetching from each subgraph, so that federated
+//! services can be tested together without an external gateway.
+
+mod compose;
+mod plan;
+mod supergraph;
+
+pub use plan::QueryPlan;
+pub use supergraph::{CompositionError, SubgraphTransport, Supergraph, SupergraphBuilder};
@@ -0,0 +1,804 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use async_graphql_value::{ConstValue, Value};
+use indexmap::{IndexMap, IndexSet};
+
+use super::compose::{named_type, write_field_set, ComposedKind, ComposedSchema, KeyField};
+use crate::{
+    parser::{
+        self,
+        types::{
+            BaseType, Directive, DocumentOperations, ExecutableDocument, OperationDefinition,
+            OperationType, Selection, SelectionSet, Type, VariableDefinition,
+        },
+    },
+    Name, Pos, Positioned, ServerError, Variables,
+};
+
+/// The maximum depth of nested fragments.
+const MAX_FRAGMENT_DEPTH: usize = 64;
+
+/// A field of the query, with its fragments flattened.
+#[derive(Debug, Clone)]
+pub(crate) struct PlanField {
+    pub(crate) response_key: String,
+    pub(crate) name: String,
+    /// The type that this field is selected on.
+    pub(crate) parent_type: String,
+    /// The named type of this field.
+    pub(crate) field_type: String,
+    /// The type of this field.
+    ty: Type,
+    arguments: Vec<(Positioned<Name>, Positioned<Value>)>,
+    directives: Vec<Positioned<Directive>>,
+    pos: Pos,
+    pub(crate) selection_set: Vec<PlanField>,
+}
+
+/// An entity fetch, which resolves the fields of the entities found at a path
+/// of the parent fetch.
+#[derive(Debug)]
+pub(crate) struct EntityFetch {
+    pub(crate) type_name: String,
+    pub(crate) path: Vec<String>,
+    pub(crate) key: Vec<KeyField>,
+    pub(crate) requires: Vec<KeyField>,
+}
+
+/// A request sent to a subgraph.
+#[derive(Debug)]
+pub(crate) struct FetchNode {
+    pub(crate) subgraph: usize,
+    pub(crate) subgraph_name: String,
+    pub(crate) query: String,
+    pub(crate) entity: Option<EntityFetch>,
+    pub(crate) children: Vec<FetchNode>,
+}
+
+/// The plan to execute a query against the subgraphs of a supergraph.
+///
+/// The root fetches are executed in parallel for a query, and sequentially for
+/// a mutation. Then each fetch resolves the entities returned by its parent.
+#[derive(Debug)]
+pub struct QueryPlan {
+    pub(crate) root_type: String,
+    pub(crate) serial: bool,
+    pub(crate) nodes: Vec<FetchNode>,
+    pub(crate) selection_set: Vec<PlanField>,
+}
+
+impl Display for QueryPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn write_node(f: &mut Formatter<'_>, node: &FetchNode, indent: usize) -> fmt::Result {
+            write!(f, "{:indent$}", "", indent = indent)?;
+            if let Some(entity) = &node.entity {
+                write!(
+                    f,
+                    "Flatten(path: \"{}\", type: \"{}\") ",
+                    entity.path.join("."),
+                    entity.type_name
+                )?;
+            }
+            writeln!(
+                f,
+                "Fetch(service: \"{}\") {}",
+                node.subgraph_name, node.query
+            )?;
+            for child in &node.children {
+                write_node(f, child, indent + 2)?;
+            }
+            Ok(())
+        }
+
+        writeln!(
+            f,
+            "QueryPlan({}) {{",
+            if self.serial { "Sequence" } else { "Parallel" }
+        )?;
+        for node in &self.nodes {
+            write_node(f, node, 2)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Plans the execution of an operation.
+pub(crate) fn plan(
+    schema: &ComposedSchema,
+    subgraph_names: &[String],
+    query: &str,
+    operation_name: Option<&str>,
+    variables: &Variables,
+) -> Result<QueryPlan, ServerError> {
+    let doc = parser::parse_query(query)?;
+    let operation = select_operation(&doc, operation_name)?;
+
+    let (root_type, serial) = match operation.node.ty {
+        OperationType::Query => ("Query", false),
+        OperationType::Mutation => match &schema.mutation_type {
+            Some(mutation_type) => (mutation_type.as_str(), true),
+            None => {
+                return Err(ServerError::new(
+                    "Schema is not configured for mutations.",
+                    Some(operation.pos),
+                ))
+            }
+        },
+        OperationType::Subscription => {
+            return Err(ServerError::new(
+                "Subscriptions are not supported by the supergraph.",
+                Some(operation.pos),
+            ))
+        }
+    };
+
+    let planner = Planner {
+        schema,
+        subgraph_names,
+        doc: &doc,
+        variables,
+        variable_definitions: &operation.node.variable_definitions,
+    };
+
+    let mut selection_set = Vec::new();
+    planner.normalize(
+        root_type,
+        &operation.node.selection_set.node,
+        &mut selection_set,
+        0,
+    )?;
+
+    let mut groups: Vec<(usize, Vec<PlanField>)> = Vec::new();
+    for field in &selection_set {
+        if field.name == "__typename" {
+            continue;
+        }
+        let subgraph = schema
+            .types
+            .get(root_type)
+            .and_then(|ty| ty.fields.get(&field.name))
+            .and_then(|field| field.resolvable_in.first().copied())
+            .ok_or_else(|| {
+                ServerError::new(
+                    format!(r#"Unknown field "{}" on type "{}"."#, field.name, root_type),
+                    Some(field.pos),
+                )
+            })?;
+        match groups.last_mut() {
+            Some((last, fields)) if *last == subgraph => fields.push(field.clone()),
+            _ if !serial => match groups.iter_mut().find(|(idx, _)| *idx == subgraph) {
+                Some((_, fields)) => fields.push(field.clone()),
+                None => groups.push((subgraph, vec![field.clone()])),
+            },
+            _ => groups.push((subgraph, vec![field.clone()])),
+        }
+    }
+
+    let nodes = groups
+        .into_iter()
+        .map(|(subgraph, fields)| {
+            planner.build_fetch(
+                subgraph,
+                root_type,
+                &fields,
+                None,
+                if serial { "mutation" } else { "query" },
+            )
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(QueryPlan {
+        root_type: root_type.to_string(),
+        serial,
+        nodes,
+        selection_set,
+    })
+}
+
+fn select_operation<'a>(
+    doc: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Result<&'a Positioned<OperationDefinition>, ServerError> {
+    match (&doc.operations, operation_name) {
+        (DocumentOperations::Single(operation), _) => Ok(operation),
+        (DocumentOperations::Multiple(operations), Some(name)) => {
+            operations.get(name).ok_or_else(|| {
+                ServerError::new(format!(r#"Unknown operation named "{}""#, name), None)
+            })
+        }
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            Ok(operations.values().next().unwrap())
+        }
+        (DocumentOperations::Multiple(_), None) => Err(ServerError::new(
+            "Operation name required in request.",
+            None,
+        )),
+    }
+}
+
+struct Planner<'a> {
+    schema: &'a ComposedSchema,
+    subgraph_names: &'a [String],
+    doc: &'a ExecutableDocument,
+    variables: &'a Variables,
+    variable_definitions: &'a [Positioned<VariableDefinition>],
+}
+
+/// The fields of a fetch that must be resolved by another subgraph.
+struct EntityGroup {
+    subgraph: usize,
+    type_name: String,
+    path: Vec<String>,
+    key: Vec<KeyField>,
+    requires: Vec<KeyField>,
+    fields: Vec<PlanField>,
+}
+
+impl<'a> Planner<'a> {
+    fn variable_value(&self, name: &str) -> Option<Value> {
+        self.variables
+            .get(name)
+            .cloned()
+            .or_else(|| {
+                self.variable_definitions
+                    .iter()
+                    .find(|definition| definition.node.name.node == name)
+                    .and_then(|definition| definition.node.default_value.as_ref())
+                    .map(|value| value.node.clone())
+            })
+            .map(ConstValue::into_value)
+    }
+
+    fn is_skipped(&self, directives: &[Positioned<Directive>]) -> Result<bool, ServerError> {
+        for directive in directives {
+            let include = match directive.node.name.node.as_str() {
+                "skip" => false,
+                "include" => true,
+                _ => continue,
+            };
+            let condition = match directive.node.get_argument("if").map(|value| &value.node) {
+                Some(Value::Variable(name)) => self.variable_value(name),
+                Some(value) => Some(value.clone()),
+                None => None,
+            };
+            match condition {
+                Some(Value::Boolean(condition)) if condition != include => return Ok(true),
+                Some(Value::Boolean(_)) => {}
+                _ => {
+                    return Err(ServerError::new(
+                        format!(
+                            r#"Invalid value for argument "if" of directive "@{}""#,
+                            directive.node.name.node
+                        ),
+                        Some(directive.pos),
+                    ))
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Flattens the fragments of a selection set and resolves the types of
+    /// its fields.
+    fn normalize(
+        &self,
+        parent_type: &str,
+        selection_set: &SelectionSet,
+        fields: &mut Vec<PlanField>,
+        depth: usize,
+    ) -> Result<(), ServerError> {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if self.is_skipped(&field.node.directives)? {
+                        continue;
+                    }
+                    let name = field.node.name.node.as_str();
+                    let response_key = field.node.response_key().node.to_string();
+
+                    if name == "__typename" {
+                        fields.push(PlanField {
+                            response_key,
+                            name: name.to_string(),
+                            parent_type: parent_type.to_string(),
+                            field_type: "String".to_string(),
+                            ty: Type::new("String!").unwrap(),
+                            arguments: Vec::new(),
+                            directives: Vec::new(),
+                            pos: field.pos,
+                            selection_set: Vec::new(),
+                        });
+                        continue;
+                    }
+                    if name.starts_with("__") {
+                        return Err(ServerError::new(
+                            "Introspection is not supported by the supergraph.",
+                            Some(field.pos),
+                        ));
+                    }
+
+                    let field_definition = self
+                        .schema
+                        .types
+                        .get(parent_type)
+                        .and_then(|ty| ty.fields.get(name))
+                        .ok_or_else(|| {
+                            ServerError::new(
+                                format!(r#"Unknown field "{}" on type "{}"."#, name, parent_type),
+                                Some(field.pos),
+                            )
+                        })?;
+                    let field_type = named_type(&field_definition.ty);
+
+                    let mut selection_set = Vec::new();
+                    self.normalize(
+                        field_type,
+                        &field.node.selection_set.node,
+                        &mut selection_set,
+                        depth,
+                    )?;
+
+                    fields.push(PlanField {
+                        response_key,
+                        name: name.to_string(),
+                        parent_type: parent_type.to_string(),
+                        field_type: field_type.to_string(),
+                        ty: field_definition.ty.clone(),
+                        arguments: field.node.arguments.clone(),
+                        directives: field
+                            .node
+                            .directives
+                            .iter()
+                            .filter(|directive| {
+                                !matches!(directive.node.name.node.as_str(), "skip" | "include")
+                            })
+                            .cloned()
+                            .collect(),
+                        pos: field.pos,
+                        selection_set,
+                    });
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if self.is_skipped(&fragment_spread.node.directives)? {
+                        continue;
+                    }
+                    let name = &fragment_spread.node.fragment_name.node;
+                    let fragment = self.doc.fragments.get(name).ok_or_else(|| {
+                        ServerError::new(
+                            format!(r#"Unknown fragment "{}"."#, name),
+                            Some(fragment_spread.pos),
+                        )
+                    })?;
+                    self.normalize_fragment(
+                        parent_type,
+                        Some(&fragment.node.type_condition.node.on),
+                        &fragment.node.selection_set.node,
+                        fields,
+                        depth,
+                    )?;
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    if self.is_skipped(&inline_fragment.node.directives)? {
+                        continue;
+                    }
+                    self.normalize_fragment(
+                        parent_type,
+                        inline_fragment
+                            .node
+                            .type_condition
+                            .as_ref()
+                            .map(|type_condition| &type_condition.node.on),
+                        &inline_fragment.node.selection_set.node,
+                        fields,
+                        depth,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn normalize_fragment(
+        &self,
+        parent_type: &str,
+        type_condition: Option<&Positioned<Name>>,
+        selection_set: &SelectionSet,
+        fields: &mut Vec<PlanField>,
+        depth: usize,
+    ) -> Result<(), ServerError> {
+        if depth >= MAX_FRAGMENT_DEPTH {
+            return Err(ServerError::new("Fragments are nested too deeply.", None));
+        }
+        let type_name = match type_condition {
+            Some(type_condition) => {
+                if !self.schema.types.contains_key(type_condition.node.as_str()) {
+                    return Err(ServerError::new(
+                        format!(r#"Unknown type "{}"."#, type_condition.node),
+                        Some(type_condition.pos),
+                    ));
+                }
+                if !self
+                    .schema
+                    .types_overlap(parent_type, type_condition.node.as_str())
+                {
+                    return Err(ServerError::new(
+                        format!(
+                            r#"Fragment cannot be spread here as objects of type "{}" can never be of type "{}""#,
+                            parent_type, type_condition.node
+                        ),
+                        Some(type_condition.pos),
+                    ));
+                }
+                type_condition.node.as_str()
+            }
+            None => parent_type,
+        };
+        self.normalize(type_name, selection_set, fields, depth + 1)
+    }
+
+    /// Builds the fetch of a subgraph, and the fetches of the fields it
+    /// cannot resolve.
+    fn build_fetch(
+        &self,
+        subgraph: usize,
+        parent_type: &str,
+        fields: &[PlanField],
+        entity: Option<EntityFetch>,
+        operation_type: &str,
+    ) -> Result<FetchNode, ServerError> {
+        let mut body = String::new();
+        let mut used_variables = IndexSet::new();
+        let mut groups = Vec::new();
+        self.write_fields(
+            subgraph,
+            parent_type,
+            fields,
+            &[],
+            &mut body,
+            &mut used_variables,
+            &mut groups,
+        )?;
+
+        let mut variable_definitions = Vec::new();
+        if entity.is_some() {
+            variable_definitions.push("$_representations: [_Any!]!".to_string());
+        }
+        for definition in self.variable_definitions {
+            if used_variables.contains(definition.node.name.node.as_str()) {
+                let mut s = format!(
+                    "${}: {}",
+                    definition.node.name.node, definition.node.var_type.node
+                );
+                if let Some(default_value) = &definition.node.default_value {
+                    write!(s, " = {}", default_value.node).ok();
+                }
+                variable_definitions.push(s);
+            }
+        }
+        let variable_definitions = if variable_definitions.is_empty() {
+            String::new()
+        } else {
+            format!("({})", variable_definitions.join(", "))
+        };
+
+        let query = match &entity {
+            Some(entity) => format!(
+                "query{} {{ _entities(representations: $_representations) {{ __typename ... on {} {{{} }} }} }}",
+                variable_definitions, entity.type_name, body
+            ),
+            None => format!("{}{} {{{} }}", operation_type, variable_definitions, body),
+        };
+
+        let children = groups
+            .into_iter()
+            .map(|group| {
+                self.build_fetch(
+                    group.subgraph,
+                    &group.type_name,
+                    &group.fields,
+                    Some(EntityFetch {
+                        type_name: group.type_name.clone(),
+                        path: group.path,
+                        key: group.key,
+                        requires: group.requires,
+                    }),
+                    "query",
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(FetchNode {
+            subgraph,
+            subgraph_name: self.subgraph_names[subgraph].clone(),
+            query,
+            entity,
+            children,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_fields(
+        &self,
+        subgraph: usize,
+        parent_type: &str,
+        fields: &[PlanField],
+        path: &[String],
+        out: &mut String,
+        used_variables: &mut IndexSet<String>,
+        groups: &mut Vec<EntityGroup>,
+    ) -> Result<(), ServerError> {
+        // The root fields are merged into the response without a type name.
+        if !path.is_empty() {
+            out.push_str(" __typename");
+        }
+
+        for field in fields {
+            if field.name == "__typename" {
+                continue;
+            }
+
+            let owner = self.schema.types.get(&field.parent_type).unwrap();
+            let field_definition = &owner.fields[&field.name];
+
+            if field.parent_type != parent_type {
+                write!(out, " ... on {} {{", field.parent_type).ok();
+            }
+
+            if field_definition.resolvable_in.contains(&subgraph) {
+                if field.response_key != field.name {
+                    write!(out, " {}:", field.response_key).ok();
+                }
+                write!(out, " {}", field.name).ok();
+                if !field.arguments.is_empty() {
+                    out.push('(');
+                    for (idx, (name, value)) in field.arguments.iter().enumerate() {
+                        if idx > 0 {
+                            out.push_str(", ");
+                        }
+                        collect_variables(&value.node, used_variables);
+                        write!(out, "{}: {}", name.node, value.node).ok();
+                    }
+                    out.push(')');
+                }
+                for directive in &field.directives {
+                    write!(out, " @{}", directive.node.name.node).ok();
+                    if !directive.node.arguments.is_empty() {
+                        out.push('(');
+                        for (idx, (name, value)) in directive.node.arguments.iter().enumerate() {
+                            if idx > 0 {
+                                out.push_str(", ");
+                            }
+                            collect_variables(&value.node, used_variables);
+                            write!(out, "{}: {}", name.node, value.node).ok();
+                        }
+                        out.push(')');
+                    }
+                }
+                if !field.selection_set.is_empty() {
+                    let mut path = path.to_vec();
+                    path.push(field.response_key.clone());
+                    out.push_str(" {");
+                    self.write_fields(
+                        subgraph,
+                        &field.field_type,
+                        &field.selection_set,
+                        &path,
+                        out,
+                        used_variables,
+                        groups,
+                    )?;
+                    out.push_str(" }");
+                }
+            } else {
+                let (target, key, requires) = self.find_entity_fetch(subgraph, field)?;
+                if field.parent_type == parent_type {
+                    write!(out, " ... on {} {{", field.parent_type).ok();
+                }
+                out.push_str(" __typename");
+                write_field_set(out, &key);
+                write_field_set(out, &requires);
+                if field.parent_type == parent_type {
+                    out.push_str(" }");
+                }
+
+                let field = field.clone();
+                match groups.iter_mut().find(|group| {
+                    group.subgraph == target
+                        && group.type_name == field.parent_type
+                        && group.path == path
+                }) {
+                    Some(group) => {
+                        for required in requires {
+                            if !group.requires.contains(&required) {
+                                group.requires.push(required);
+                            }
+                        }
+                        group.fields.push(field);
+                    }
+                    None => groups.push(EntityGroup {
+                        subgraph: target,
+                        type_name: field.parent_type.clone(),
+                        path: path.to_vec(),
+                        key,
+                        requires,
+                        fields: vec![field],
+                    }),
+                }
+            }
+
+            if field.parent_type != parent_type {
+                out.push_str(" }");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds the subgraph that resolves a field that `subgraph` cannot
+    /// resolve, with the key and required fields it needs from `subgraph`.
+    fn find_entity_fetch(
+        &self,
+        subgraph: usize,
+        field: &PlanField,
+    ) -> Result<(usize, Vec<KeyField>, Vec<KeyField>), ServerError> {
+        let owner = &self.schema.types[&field.parent_type];
+        let field_definition = &owner.fields[&field.name];
+
+        if owner.kind == ComposedKind::Object {
+            for target in &field_definition.resolvable_in {
+                let requires = field_definition
+                    .requires
+                    .get(target)
+                    .cloned()
+                    .unwrap_or_default();
+                if !self
+                    .schema
+                    .is_field_set_resolvable(&owner.name, &requires, subgraph)
+                {
+                    continue;
+                }
+                let key = owner.keys.get(target).and_then(|keys| {
+                    keys.iter().find(|key| {
+                        key.resolvable
+                            && self.schema.is_field_set_resolvable(
+                                &owner.name,
+                                &key.fields,
+                                subgraph,
+                            )
+                    })
+                });
+                if let Some(key) = key {
+                    return Ok((*target, key.fields.clone(), requires));
+                }
+            }
+        }
+
+        Err(ServerError::new(
+            format!(
+                r#"Field "{}.{}" cannot be resolved from subgraph "{}"."#,
+                field.parent_type, field.name, self.subgraph_names[subgraph]
+            ),
+            Some(field.pos),
+        ))
+    }
+}
+
+fn collect_variables(value: &Value, used_variables: &mut IndexSet<String>) {
+    match value {
+        Value::Variable(name) => {
+            used_variables.insert(name.to_string());
+        }
+        Value::List(items) => {
+            for item in items {
+                collect_variables(item, used_variables);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values() {
+                collect_variables(value, used_variables);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes the fields that were fetched for planning purposes from a value,
+/// keeping only the fields of the selection set.
+///
+/// A `null` in a non-null position, such as a failed entity fetch, propagates
+/// to the closest nullable parent, the data is `null` if it reaches the root.
+pub(crate) fn project(
+    schema: &ComposedSchema,
+    value: &ConstValue,
+    type_name: &str,
+    selection_set: &[PlanField],
+) -> ConstValue {
+    project_object(schema, value, type_name, selection_set).unwrap_or(ConstValue::Null)
+}
+
+/// Projects an object, returns `None` if a non-null field is `null`.
+fn project_object(
+    schema: &ComposedSchema,
+    value: &ConstValue,
+    type_name: &str,
+    selection_set: &[PlanField],
+) -> Option<ConstValue> {
+    let object = match value {
+        ConstValue::Object(object) => object,
+        _ => return Some(value.clone()),
+    };
+    let type_name = match object.get("__typename") {
+        Some(ConstValue::String(type_name)) => type_name.as_str(),
+        _ => type_name,
+    };
+    let mut res = IndexMap::new();
+    for field in selection_set {
+        if !schema.is_possible_type(&field.parent_type, type_name) {
+            continue;
+        }
+        let value = if field.name == "__typename" {
+            ConstValue::String(type_name.to_string())
+        } else {
+            let value = object
+                .get(field.response_key.as_str())
+                .unwrap_or(&ConstValue::Null);
+            project_value(schema, value, &field.ty, &field.selection_set)?
+        };
+        match res.get_mut(field.response_key.as_str()) {
+            Some(existing) => merge(existing, value),
+            None => {
+                res.insert(Name::new(&field.response_key), value);
+            }
+        }
+    }
+    Some(ConstValue::Object(res))
+}
+
+/// Projects a value of a type, returns `None` if it is `null` in a non-null
+/// position.
+fn project_value(
+    schema: &ComposedSchema,
+    value: &ConstValue,
+    ty: &Type,
+    selection_set: &[PlanField],
+) -> Option<ConstValue> {
+    let res = match (value, &ty.base) {
+        (ConstValue::Null, _) => None,
+        (ConstValue::List(items), BaseType::List(item_ty)) => items
+            .iter()
+            .map(|item| project_value(schema, item, item_ty, selection_set))
+            .collect::<Option<Vec<_>>>()
+            .map(ConstValue::List),
+        (_, BaseType::Named(type_name)) if !selection_set.is_empty() => {
+            project_object(schema, value, type_name, selection_set)
+        }
+        _ => Some(value.clone()),
+    };
+    match res {
+        None if ty.nullable => Some(ConstValue::Null),
+        res => res,
+    }
+}
+
+/// Deeply merges a value into another.
+pub(crate) fn merge(target: &mut ConstValue, source: ConstValue) {
+    match (target, source) {
+        (ConstValue::Object(target), ConstValue::Object(source)) => {
+            for (name, value) in source {
+                match target.get_mut(&name) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(name, value);
+                    }
+                }
+            }
+        }
+        (ConstValue::List(target), ConstValue::List(source)) if target.len() == source.len() => {
+            for (target, source) in target.iter_mut().zip(source) {
+                merge(target, source);
+            }
+        }
+        (_, ConstValue::Null) => {}
+        (target, source) => *target = source,
+    }
+}
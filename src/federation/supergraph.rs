@@ -0,0 +1,556 @@
+use std::sync::Arc;
+
+use futures_util::{
+    future::BoxFuture,
+    stream::{self, BoxStream},
+    FutureExt, StreamExt,
+};
+
+use super::{
+    compose::{ComposedSchema, KeyField},
+    plan::{self, merge, project, FetchNode, QueryPlan},
+};
+use crate::{
+    parser, Data, Executor, Name, PathSegment, Request, Response, ServerError, Value, Variables,
+};
+
+/// An error can occur when composing a supergraph
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+#[error("{}", .0.join("\n"))]
+pub struct CompositionError(pub Vec<String>);
+
+/// The transport used by a supergraph to send requests to a subgraph.
+///
+/// It is implemented for all the [`Executor`]s, so a [`Schema`](crate::Schema)
+/// can be used as an in-process subgraph.
+#[async_trait::async_trait]
+pub trait SubgraphTransport: Send + Sync + 'static {
+    /// Sends a request to the subgraph.
+    async fn execute(&self, request: Request) -> Response;
+}
+
+#[async_trait::async_trait]
+impl<E: Executor> SubgraphTransport for E {
+    async fn execute(&self, request: Request) -> Response {
+        Executor::execute(self, request).await
+    }
+}
+
+struct Subgraph {
+    name: String,
+    sdl: Option<String>,
+    transport: Arc<dyn SubgraphTransport>,
+}
+
+/// Supergraph builder
+pub struct SupergraphBuilder {
+    subgraphs: Vec<Subgraph>,
+}
+
+impl SupergraphBuilder {
+    /// Add a subgraph, whose SDL is fetched from its `_service` field.
+    ///
+    /// The subgraph must enable federation support, either by defining an
+    /// entity or with [`SchemaBuilder::enable_federation`](crate::SchemaBuilder::enable_federation).
+    #[must_use]
+    pub fn subgraph(mut self, name: impl Into<String>, transport: impl SubgraphTransport) -> Self {
+        self.subgraphs.push(Subgraph {
+            name: name.into(),
+            sdl: None,
+            transport: Arc::new(transport),
+        });
+        self
+    }
+
+    /// Add a subgraph with its federation SDL.
+    #[must_use]
+    pub fn subgraph_with_sdl(
+        mut self,
+        name: impl Into<String>,
+        sdl: impl Into<String>,
+        transport: impl SubgraphTransport,
+    ) -> Self {
+        self.subgraphs.push(Subgraph {
+            name: name.into(),
+            sdl: Some(sdl.into()),
+            transport: Arc::new(transport),
+        });
+        self
+    }
+
+    /// Fetches the SDL of the subgraphs, then composes and validates the
+    /// supergraph.
+    pub async fn finish(self) -> Result<Supergraph, CompositionError> {
+        let mut errors = Vec::new();
+        let mut docs = Vec::new();
+
+        for (idx, subgraph) in self.subgraphs.iter().enumerate() {
+            if self.subgraphs[..idx]
+                .iter()
+                .any(|other| other.name == subgraph.name)
+            {
+                errors.push(format!("Subgraph \"{}\" is defined twice", subgraph.name));
+                continue;
+            }
+
+            let sdl = match &subgraph.sdl {
+                Some(sdl) => sdl.clone(),
+                None => match fetch_sdl(&*subgraph.transport).await {
+                    Ok(sdl) => sdl,
+                    Err(err) => {
+                        errors.push(format!(
+                            "Failed to fetch the SDL of subgraph \"{}\": {}",
+                            subgraph.name, err
+                        ));
+                        continue;
+                    }
+                },
+            };
+            match parser::parse_schema(&sdl) {
+                Ok(doc) => docs.push((subgraph.name.clone(), doc)),
+                Err(err) => errors.push(format!(
+                    "Failed to parse the SDL of subgraph \"{}\": {}",
+                    subgraph.name, err
+                )),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(CompositionError(errors));
+        }
+
+        let schema = ComposedSchema::compose(&docs).map_err(CompositionError)?;
+        Ok(Supergraph(Arc::new(SupergraphInner {
+            sdl: schema.sdl(),
+            schema,
+            subgraphs: self
+                .subgraphs
+                .into_iter()
+                .map(|subgraph| (subgraph.name, subgraph.transport))
+                .collect(),
+        })))
+    }
+}
+
+async fn fetch_sdl(transport: &dyn SubgraphTransport) -> Result<String, String> {
+    let resp = transport
+        .execute(Request::new("{ _service { sdl } }"))
+        .await;
+    if let Some(err) = resp.errors.first() {
+        return Err(err.message.clone());
+    }
+    match resp.data {
+        Value::Object(mut data) => match data.remove("_service") {
+            Some(Value::Object(mut service)) => match service.remove("sdl") {
+                Some(Value::String(sdl)) => Ok(sdl),
+                _ => Err("the subgraph returned no SDL".to_string()),
+            },
+            _ => Err("the subgraph returned no SDL".to_string()),
+        },
+        _ => Err("the subgraph returned no SDL".to_string()),
+    }
+}
+
+struct SupergraphInner {
+    schema: ComposedSchema,
+    sdl: String,
+    subgraphs: Vec<(String, Arc<dyn SubgraphTransport>)>,
+}
+
+/// An in-process federation gateway
+///
+/// A supergraph composes the schemas of several subgraphs, and executes a
+/// query by planning the requests to send to each subgraph and merging their
+/// responses. The subgraphs are called through a [`SubgraphTransport`], so
+/// several [`Schema`](crate::Schema)s can be tested together without an
+/// external gateway.
+///
+/// The query planner is intentionally simple. It does not support
+/// introspection, subscriptions and `@interfaceObject`, and the context data
+/// of a request is not forwarded to the subgraphs.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{federation::Supergraph, *};
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     id: ID,
+///     name: String,
+/// }
+///
+/// struct AccountsQuery;
+///
+/// #[Object]
+/// impl AccountsQuery {
+///     async fn me(&self) -> User {
+///         User { id: "1".into(), name: "Alice".to_string() }
+///     }
+///
+///     #[graphql(entity)]
+///     async fn find_user_by_id(&self, id: ID) -> User {
+///         User { id, name: "Alice".to_string() }
+///     }
+/// }
+///
+/// #[derive(SimpleObject)]
+/// #[graphql(name = "User", complex)]
+/// struct Reviewer {
+///     id: ID,
+/// }
+///
+/// #[ComplexObject]
+/// impl Reviewer {
+///     async fn review_count(&self) -> i32 {
+///         3
+///     }
+/// }
+///
+/// struct ReviewsQuery;
+///
+/// #[Object]
+/// impl ReviewsQuery {
+///     #[graphql(entity)]
+///     async fn find_user_by_id(&self, id: ID) -> Reviewer {
+///         Reviewer { id }
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let accounts = Schema::new(AccountsQuery, EmptyMutation, EmptySubscription);
+/// let reviews = Schema::new(ReviewsQuery, EmptyMutation, EmptySubscription);
+/// let supergraph = Supergraph::build()
+///     .subgraph("accounts", accounts)
+///     .subgraph("reviews", reviews)
+///     .finish()
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(
+///     supergraph.execute("{ me { name reviewCount } }").await.into_result().unwrap().data,
+///     value!({ "me": { "name": "Alice", "reviewCount": 3 } })
+/// );
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Supergraph(Arc<SupergraphInner>);
+
+impl Supergraph {
+    /// Create a supergraph builder.
+    pub fn build() -> SupergraphBuilder {
+        SupergraphBuilder {
+            subgraphs: Vec::new(),
+        }
+    }
+
+    /// Returns the SDL of the composed schema.
+    pub fn sdl(&self) -> &str {
+        &self.0.sdl
+    }
+
+    /// Plans the execution of a request without executing it.
+    pub fn plan(&self, request: impl Into<Request>) -> Result<QueryPlan, ServerError> {
+        let request = request.into();
+        self.plan_request(&request)
+    }
+
+    fn plan_request(&self, request: &Request) -> Result<QueryPlan, ServerError> {
+        let names = self
+            .0
+            .subgraphs
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        plan::plan(
+            &self.0.schema,
+            &names,
+            &request.query,
+            request.operation_name.as_deref(),
+            &request.variables,
+        )
+    }
+
+    /// Execute a GraphQL query.
+    pub async fn execute(&self, request: impl Into<Request>) -> Response {
+        let request = request.into();
+        let plan = match self.plan_request(&request) {
+            Ok(plan) => plan,
+            Err(err) => return Response::from_errors(vec![err]),
+        };
+        let variables = &request.variables;
+
+        let mut data = Value::Object(Default::default());
+        let mut errors = Vec::new();
+
+        if plan.serial {
+            for node in &plan.nodes {
+                let resp = self.fetch(node, variables.clone()).await;
+                self.merge_root(&mut data, &mut errors, node, resp, variables)
+                    .await;
+            }
+        } else {
+            let responses = futures_util::future::join_all(
+                plan.nodes
+                    .iter()
+                    .map(|node| self.fetch(node, variables.clone())),
+            )
+            .await;
+            for (node, resp) in plan.nodes.iter().zip(responses) {
+                self.merge_root(&mut data, &mut errors, node, resp, variables)
+                    .await;
+            }
+        }
+
+        let mut resp = Response::new(project(
+            &self.0.schema,
+            &data,
+            &plan.root_type,
+            &plan.selection_set,
+        ));
+        resp.errors = errors;
+        resp
+    }
+
+    async fn fetch(&self, node: &FetchNode, variables: Variables) -> Response {
+        let transport = &self.0.subgraphs[node.subgraph].1;
+        transport
+            .execute(Request::new(node.query.clone()).variables(variables))
+            .await
+    }
+
+    async fn merge_root(
+        &self,
+        data: &mut Value,
+        errors: &mut Vec<ServerError>,
+        node: &FetchNode,
+        resp: Response,
+        variables: &Variables,
+    ) {
+        errors.extend(resp.errors);
+        merge(data, resp.data);
+        self.execute_children(data, errors, vec![Vec::new()], &node.children, variables)
+            .await;
+    }
+
+    /// Executes the entity fetches of the children of a fetch, whose results
+    /// were merged at `locations`.
+    fn execute_children<'a>(
+        &'a self,
+        data: &'a mut Value,
+        errors: &'a mut Vec<ServerError>,
+        locations: Vec<Vec<PathSegment>>,
+        children: &'a [FetchNode],
+        variables: &'a Variables,
+    ) -> BoxFuture<'a, ()> {
+        async move {
+            let mut fetches = Vec::new();
+            for child in children {
+                let entity = match &child.entity {
+                    Some(entity) => entity,
+                    None => continue,
+                };
+                let mut entity_locations = Vec::new();
+                for location in &locations {
+                    if let Some(value) = value_at(data, location) {
+                        collect_entities(
+                            value,
+                            &entity.path,
+                            &entity.type_name,
+                            location.clone(),
+                            &mut entity_locations,
+                        );
+                    }
+                }
+
+                let mut representations = Vec::new();
+                entity_locations.retain(|location| {
+                    let representation = value_at(data, location).and_then(|value| {
+                        representation(value, &entity.type_name, &entity.key, &entity.requires)
+                    });
+                    match representation {
+                        Some(representation) => {
+                            representations.push(representation);
+                            true
+                        }
+                        None => false,
+                    }
+                });
+                if !entity_locations.is_empty() {
+                    fetches.push((child, entity_locations, representations));
+                }
+            }
+
+            let responses = futures_util::future::join_all(fetches.iter().map(
+                |(child, _, representations)| {
+                    let mut variables = variables.clone();
+                    variables.insert(
+                        Name::new("_representations"),
+                        Value::List(representations.clone()),
+                    );
+                    self.fetch(child, variables)
+                },
+            ))
+            .await;
+
+            for ((child, entity_locations, _), resp) in fetches.into_iter().zip(responses) {
+                for mut err in resp.errors {
+                    if let [PathSegment::Field(name), PathSegment::Index(idx), rest @ ..] =
+                        err.path.as_slice()
+                    {
+                        if name == "_entities" {
+                            if let Some(location) = entity_locations.get(*idx) {
+                                err.path = location.iter().cloned().chain(rest.to_vec()).collect();
+                            }
+                        }
+                    }
+                    errors.push(err);
+                }
+
+                if let Value::Object(mut resp_data) = resp.data {
+                    if let Some(Value::List(entities)) = resp_data.remove("_entities") {
+                        for (location, entity) in entity_locations.iter().zip(entities) {
+                            if let Some(value) = value_at_mut(data, location) {
+                                merge(value, entity);
+                            }
+                        }
+                    }
+                }
+
+                self.execute_children(data, errors, entity_locations, &child.children, variables)
+                    .await;
+            }
+        }
+        .boxed()
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for Supergraph {
+    async fn execute(&self, request: Request) -> Response {
+        Supergraph::execute(self, request).await
+    }
+
+    fn execute_stream(
+        &self,
+        _request: Request,
+        _session_data: Option<Arc<Data>>,
+    ) -> BoxStream<'static, Response> {
+        stream::once(async {
+            Response::from_errors(vec![ServerError::new(
+                "Subscriptions are not supported by the supergraph.",
+                None,
+            )])
+        })
+        .boxed()
+    }
+}
+
+fn value_at<'a>(value: &'a Value, location: &[PathSegment]) -> Option<&'a Value> {
+    location
+        .iter()
+        .try_fold(value, |value, segment| match (value, segment) {
+            (Value::Object(object), PathSegment::Field(name)) => object.get(name.as_str()),
+            (Value::List(items), PathSegment::Index(idx)) => items.get(*idx),
+            _ => None,
+        })
+}
+
+fn value_at_mut<'a>(value: &'a mut Value, location: &[PathSegment]) -> Option<&'a mut Value> {
+    location
+        .iter()
+        .try_fold(value, |value, segment| match (value, segment) {
+            (Value::Object(object), PathSegment::Field(name)) => object.get_mut(name.as_str()),
+            (Value::List(items), PathSegment::Index(idx)) => items.get_mut(*idx),
+            _ => None,
+        })
+}
+
+/// Collects the locations of the entities of a type at a path.
+fn collect_entities(
+    value: &Value,
+    path: &[String],
+    type_name: &str,
+    location: Vec<PathSegment>,
+    locations: &mut Vec<Vec<PathSegment>>,
+) {
+    match value {
+        Value::List(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                let mut location = location.clone();
+                location.push(PathSegment::Index(idx));
+                collect_entities(item, path, type_name, location, locations);
+            }
+        }
+        Value::Object(object) => match path.split_first() {
+            Some((name, path)) => {
+                if let Some(value) = object.get(name.as_str()) {
+                    let mut location = location;
+                    location.push(PathSegment::Field(name.clone()));
+                    collect_entities(value, path, type_name, location, locations);
+                }
+            }
+            None => {
+                if matches!(object.get("__typename"), Some(Value::String(name)) if name == type_name)
+                {
+                    locations.push(location);
+                }
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Creates the representation of an entity.
+fn representation(
+    value: &Value,
+    type_name: &str,
+    key: &[KeyField],
+    requires: &[KeyField],
+) -> Option<Value> {
+    let object = match value {
+        Value::Object(object) => object,
+        _ => return None,
+    };
+    let mut representation = indexmap::IndexMap::new();
+    representation.insert(
+        Name::new("__typename"),
+        Value::String(type_name.to_string()),
+    );
+    for field in key {
+        let value = select_field_set(object.get(field.name.as_str())?, &field.children)?;
+        if matches!(value, Value::Null) {
+            return None;
+        }
+        representation.insert(Name::new(&field.name), value);
+    }
+    for field in requires {
+        let value = match object.get(field.name.as_str()) {
+            Some(value) => select_field_set(value, &field.children)?,
+            None => Value::Null,
+        };
+        representation.insert(Name::new(&field.name), value);
+    }
+    Some(Value::Object(representation))
+}
+
+fn select_field_set(value: &Value, fields: &[KeyField]) -> Option<Value> {
+    if fields.is_empty() {
+        return Some(value.clone());
+    }
+    match value {
+        Value::Object(object) => {
+            let mut res = indexmap::IndexMap::new();
+            for field in fields {
+                let value = select_field_set(object.get(field.name.as_str())?, &field.children)?;
+                res.insert(Name::new(&field.name), value);
+            }
+            Some(Value::Object(res))
+        }
+        Value::List(items) => items
+            .iter()
+            .map(|item| select_field_set(item, fields))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::List),
+        _ => Some(value.clone()),
+    }
+}
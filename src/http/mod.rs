@@ -13,13 +13,58 @@ pub use graphiql_source::graphiql_source;
 pub use graphiql_v2_source::GraphiQLSource;
 use mime;
 pub use multipart::MultipartOptions;
+#[cfg(feature = "tempfile")]
+pub(crate) use multipart::ReaderStream;
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
 use serde::Deserialize;
 pub use websocket::{
     ClientMessage, Protocols as WebSocketProtocols, WebSocket, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
 };
 
-use crate::{BatchRequest, ParseRequestError, Request};
+use crate::{BatchRequest, ParseRequestError, Request, Value};
+
+/// The header sent by server-sent events clients with the id of the last event
+/// they received, when they reconnect.
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Apply the HTTP headers supported by async-graphql to each request of the
+/// batch.
+///
+/// `get_header` returns the value of the header with the given lowercase name.
+/// The [`LAST_EVENT_ID_HEADER`] header sets
+/// [`Request::last_event_id`](crate::Request::last_event_id), so that a
+/// server-sent events client resumes its subscriptions.
+///
+/// With the `apollo_tracing` feature, the `apollo-federation-include-trace:
+/// ftv1` header adds
+/// [`IncludeFederatedTrace`](crate::extensions::IncludeFederatedTrace) to the
+/// request data.
+pub fn apply_headers<'a>(
+    mut request: BatchRequest,
+    get_header: impl Fn(&str) -> Option<&'a str>,
+) -> BatchRequest {
+    if let Some(last_event_id) = get_header(LAST_EVENT_ID_HEADER) {
+        for request in request.iter_mut() {
+            request.extensions.insert(
+                "lastEventId".to_string(),
+                Value::String(last_event_id.to_string()),
+            );
+        }
+    }
+
+    #[cfg(feature = "apollo_tracing")]
+    {
+        use crate::extensions::{IncludeFederatedTrace, INCLUDE_TRACE_HEADER};
+
+        if let Some(include_trace) = get_header(INCLUDE_TRACE_HEADER)
+            .and_then(|value| IncludeFederatedTrace::from_header_value(value.as_bytes()))
+        {
+            request = request.data(include_trace);
+        }
+    }
+
+    request
+}
 
 /// Parse a GraphQL request from a query string.
 pub fn parse_query_string(input: &str) -> Result<Request, ParseRequestError> {
@@ -103,6 +148,53 @@ pub async fn receive_batch_body(
     }
 }
 
+/// Receive a GraphQL request from a content type and body, the files of a
+/// multipart request are not read until they are used.
+///
+/// See [`receive_batch_body_streaming`].
+pub async fn receive_body_streaming(
+    content_type: Option<impl AsRef<str>>,
+    body: impl AsyncRead + Send + 'static,
+    opts: MultipartOptions,
+) -> Result<Request, ParseRequestError> {
+    receive_batch_body_streaming(content_type, body, opts)
+        .await?
+        .into_single()
+}
+
+/// Receive a GraphQL request from a content type and body, the files of a
+/// multipart request are not read until they are used.
+///
+/// Only the `operations` and `map` fields of a multipart request are read, the
+/// files are not written to temporary files, they are read from the body by
+/// [`Upload::stream`](crate::Upload::stream) in the order in which they are
+/// sent.
+pub async fn receive_batch_body_streaming(
+    content_type: Option<impl AsRef<str>>,
+    body: impl AsyncRead + Send + 'static,
+    opts: MultipartOptions,
+) -> Result<BatchRequest, ParseRequestError> {
+    let content_type = content_type
+        .as_ref()
+        .map(AsRef::as_ref)
+        .unwrap_or("application/json");
+
+    let content_type: mime::Mime = content_type.parse()?;
+
+    match (content_type.type_(), content_type.subtype()) {
+        (mime::MULTIPART, _) => {
+            if let Some(boundary) = content_type.get_param("boundary") {
+                multipart::receive_batch_multipart_streaming(body, boundary.to_string(), opts).await
+            } else {
+                Err(ParseRequestError::InvalidMultipart(
+                    multer::Error::NoBoundary,
+                ))
+            }
+        }
+        _ => receive_batch_body_no_multipart(&content_type, body).await,
+    }
+}
+
 /// Receives a GraphQL query which is either cbor or json but NOT multipart
 /// This method is only to avoid recursive calls with [``receive_batch_body``]
 /// and [``multipart::receive_batch_multipart``]
@@ -186,4 +278,48 @@ mod tests {
             Variables::from_value(value!({ "a" : 10 }))
         );
     }
+
+    #[test]
+    fn test_apply_headers() {
+        let request = apply_headers(Request::new("{ a }").into(), |name| match name {
+            LAST_EVENT_ID_HEADER => Some("10"),
+            _ => None,
+        })
+        .into_single()
+        .unwrap();
+        assert_eq!(request.extensions["lastEventId"], value!("10"));
+
+        let request = apply_headers(Request::new("{ a }").into(), |_| None)
+            .into_single()
+            .unwrap();
+        assert!(!request.extensions.contains_key("lastEventId"));
+    }
+
+    #[cfg(feature = "apollo_tracing")]
+    #[test]
+    fn test_apply_headers_include_trace() {
+        use crate::extensions::IncludeFederatedTrace;
+
+        let request = apply_headers(Request::new("{ a }").into(), |name| match name {
+            "apollo-federation-include-trace" => Some("ftv1"),
+            _ => None,
+        })
+        .into_single()
+        .unwrap();
+        assert_eq!(
+            request
+                .data
+                .get(&std::any::TypeId::of::<IncludeFederatedTrace>())
+                .and_then(|data| data.downcast_ref::<IncludeFederatedTrace>()),
+            Some(&IncludeFederatedTrace)
+        );
+
+        let request = apply_headers(Request::new("{ a }").into(), |name| match name {
+            "apollo-federation-include-trace" => Some("other"),
+            _ => None,
+        })
+        .into_single()
+        .unwrap();
+        assert!(request.data.is_empty());
+    }
 }
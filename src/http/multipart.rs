@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     io,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -9,7 +10,7 @@ use futures_util::{io::AsyncRead, stream::Stream};
 use multer::{Constraints, Multipart, SizeLimit};
 use pin_project_lite::pin_project;
 
-use crate::{BatchRequest, ParseRequestError, UploadValue};
+use crate::{types::UploadStreams, BatchRequest, ParseRequestError, Request, UploadValue};
 
 /// Options for `receive_multipart`.
 #[derive(Default, Clone, Copy)]
@@ -46,102 +47,36 @@ pub(super) async fn receive_batch_multipart(
     boundary: impl Into<String>,
     opts: MultipartOptions,
 ) -> Result<BatchRequest, ParseRequestError> {
-    let mut multipart = Multipart::with_constraints(
-        ReaderStream::new(body),
-        boundary,
-        Constraints::new().size_limit({
-            let mut limit = SizeLimit::new();
-            if let (Some(max_file_size), Some(max_num_files)) =
-                (opts.max_file_size, opts.max_num_files)
-            {
-                limit = limit.whole_stream((max_file_size * max_num_files) as u64);
-            }
-            if let Some(max_file_size) = opts.max_file_size {
-                limit = limit.per_field(max_file_size as u64);
-            }
-            limit
-        }),
-    );
-
-    let mut request = None;
-    let mut map = None;
+    let mut multipart = create_multipart(body, boundary, opts);
+    let (mut request, mut map) = receive_operations(&mut multipart).await?;
     let mut files = Vec::new();
 
     while let Some(field) = multipart.next_field().await? {
-        // in multipart, each field / file can actually have a own Content-Type.
-        // We use this to determine the encoding of the graphql query
-        let content_type = field
-            .content_type()
-            // default to json
-            .unwrap_or(&mime::APPLICATION_JSON)
-            .clone();
-        match field.name() {
-            Some("operations") => {
-                let body = field.bytes().await?;
-                request = Some(
-                    super::receive_batch_body_no_multipart(&content_type, body.as_ref()).await?,
-                )
-            }
-            Some("map") => {
-                let map_bytes = field.bytes().await?;
-
-                match (content_type.type_(), content_type.subtype()) {
-                    // cbor is in application/octet-stream.
-                    // TODO: wait for mime to add application/cbor and match against that too
-                    // Note: we actually differ here from the inoffical spec for this:
-                    // (https://github.com/jaydenseric/graphql-multipart-request-spec#multipart-form-field-structure)
-                    // It says: "map: A JSON encoded map of where files occurred in the operations.
-                    // For each file, the key is the file multipart form field name and the value is
-                    // an array of operations paths." However, I think, that
-                    // since we accept CBOR as operation, which is valid, we should also accept it
-                    // as the mapping for the files.
-                    #[cfg(feature = "cbor")]
-                    (mime::OCTET_STREAM, _) | (mime::APPLICATION, mime::OCTET_STREAM) => {
-                        map = Some(
-                            serde_cbor::from_slice::<HashMap<String, Vec<String>>>(&map_bytes)
-                                .map_err(|e| ParseRequestError::InvalidFilesMap(Box::new(e)))?,
-                        );
-                    }
-                    // default to json
-                    _ => {
-                        map = Some(
-                            serde_json::from_slice::<HashMap<String, Vec<String>>>(&map_bytes)
-                                .map_err(|e| ParseRequestError::InvalidFilesMap(Box::new(e)))?,
-                        );
-                    }
-                }
-            }
-            _ => {
-                if let Some(name) = field.name().map(ToString::to_string) {
-                    if let Some(filename) = field.file_name().map(ToString::to_string) {
-                        let content_type = field.content_type().map(ToString::to_string);
-
-                        #[cfg(feature = "tempfile")]
-                        let content = {
-                            use std::io::{Seek, SeekFrom, Write};
-
-                            let mut field = field;
-                            let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                            while let Some(chunk) = field.chunk().await? {
-                                file.write(&chunk).map_err(ParseRequestError::Io)?;
-                            }
-                            file.seek(SeekFrom::Start(0))?;
-                            file
-                        };
-
-                        #[cfg(not(feature = "tempfile"))]
-                        let content = field.bytes().await?;
-
-                        files.push((name, filename, content_type, content));
+        if let Some(name) = field.name().map(ToString::to_string) {
+            if let Some(filename) = field.file_name().map(ToString::to_string) {
+                let content_type = field.content_type().map(ToString::to_string);
+
+                #[cfg(feature = "tempfile")]
+                let content = {
+                    use std::io::{Seek, SeekFrom, Write};
+
+                    let mut field = field;
+                    let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
+                    while let Some(chunk) = field.chunk().await? {
+                        file.write(&chunk).map_err(ParseRequestError::Io)?;
                     }
-                }
+                    file.seek(SeekFrom::Start(0))?;
+                    file
+                };
+
+                #[cfg(not(feature = "tempfile"))]
+                let content = field.bytes().await?;
+
+                files.push((name, filename, content_type, content));
             }
         }
     }
 
-    let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
-    let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
-
     for (name, filename, content_type, file) in files {
         if let Some(var_paths) = map.remove(&name) {
             let upload = UploadValue {
@@ -151,21 +86,8 @@ pub(super) async fn receive_batch_multipart(
             };
 
             for var_path in var_paths {
-                match &mut request {
-                    BatchRequest::Single(request) => {
-                        request.set_upload(&var_path, upload.try_clone()?);
-                    }
-                    BatchRequest::Batch(requests) => {
-                        let mut s = var_path.splitn(2, '.');
-                        let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
-                        let path = s.next();
-
-                        if let (Some(idx), Some(path)) = (idx, path) {
-                            if let Some(request) = requests.get_mut(idx) {
-                                request.set_upload(path, upload.try_clone()?);
-                            }
-                        }
-                    }
+                if let Some((request, path)) = request_of_var_path(&mut request, &var_path) {
+                    request.set_upload(path, upload.try_clone()?);
                 }
             }
         }
@@ -178,6 +100,132 @@ pub(super) async fn receive_batch_multipart(
     Ok(request)
 }
 
+pub(super) async fn receive_batch_multipart_streaming(
+    body: impl AsyncRead + Send + 'static,
+    boundary: impl Into<String>,
+    opts: MultipartOptions,
+) -> Result<BatchRequest, ParseRequestError> {
+    let mut multipart = create_multipart(body, boundary, opts);
+    let (mut request, map) = receive_operations(&mut multipart).await?;
+
+    for (name, var_paths) in map {
+        for var_path in var_paths {
+            if let Some((request, path)) = request_of_var_path(&mut request, &var_path) {
+                request.set_upload_stream(path, &name);
+            }
+        }
+    }
+
+    let upload_streams = Arc::new(UploadStreams::new(multipart));
+    match &mut request {
+        BatchRequest::Single(request) => request.upload_streams = Some(upload_streams),
+        BatchRequest::Batch(requests) => {
+            for request in requests {
+                request.upload_streams = Some(upload_streams.clone());
+            }
+        }
+    }
+    Ok(request)
+}
+
+fn create_multipart<'a>(
+    body: impl AsyncRead + Send + 'a,
+    boundary: impl Into<String>,
+    opts: MultipartOptions,
+) -> Multipart<'a> {
+    Multipart::with_constraints(
+        ReaderStream::new(body),
+        boundary,
+        Constraints::new().size_limit({
+            let mut limit = SizeLimit::new();
+            if let (Some(max_file_size), Some(max_num_files)) =
+                (opts.max_file_size, opts.max_num_files)
+            {
+                limit = limit.whole_stream((max_file_size * max_num_files) as u64);
+            }
+            if let Some(max_file_size) = opts.max_file_size {
+                limit = limit.per_field(max_file_size as u64);
+            }
+            limit
+        }),
+    )
+}
+
+/// Receives the `operations` and `map` fields, which must be the first two
+/// fields of the request.
+///
+/// **Reference:** <https://github.com/jaydenseric/graphql-multipart-request-spec#multipart-form-field-structure>
+async fn receive_operations(
+    multipart: &mut Multipart<'_>,
+) -> Result<(BatchRequest, HashMap<String, Vec<String>>), ParseRequestError> {
+    let field = match multipart.next_field().await? {
+        Some(field) if field.name() == Some("operations") => field,
+        _ => return Err(ParseRequestError::MissingOperatorsPart),
+    };
+    // in multipart, each field / file can actually have a own Content-Type.
+    // We use this to determine the encoding of the graphql query
+    let content_type = field
+        .content_type()
+        // default to json
+        .unwrap_or(&mime::APPLICATION_JSON)
+        .clone();
+    let body = field.bytes().await?;
+    let request = super::receive_batch_body_no_multipart(&content_type, body.as_ref()).await?;
+
+    let field = match multipart.next_field().await? {
+        Some(field) if field.name() == Some("map") => field,
+        _ => return Err(ParseRequestError::MissingMapPart),
+    };
+    let content_type = field
+        .content_type()
+        .unwrap_or(&mime::APPLICATION_JSON)
+        .clone();
+    let map_bytes = field.bytes().await?;
+
+    let map = match (content_type.type_(), content_type.subtype()) {
+        // cbor is in application/octet-stream.
+        // TODO: wait for mime to add application/cbor and match against that too
+        // Note: we actually differ here from the inoffical spec for this:
+        // (https://github.com/jaydenseric/graphql-multipart-request-spec#multipart-form-field-structure)
+        // It says: "map: A JSON encoded map of where files occurred in the operations.
+        // For each file, the key is the file multipart form field name and the value is
+        // an array of operations paths." However, I think, that
+        // since we accept CBOR as operation, which is valid, we should also accept it
+        // as the mapping for the files.
+        #[cfg(feature = "cbor")]
+        (mime::OCTET_STREAM, _) | (mime::APPLICATION, mime::OCTET_STREAM) => {
+            serde_cbor::from_slice::<HashMap<String, Vec<String>>>(&map_bytes)
+                .map_err(|e| ParseRequestError::InvalidFilesMap(Box::new(e)))?
+        }
+        // default to json
+        _ => serde_json::from_slice::<HashMap<String, Vec<String>>>(&map_bytes)
+            .map_err(|e| ParseRequestError::InvalidFilesMap(Box::new(e)))?,
+    };
+
+    Ok((request, map))
+}
+
+/// Returns the request and the variable path of an operation path of the
+/// `map` field.
+fn request_of_var_path<'a, 'b>(
+    request: &'a mut BatchRequest,
+    var_path: &'b str,
+) -> Option<(&'a mut Request, &'b str)> {
+    match request {
+        BatchRequest::Single(request) => Some((request, var_path)),
+        BatchRequest::Batch(requests) => {
+            let mut s = var_path.splitn(2, '.');
+            let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
+            let path = s.next();
+
+            match (idx, path) {
+                (Some(idx), Some(path)) => requests.get_mut(idx).map(|request| (request, path)),
+                _ => None,
+            }
+        }
+    }
+}
+
 pin_project! {
     pub(crate) struct ReaderStream<T> {
         buf: [u8; 2048],
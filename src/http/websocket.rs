@@ -16,7 +16,7 @@ use futures_util::{
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
 
-use crate::{Data, Error, Executor, Request, Response, Result};
+use crate::{Data, Error, Executor, Request, Response, Result, Value};
 
 /// All known protocols based on WebSocket.
 pub const ALL_WEBSOCKET_PROTOCOLS: [&str; 2] = ["graphql-transport-ws", "graphql-ws"];
@@ -328,7 +328,12 @@ impl Protocols {
     }
 
     #[inline]
-    fn next_message<'s>(&self, id: &'s str, payload: Response) -> ServerMessage<'s> {
+    fn next_message<'s>(&self, id: &'s str, mut payload: Response) -> ServerMessage<'s> {
+        if let Some(event_id) = payload.event_id.take() {
+            payload
+                .extensions
+                .insert("eventId".to_string(), Value::String(event_id));
+        }
         match self {
             Protocols::SubscriptionsTransportWS => ServerMessage::Data { id, payload },
             Protocols::GraphQLWS => ServerMessage::Next { id, payload },
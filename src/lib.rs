@@ -61,7 +61,8 @@
 //! default:
 //!
 //! - `apollo_tracing`: Enable the [Apollo tracing
-//!   extension](extensions/struct.ApolloTracing.html).
+//!   extension](extensions/struct.ApolloTracing.html) and the [Apollo
+//!   federated tracing extension](extensions/struct.ApolloFederatedTracing.html).
 //! - `apollo_persisted_queries`: Enable the [Apollo persisted queries
 //!   extension](extensions/apollo_persisted_queries/struct.
 //!   ApolloPersistedQueries.html).
@@ -80,6 +81,7 @@
 //! - `string_number`: Enable the
 //!   [StringNumber](types/struct.StringNumber.html).
 //! - `dataloader`: Support [DataLoader](dataloader/struct.DataLoader.html).
+//! - `broker`: Support the publish/subscribe [Broker](broker/struct.Broker.html).
 //! - `secrecy`: Integrate with the [`secrecy` crate](https://crates.io/crates/secrecy).
 //! - `decimal`: Integrate with the [`rust_decimal` crate](https://crates.io/crates/rust_decimal).
 //! - `bigdecimal`: Integrate with the [`bigdecimal` crate](https://crates.io/crates/bigdecimal).
@@ -92,6 +94,10 @@
 //! - `fast_chemail`: Integrate with the [`fast_chemail` crate](https://crates.io/crates/fast_chemail).
 //! - `tempfile`: Save the uploaded content in the temporary file.
 //! - `dynamic-schema`: Support dynamic schema.
+//! - `federation`: Support the in-process federation
+//!   [Supergraph](federation/struct.Supergraph.html).
+//! - `signed_cursor`: Enable the signed and encrypted [connection
+//!   cursors](types/connection/struct.SignedCursor.html).
 //!
 //! ## Integrations
 //!
@@ -194,6 +200,9 @@ mod schema;
 mod subscription;
 mod validation;
 
+#[cfg(feature = "broker")]
+#[cfg_attr(docsrs, doc(cfg(feature = "broker")))]
+pub mod broker;
 pub mod context;
 #[cfg(feature = "dataloader")]
 #[cfg_attr(docsrs, doc(cfg(feature = "dataloader")))]
@@ -202,6 +211,9 @@ pub mod dataloader;
 #[cfg_attr(docsrs, doc(cfg(feature = "dynamic-schema")))]
 pub mod dynamic;
 pub mod extensions;
+#[cfg(feature = "federation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "federation")))]
+pub mod federation;
 pub mod http;
 pub mod resolver_utils;
 pub mod types;
@@ -227,7 +239,7 @@ pub use base::{
 #[doc(hidden)]
 pub use context::ContextSelectionSet;
 pub use context::*;
-pub use custom_directive::{CustomDirective, CustomDirectiveFactory};
+pub use custom_directive::{CustomDirective, CustomDirectiveFactory, TypeDirective};
 pub use error::{
     Error, ErrorExtensionValues, ErrorExtensions, InputValueError, InputValueResult,
     ParseRequestError, PathSegment, Result, ResultExt, ServerError, ServerResult,
@@ -250,7 +262,9 @@ pub use response::{BatchResponse, Response};
 pub use schema::{IntrospectionMode, Schema, SchemaBuilder, SchemaEnv};
 #[doc(hidden)]
 pub use static_assertions;
-pub use subscription::SubscriptionType;
+pub use subscription::{
+    replay_events, SubscriptionBuffer, SubscriptionBufferPolicy, SubscriptionType,
+};
 pub use types::*;
 pub use validation::{ValidationMode, ValidationResult, VisitorContext};
 pub use validators::CustomValidator;
@@ -291,5 +305,7 @@ pub use async_graphql_derive::Scalar;
 pub use async_graphql_derive::SimpleObject;
 #[doc = include_str!("docs/subscription.md")]
 pub use async_graphql_derive::Subscription;
+#[doc = include_str!("docs/type_directive.md")]
+pub use async_graphql_derive::TypeDirective;
 #[doc = include_str!("docs/union.md")]
 pub use async_graphql_derive::Union;
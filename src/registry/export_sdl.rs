@@ -1,6 +1,9 @@
 use std::fmt::Write;
 
-use crate::registry::{Deprecation, MetaField, MetaInputValue, MetaType, Registry};
+use crate::registry::{
+    __DirectiveLocation, Deprecation, MetaDirectiveInvocation, MetaField, MetaInputValue, MetaType,
+    Registry,
+};
 
 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -95,6 +98,37 @@ impl Registry {
             sdl.write_str("directive @oneOf on INPUT_OBJECT\n\n").ok();
         }
 
+        let mut directives = self
+            .directives
+            .values()
+            .filter(|directive| directive.locations.iter().any(is_type_system_location))
+            .collect::<Vec<_>>();
+        directives.sort_by_key(|directive| &directive.name);
+
+        for directive in directives {
+            if let Some(description) = &directive.description {
+                export_description(&mut sdl, &options, true, description);
+            }
+            write!(sdl, "directive @{}", directive.name).ok();
+            if !directive.args.is_empty() {
+                let args = directive
+                    .args
+                    .values()
+                    .map(export_input_value)
+                    .collect::<Vec<_>>();
+                write!(sdl, "({})", args.join(", ")).ok();
+            }
+            if directive.is_repeatable {
+                write!(sdl, " repeatable").ok();
+            }
+            let locations = directive
+                .locations
+                .iter()
+                .map(|location| format!("{:?}", location))
+                .collect::<Vec<_>>();
+            writeln!(sdl, " on {}\n", locations.join(" | ")).ok();
+        }
+
         for ty in self.types.values() {
             if ty.name().starts_with("__") {
                 continue;
@@ -112,25 +146,74 @@ impl Registry {
         }
 
         if options.federation {
+            let (version, imports) = self.federation_imports();
             writeln!(sdl, "extend schema @link(").ok();
-            writeln!(sdl, "\turl: \"https://specs.apollo.dev/federation/v2.0\",").ok();
-            writeln!(sdl, "\timport: [\"@key\", \"@tag\", \"@shareable\", \"@inaccessible\", \"@override\", \"@external\", \"@provides\", \"@requires\"]").ok();
+            writeln!(
+                sdl,
+                "\turl: \"https://specs.apollo.dev/federation/v2.{}\",",
+                version
+            )
+            .ok();
+            writeln!(
+                sdl,
+                "\timport: [{}]",
+                imports
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .ok();
             writeln!(sdl, ")").ok();
-        } else {
-            writeln!(sdl, "schema {{").ok();
-            writeln!(sdl, "\tquery: {}", self.query_type).ok();
-            if let Some(mutation_type) = self.mutation_type.as_deref() {
-                writeln!(sdl, "\tmutation: {}", mutation_type).ok();
+            for link in &self.federation_links {
+                writeln!(
+                    sdl,
+                    "extend schema @link(url: \"{}\", import: [{}])",
+                    link.url,
+                    link.import
+                        .iter()
+                        .map(|name| format!("\"{}\"", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .ok();
+            }
+            for name in &self.compose_directives {
+                writeln!(sdl, "extend schema @composeDirective(name: \"{}\")", name).ok();
             }
-            if let Some(subscription_type) = self.subscription_type.as_deref() {
-                writeln!(sdl, "\tsubscription: {}", subscription_type).ok();
+
+            // Subgraphs only need a schema definition for renamed root types.
+            if self.query_type != "Query"
+                || self
+                    .mutation_type
+                    .as_deref()
+                    .map_or(false, |name| name != "Mutation")
+                || self
+                    .subscription_type
+                    .as_deref()
+                    .map_or(false, |name| name != "Subscription")
+            {
+                self.export_schema_definition(&mut sdl);
             }
-            writeln!(sdl, "}}").ok();
+        } else {
+            self.export_schema_definition(&mut sdl);
         }
 
         sdl
     }
 
+    fn export_schema_definition(&self, sdl: &mut String) {
+        writeln!(sdl, "schema {{").ok();
+        writeln!(sdl, "\tquery: {}", self.query_type).ok();
+        if let Some(mutation_type) = self.mutation_type.as_deref() {
+            writeln!(sdl, "\tmutation: {}", mutation_type).ok();
+        }
+        if let Some(subscription_type) = self.subscription_type.as_deref() {
+            writeln!(sdl, "\tsubscription: {}", subscription_type).ok();
+        }
+        writeln!(sdl, "}}").ok();
+    }
+
     fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
         sdl: &mut String,
         it: I,
@@ -176,6 +259,7 @@ impl Registry {
                             write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                         }
                     }
+                    write_directive_invocations(sdl, &arg.directive_invocations);
                 }
                 write!(sdl, "): {}", field.ty).ok();
             } else {
@@ -204,9 +288,16 @@ impl Registry {
                     write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                 }
                 if let Some(from) = field.override_from {
-                    write!(sdl, " @override(from: \"{}\")", from).ok();
+                    match field.override_label {
+                        Some(label) => {
+                            write!(sdl, " @override(from: \"{}\", label: \"{}\")", from, label).ok()
+                        }
+                        None => write!(sdl, " @override(from: \"{}\")", from).ok(),
+                    };
                 }
+                write_access_control(sdl, field.authenticated, &field.requires_scopes);
             }
+            write_directive_invocations(sdl, &field.directive_invocations);
 
             writeln!(sdl).ok();
         }
@@ -259,10 +350,15 @@ impl Registry {
                 fields,
                 extends,
                 keys,
+                resolvable,
+                interface_object,
+                authenticated,
+                requires_scopes,
                 description,
                 shareable,
                 inaccessible,
                 tags,
+                directive_invocations,
                 ..
             } => {
                 if Some(name.as_str()) == self.subscription_type.as_deref()
@@ -303,9 +399,16 @@ impl Registry {
                 if options.federation {
                     if let Some(keys) = keys {
                         for key in keys {
-                            write!(sdl, " @key(fields: \"{}\")", key).ok();
+                            if *resolvable {
+                                write!(sdl, " @key(fields: \"{}\")", key).ok();
+                            } else {
+                                write!(sdl, " @key(fields: \"{}\", resolvable: false)", key).ok();
+                            }
                         }
                     }
+                    if *interface_object {
+                        write!(sdl, " @interfaceObject").ok();
+                    }
                     if *shareable {
                         write!(sdl, " @shareable").ok();
                     }
@@ -317,7 +420,9 @@ impl Registry {
                     for tag in tags {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
+                    write_access_control(sdl, *authenticated, requires_scopes);
                 }
+                write_directive_invocations(sdl, directive_invocations);
 
                 writeln!(sdl, " {{").ok();
                 Self::export_fields(sdl, fields.values(), options);
@@ -328,6 +433,8 @@ impl Registry {
                 fields,
                 extends,
                 keys,
+                authenticated,
+                requires_scopes,
                 description,
                 inaccessible,
                 tags,
@@ -355,6 +462,7 @@ impl Registry {
                     for tag in tags {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
+                    write_access_control(sdl, *authenticated, requires_scopes);
                 }
                 self.write_implements(sdl, name);
 
@@ -406,6 +514,7 @@ impl Registry {
                             write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                         }
                     }
+                    write_directive_invocations(sdl, &value.directive_invocations);
                     writeln!(sdl).ok();
                 }
 
@@ -457,6 +566,7 @@ impl Registry {
                             write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                         }
                     }
+                    write_directive_invocations(sdl, &field.directive_invocations);
                     writeln!(sdl).ok();
                 }
 
@@ -497,6 +607,80 @@ impl Registry {
         }
     }
 
+    /// Returns the minor version of Federation 2 and the directives imported
+    /// from it, which depend on the directives used by the schema.
+    fn federation_imports(&self) -> (u32, Vec<&'static str>) {
+        let mut version = 0;
+        let mut imports = vec![
+            "@key",
+            "@tag",
+            "@shareable",
+            "@inaccessible",
+            "@override",
+            "@external",
+            "@provides",
+            "@requires",
+        ];
+        let mut interface_object = false;
+        let mut authenticated = false;
+        let mut requires_scopes = false;
+        let mut override_label = false;
+
+        for ty in self.types.values() {
+            let fields = match ty {
+                MetaType::Object {
+                    fields,
+                    interface_object: is_interface_object,
+                    authenticated: is_authenticated,
+                    requires_scopes: scopes,
+                    ..
+                } => {
+                    interface_object |= *is_interface_object;
+                    authenticated |= *is_authenticated;
+                    requires_scopes |= !scopes.is_empty();
+                    fields
+                }
+                MetaType::Interface {
+                    fields,
+                    authenticated: is_authenticated,
+                    requires_scopes: scopes,
+                    ..
+                } => {
+                    authenticated |= *is_authenticated;
+                    requires_scopes |= !scopes.is_empty();
+                    fields
+                }
+                _ => continue,
+            };
+            for field in fields.values() {
+                authenticated |= field.authenticated;
+                requires_scopes |= !field.requires_scopes.is_empty();
+                override_label |= field.override_label.is_some();
+            }
+        }
+
+        if !self.compose_directives.is_empty() {
+            version = version.max(1);
+            imports.push("@composeDirective");
+        }
+        if interface_object {
+            version = version.max(3);
+            imports.push("@interfaceObject");
+        }
+        if authenticated {
+            version = version.max(5);
+            imports.push("@authenticated");
+        }
+        if requires_scopes {
+            version = version.max(5);
+            imports.push("@requiresScopes");
+        }
+        if override_label {
+            version = version.max(7);
+        }
+        (version, imports)
+    }
+
     fn write_implements(&self, sdl: &mut String, name: &str) {
         if let Some(implements) = self.implements.get(name) {
             if !implements.is_empty() {
@@ -544,6 +728,45 @@ fn export_input_value(input_value: &MetaInputValue) -> String {
     }
 }
 
+fn write_access_control(sdl: &mut String, authenticated: bool, requires_scopes: &[String]) {
+    if authenticated {
+        write!(sdl, " @authenticated").ok();
+    }
+    if !requires_scopes.is_empty() {
+        let scopes = requires_scopes
+            .iter()
+            .map(|scopes| {
+                let scopes = scopes
+                    .split_whitespace()
+                    .map(|scope| format!("\"{}\"", scope.replace('"', "\\\"")))
+                    .collect::<Vec<_>>();
+                format!("[{}]", scopes.join(", "))
+            })
+            .collect::<Vec<_>>();
+        write!(sdl, " @requiresScopes(scopes: [{}])", scopes.join(", ")).ok();
+    }
+}
+
+fn write_directive_invocations(sdl: &mut String, invocations: &[MetaDirectiveInvocation]) {
+    for invocation in invocations {
+        write!(sdl, " {}", invocation.sdl()).ok();
+    }
+}
+
+fn is_type_system_location(location: &__DirectiveLocation) -> bool {
+    !matches!(
+        location,
+        __DirectiveLocation::QUERY
+            | __DirectiveLocation::MUTATION
+            | __DirectiveLocation::SUBSCRIPTION
+            | __DirectiveLocation::FIELD
+            | __DirectiveLocation::FRAGMENT_DEFINITION
+            | __DirectiveLocation::FRAGMENT_SPREAD
+            | __DirectiveLocation::INLINE_FRAGMENT
+            | __DirectiveLocation::VARIABLE_DEFINITION
+    )
+}
+
 fn write_deprecated(sdl: &mut String, deprecation: &Deprecation) {
     if let Deprecation::Deprecated { reason } = deprecation {
         let _ = match reason {
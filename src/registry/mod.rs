@@ -15,6 +15,7 @@ pub use crate::model::__DirectiveLocation;
 use crate::{
     model::__Schema,
     parser::types::{BaseType as ParsedBaseType, Field, Type as ParsedType, VariableDefinition},
+    relay::{Node, NodeFetcher},
     schema::IntrospectionMode,
     Any, Context, InputType, OutputType, Positioned, ServerResult, SubscriptionType, Value,
     VisitorContext, ID,
@@ -127,9 +128,12 @@ pub struct MetaInputValue {
     pub tags: Vec<String>,
     /// Indicate that an input obnject is secret
     pub is_secret: bool,
+    /// The type-system directives applied to the input value
+    pub directive_invocations: Vec<MetaDirectiveInvocation>,
 }
 
-type ComputeComplexityFn = fn(
+/// A function that computes the complexity of a field
+pub type ComputeComplexityFn = fn(
     &VisitorContext<'_>,
     &[Positioned<VariableDefinition>],
     &Field,
@@ -211,8 +215,19 @@ pub struct MetaField {
     /// Mark the field as overriding a field currently present on another
     /// subgraph. It is used to migrate fields between subgraphs.
     pub override_from: Option<&'static str>,
+    /// The label of a progressive override, which migrates the field
+    /// gradually, e.g. `percent(5)`.
+    pub override_label: Option<&'static str>,
+    /// Indicate that the field can only be accessed by authenticated users
+    pub authenticated: bool,
+    /// The scopes required to access the field, the field is accessible if
+    /// any of the groups is satisfied and a group is satisfied if all of its
+    /// space-separated scopes are granted
+    pub requires_scopes: Vec<String>,
     /// A constant or function to get the complexity
     pub compute_complexity: Option<ComplexityType>,
+    /// The type-system directives applied to the field
+    pub directive_invocations: Vec<MetaDirectiveInvocation>,
 }
 
 #[derive(Clone)]
@@ -223,9 +238,11 @@ pub struct MetaEnumValue {
     pub visible: Option<MetaVisibleFn>,
     pub inaccessible: bool,
     pub tags: Vec<String>,
+    pub directive_invocations: Vec<MetaDirectiveInvocation>,
 }
 
-type MetaVisibleFn = fn(&Context<'_>) -> bool;
+/// A function that uses to check if a schema element should be visible
+pub type MetaVisibleFn = fn(&Context<'_>) -> bool;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum MetaTypeId {
@@ -311,6 +328,26 @@ pub enum MetaType {
         ///
         /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#key>
         keys: Option<Vec<String>>,
+        /// Indicates that the keys are only used to reference the entity, which
+        /// cannot be resolved by this subgraph
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#key>
+        resolvable: bool,
+        /// Indicates that the object represents an interface entity of another
+        /// subgraph
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#interfaceobject>
+        interface_object: bool,
+        /// Indicates that the object can only be accessed by authenticated
+        /// users
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#authenticated>
+        authenticated: bool,
+        /// The scopes required to access the object, see
+        /// [`MetaField::requires_scopes`]
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#requiresscopes>
+        requires_scopes: Vec<String>,
         /// A function that uses to check if the object should be exported to
         /// schemas
         visible: Option<MetaVisibleFn>,
@@ -328,6 +365,8 @@ pub enum MetaType {
         is_subscription: bool,
         /// The Rust typename corresponding to the object
         rust_typename: Option<&'static str>,
+        /// The type-system directives applied to the object
+        directive_invocations: Vec<MetaDirectiveInvocation>,
     },
     /// Interface
     ///
@@ -352,6 +391,16 @@ pub enum MetaType {
         ///
         /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#key>
         keys: Option<Vec<String>>,
+        /// Indicates that the interface can only be accessed by authenticated
+        /// users
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#authenticated>
+        authenticated: bool,
+        /// The scopes required to access the interface, see
+        /// [`MetaField::requires_scopes`]
+        ///
+        /// Reference: <https://www.apollographql.com/docs/federation/federated-types/federated-directives/#requiresscopes>
+        requires_scopes: Vec<String>,
         /// A function that uses to check if the interface should be exported to
         /// schemas
         visible: Option<MetaVisibleFn>,
@@ -579,6 +628,44 @@ impl MetaType {
     }
 }
 
+/// A `@link` that imports the definitions of another specification into the
+/// federation schema
+///
+/// Reference: <https://specs.apollo.dev/link/v1.0/>
+#[derive(Debug, Clone)]
+pub struct MetaLink {
+    /// The URL of the specification
+    pub url: String,
+    /// The imported definitions, e.g. `@custom`
+    pub import: Vec<String>,
+}
+
+/// A type-system directive applied to a schema element, e.g.
+/// `@auth(role: ADMIN)`
+#[derive(Debug, Clone)]
+pub struct MetaDirectiveInvocation {
+    /// The name of the directive
+    pub name: String,
+    /// The arguments of the directive
+    pub args: IndexMap<String, Value>,
+}
+
+impl MetaDirectiveInvocation {
+    /// Returns the directive in SDL syntax.
+    pub fn sdl(&self) -> String {
+        if self.args.is_empty() {
+            format!("@{}", self.name)
+        } else {
+            let args = self
+                .args
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>();
+            format!("@{}({})", self.name, args.join(", "))
+        }
+    }
+}
+
 pub struct MetaDirective {
     pub name: String,
     pub description: Option<String>,
@@ -602,6 +689,9 @@ pub struct Registry {
     pub federation_subscription: bool,
     pub ignore_name_conflicts: HashSet<String>,
     pub enable_suggestions: bool,
+    pub node_fetchers: HashMap<String, NodeFetcher>,
+    pub federation_links: Vec<MetaLink>,
+    pub compose_directives: Vec<String>,
 }
 
 impl Registry {
@@ -625,6 +715,7 @@ impl Registry {
                     inaccessible: false,
                     tags: Default::default(),
                     is_secret: false,
+                    directive_invocations: Vec::new(),
                 });
                 args
             },
@@ -651,6 +742,7 @@ impl Registry {
                     inaccessible: false,
                     tags: Default::default(),
                     is_secret: false,
+                    directive_invocations: Vec::new(),
                 });
                 args
             },
@@ -745,9 +837,14 @@ impl Registry {
                         inaccessible: false,
                         tags: Default::default(),
                         keys: None,
+                        resolvable: true,
+                        interface_object: false,
+                        authenticated: false,
+                        requires_scopes: Default::default(),
                         visible: None,
                         is_subscription: false,
                         rust_typename: None,
+                        directive_invocations: Vec::new(),
                     },
                 );
                 let ty = f(self);
@@ -798,6 +895,139 @@ impl Registry {
             });
     }
 
+    /// Registers an object type that implements the Relay `Node` interface.
+    pub fn add_node_type<T: Node>(&mut self) {
+        let type_name = T::type_name().into_owned();
+        let interface =
+            self.types
+                .entry("Node".to_string())
+                .or_insert_with(|| MetaType::Interface {
+                    name: "Node".to_string(),
+                    description: Some("An object with a global ID.".to_string()),
+                    fields: {
+                        let mut fields = IndexMap::new();
+                        fields.insert(
+                            "id".to_string(),
+                            MetaField {
+                                name: "id".to_string(),
+                                description: Some("The global ID of the object.".to_string()),
+                                args: Default::default(),
+                                ty: "ID!".to_string(),
+                                deprecation: Default::default(),
+                                cache_control: Default::default(),
+                                external: false,
+                                requires: None,
+                                provides: None,
+                                shareable: false,
+                                inaccessible: false,
+                                tags: Default::default(),
+                                override_from: None,
+                                override_label: None,
+                                authenticated: false,
+                                requires_scopes: Default::default(),
+                                visible: None,
+                                compute_complexity: None,
+                                directive_invocations: Vec::new(),
+                            },
+                        );
+                        fields
+                    },
+                    possible_types: Default::default(),
+                    extends: false,
+                    keys: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    rust_typename: Some("async_graphql::relay::Node"),
+                });
+        if let MetaType::Interface { possible_types, .. } = interface {
+            possible_types.insert(type_name.clone());
+        }
+        self.add_implements(&type_name, "Node");
+        self.node_fetchers
+            .insert(type_name, crate::types::relay::fetch_node::<T>);
+    }
+
+    /// Adds the `node(id:)` and `nodes(ids:)` fields to the query type.
+    ///
+    /// Panics if the query type already has other fields with these names.
+    pub(crate) fn create_node_root_fields(&mut self) {
+        let id_arg = |name: &str, ty: &str| {
+            let mut args = IndexMap::new();
+            args.insert(
+                name.to_string(),
+                MetaInputValue {
+                    name: name.to_string(),
+                    description: None,
+                    ty: ty.to_string(),
+                    default_value: None,
+                    visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    is_secret: false,
+                    directive_invocations: Vec::new(),
+                },
+            );
+            args
+        };
+        let root_field = |name: &str, description: &str, args, ty: &str| MetaField {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            args,
+            ty: ty.to_string(),
+            deprecation: Default::default(),
+            cache_control: Default::default(),
+            external: false,
+            requires: None,
+            provides: None,
+            shareable: false,
+            inaccessible: false,
+            tags: Default::default(),
+            override_from: None,
+            override_label: None,
+            authenticated: false,
+            requires_scopes: Default::default(),
+            visible: None,
+            compute_complexity: None,
+            directive_invocations: Vec::new(),
+        };
+
+        if let Some(MetaType::Object { fields, .. }) = self.types.get_mut(&self.query_type) {
+            for field in [
+                root_field(
+                    "node",
+                    "Fetches an object given its global ID.",
+                    id_arg("id", "ID!"),
+                    "Node",
+                ),
+                root_field(
+                    "nodes",
+                    "Fetches objects given their global IDs.",
+                    id_arg("ids", "[ID!]!"),
+                    "[Node]!",
+                ),
+            ] {
+                match fields.get(&field.name) {
+                    // Already added, the fields are created again when types are registered
+                    // after the query type
+                    Some(prev)
+                        if prev.ty == field.ty
+                            && prev.description == field.description
+                            && prev.args.keys().eq(field.args.keys()) => {}
+                    Some(_) => panic!(
+                        "The field `{}` of the query type `{}` conflicts with the field generated for the `Node` interface",
+                        field.name, self.query_type,
+                    ),
+                    None => {
+                        fields.insert(field.name.clone(), field);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
@@ -847,6 +1077,7 @@ impl Registry {
                 MetaType::Object {
                     name,
                     keys: Some(keys),
+                    resolvable: true,
                     ..
                 } if !keys.is_empty() => Some(name.clone()),
                 MetaType::Interface {
@@ -875,8 +1106,12 @@ impl Registry {
                     inaccessible: false,
                     tags: Default::default(),
                     override_from: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
                     visible: None,
                     compute_complexity: None,
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -914,6 +1149,7 @@ impl Registry {
                                     inaccessible: false,
                                     tags: Default::default(),
                                     is_secret: false,
+                                    directive_invocations: Vec::new(),
                                 },
                             );
                             args
@@ -929,7 +1165,11 @@ impl Registry {
                         inaccessible: false,
                         tags: Default::default(),
                         override_from: None,
+                        override_label: None,
+                        authenticated: false,
+                        requires_scopes: Default::default(),
                         compute_complexity: None,
+                        directive_invocations: Vec::new(),
                     },
                 );
             }
@@ -957,7 +1197,11 @@ impl Registry {
                     tags: Default::default(),
                     visible: None,
                     compute_complexity: None,
+                    directive_invocations: Vec::new(),
                     override_from: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
                 },
             );
 
@@ -979,6 +1223,7 @@ impl Registry {
                                 inaccessible: false,
                                 tags: Default::default(),
                                 is_secret: false,
+                                directive_invocations: Vec::new(),
                             },
                         );
                         args
@@ -993,8 +1238,12 @@ impl Registry {
                     inaccessible: false,
                     tags: Default::default(),
                     override_from: None,
+                    override_label: None,
+                    authenticated: false,
+                    requires_scopes: Default::default(),
                     visible: None,
                     compute_complexity: None,
+                    directive_invocations: Vec::new(),
                 },
             );
         }
@@ -1027,7 +1276,11 @@ impl Registry {
                             inaccessible: false,
                             tags: Default::default(),
                             override_from: None,
+                            override_label: None,
+                            authenticated: false,
+                            requires_scopes: Default::default(),
                             compute_complexity: None,
+                            directive_invocations: Vec::new(),
                         },
                     );
                     fields
@@ -1036,11 +1289,16 @@ impl Registry {
                 extends: false,
                 shareable: false,
                 keys: None,
+                resolvable: true,
+                interface_object: false,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: Default::default(),
                 is_subscription: false,
                 rust_typename: Some("async_graphql::federation::Service"),
+                directive_invocations: Vec::new(),
             },
         );
 
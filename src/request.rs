@@ -2,6 +2,7 @@ use std::{
     any::Any,
     collections::HashMap,
     fmt::{self, Debug, Formatter},
+    sync::Arc,
 };
 
 use serde::{Deserialize, Deserializer, Serialize};
@@ -9,6 +10,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use crate::{
     parser::{parse_query, types::ExecutableDocument},
     schema::IntrospectionMode,
+    types::{UploadStreams, UPLOAD_PREFIX, UPLOAD_STREAM_PREFIX},
     Data, ParseRequestError, ServerError, UploadValue, Value, Variables,
 };
 
@@ -54,6 +56,9 @@ pub struct Request {
     /// [IntrospectionMode::Enabled]).
     #[serde(skip)]
     pub introspection_mode: IntrospectionMode,
+
+    #[serde(skip)]
+    pub(crate) upload_streams: Option<Arc<UploadStreams>>,
 }
 
 impl Request {
@@ -68,6 +73,7 @@ impl Request {
             extensions: Default::default(),
             parsed_query: None,
             introspection_mode: IntrospectionMode::Enabled,
+            upload_streams: None,
         }
     }
 
@@ -100,6 +106,18 @@ impl Request {
         self
     }
 
+    /// Set the id of the last event received by the client, used to resume a
+    /// subscription. This sets the `lastEventId` extension of the request.
+    ///
+    /// [`http::apply_headers`](crate::http::apply_headers) sets it from the
+    /// `Last-Event-ID` header of server-sent events requests.
+    #[must_use]
+    pub fn last_event_id(mut self, id: impl Into<String>) -> Self {
+        self.extensions
+            .insert("lastEventId".to_string(), Value::String(id.into()));
+        self
+    }
+
     /// Only allow introspection queries for this request.
     #[must_use]
     pub fn only_introspection(mut self) -> Self {
@@ -132,31 +150,39 @@ impl Request {
     /// to the Rust code `request.variables["files"][2]["content"]`. If no
     /// variable exists at the path this function won't do anything.
     pub fn set_upload(&mut self, var_path: &str, upload: UploadValue) {
-        fn variable_path<'a>(variables: &'a mut Variables, path: &str) -> Option<&'a mut Value> {
-            let mut parts = path.strip_prefix("variables.")?.split('.');
-
-            let initial = variables.get_mut(parts.next().unwrap())?;
-
-            parts.try_fold(initial, |current, part| match current {
-                Value::List(list) => part
-                    .parse::<u32>()
-                    .ok()
-                    .and_then(|idx| usize::try_from(idx).ok())
-                    .and_then(move |idx| list.get_mut(idx)),
-                Value::Object(obj) => obj.get_mut(part),
-                _ => None,
-            })
-        }
-
         let variable = match variable_path(&mut self.variables, var_path) {
             Some(variable) => variable,
             None => return,
         };
         self.uploads.push(upload);
-        *variable = Value::String(format!("#__graphql_file__:{}", self.uploads.len() - 1));
+        *variable = Value::String(format!("{}{}", UPLOAD_PREFIX, self.uploads.len() - 1));
+    }
+
+    /// Set a variable to a file that is read from the multipart request body
+    /// when it is used.
+    pub(crate) fn set_upload_stream(&mut self, var_path: &str, name: &str) {
+        if let Some(variable) = variable_path(&mut self.variables, var_path) {
+            *variable = Value::String(format!("{}{}", UPLOAD_STREAM_PREFIX, name));
+        }
     }
 }
 
+fn variable_path<'a>(variables: &'a mut Variables, path: &str) -> Option<&'a mut Value> {
+    let mut parts = path.strip_prefix("variables.")?.split('.');
+
+    let initial = variables.get_mut(parts.next().unwrap())?;
+
+    parts.try_fold(initial, |current, part| match current {
+        Value::List(list) => part
+            .parse::<u32>()
+            .ok()
+            .and_then(|idx| usize::try_from(idx).ok())
+            .and_then(move |idx| list.get_mut(idx)),
+        Value::Object(obj) => obj.get_mut(part),
+        _ => None,
+    })
+}
+
 impl<T: Into<String>> From<T> for Request {
     fn from(query: T) -> Self {
         Self::new(query)
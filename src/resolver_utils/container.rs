@@ -4,8 +4,10 @@ use futures_util::FutureExt;
 use indexmap::IndexMap;
 
 use crate::{
-    extensions::ResolveInfo, parser::types::Selection, Context, ContextBase, ContextSelectionSet,
-    Error, IntrospectionMode, Name, OutputType, ServerError, ServerResult, Value,
+    extensions::ResolveInfo,
+    parser::types::{Directive, Selection},
+    Context, ContextBase, ContextSelectionSet, Error, IntrospectionMode, Name, OutputType,
+    Positioned, ServerError, ServerResult, Value,
 };
 
 /// Represents a GraphQL container object.
@@ -48,6 +50,22 @@ pub trait ContainerType: OutputType {
     async fn find_entity(&self, _: &Context<'_>, _params: &Value) -> ServerResult<Option<Value>> {
         Ok(None)
     }
+
+    /// Find all the GraphQL entities of the given type name at once.
+    ///
+    /// `representations` holds the index of each representation in the
+    /// `_entities` list and its value. Returns `None` if no batch entity
+    /// resolver handles this type, in which case `find_entity` is called for
+    /// each representation instead. Otherwise a result must be returned for
+    /// each representation, in the same order.
+    async fn find_entities(
+        &self,
+        _ctx: &Context<'_>,
+        _type_name: &str,
+        _representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -59,6 +77,15 @@ impl<T: ContainerType + ?Sized> ContainerType for &T {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(*self, ctx, params).await
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        type_name: &str,
+        representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        T::find_entities(*self, ctx, type_name, representations).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,6 +97,15 @@ impl<T: ContainerType + ?Sized> ContainerType for Arc<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        type_name: &str,
+        representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        T::find_entities(self, ctx, type_name, representations).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,6 +117,15 @@ impl<T: ContainerType + ?Sized> ContainerType for Box<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        type_name: &str,
+        representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        T::find_entities(self, ctx, type_name, representations).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +143,18 @@ impl<T: ContainerType, E: Into<Error> + Send + Sync + Clone> ContainerType for R
             Err(err) => Err(ctx.set_error_path(err.clone().into().into_server_error(ctx.item.pos))),
         }
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        type_name: &str,
+        representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        match self {
+            Ok(value) => T::find_entities(value, ctx, type_name, representations).await,
+            Err(_) => None,
+        }
+    }
 }
 
 /// Resolve an container by executing each of the fields concurrently.
@@ -155,28 +212,57 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
     root: &'a T,
     parallel: bool,
 ) -> ServerResult<Value> {
-    let mut fields = Fields(Vec::new());
-    fields.add_set(ctx, root)?;
-
-    let res = if parallel {
-        futures_util::future::try_join_all(fields.0).await?
-    } else {
-        let mut results = Vec::with_capacity(fields.0.len());
-        for field in fields.0 {
-            results.push(field.await?);
-        }
-        results
+    let mut fields = Fields {
+        futures: Vec::new(),
+        serial: !parallel,
     };
+    fields.add_set(ctx, root)?;
+    let fut = fields.resolve();
+    #[cfg(feature = "dataloader")]
+    let fut = crate::dataloader::drive_batches(fut);
+    fut.await
+}
 
-    Ok(create_value_object(res))
+enum FieldValue {
+    Field(Name, Value),
+    /// The fields of a fragment wrapped by custom directives.
+    Fragment(IndexMap<Name, Value>),
 }
 
-type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
+type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<FieldValue>> + 'a + Send>>;
 
 /// A set of fields on an container that are being selected.
-pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
+pub struct Fields<'a> {
+    futures: Vec<BoxFieldFuture<'a>>,
+    serial: bool,
+}
 
 impl<'a> Fields<'a> {
+    async fn resolve(self) -> ServerResult<Value> {
+        let res = if self.serial {
+            let mut results = Vec::with_capacity(self.futures.len());
+            for field in self.futures {
+                results.push(field.await?);
+            }
+            results
+        } else {
+            futures_util::future::try_join_all(self.futures).await?
+        };
+
+        let mut map = IndexMap::new();
+        for value in res {
+            match value {
+                FieldValue::Field(name, value) => insert_value(&mut map, name, value),
+                FieldValue::Fragment(values) => {
+                    for (name, value) in values {
+                        insert_value(&mut map, name, value);
+                    }
+                }
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
     /// Add another set of fields to this set of fields using the given
     /// container.
     pub fn add_set<T: ContainerType + ?Sized>(
@@ -200,12 +286,15 @@ impl<'a> Fields<'a> {
                             let field_name = ctx_field.item.node.response_key().node.clone();
                             let typename = root.introspection_type_name().into_owned();
 
-                            self.0.push(Box::pin(async move {
-                                Ok((field_name, Value::String(typename)))
+                            self.futures.push(Box::pin(async move {
+                                Ok(FieldValue::Field(field_name, Value::String(typename)))
                             }));
                         } else {
-                            self.0.push(Box::pin(async move {
-                                Ok((field.node.response_key().node.clone(), Value::Null))
+                            self.futures.push(Box::pin(async move {
+                                Ok(FieldValue::Field(
+                                    field.node.response_key().node.clone(),
+                                    Value::Null,
+                                ))
                             }));
                         }
 
@@ -220,7 +309,7 @@ impl<'a> Fields<'a> {
                             let extensions = &ctx.query_env.extensions;
 
                             if extensions.is_empty() && field.node.directives.is_empty() {
-                                Ok((
+                                Ok(FieldValue::Field(
                                     field_name,
                                     root.resolve_field(&ctx_field).await?.unwrap_or_default(),
                                 ))
@@ -263,7 +352,7 @@ impl<'a> Fields<'a> {
 
                                 if field.node.directives.is_empty() {
                                     futures_util::pin_mut!(resolve_fut);
-                                    Ok((
+                                    Ok(FieldValue::Field(
                                         field_name,
                                         extensions
                                             .resolve(resolve_info, &mut resolve_fut)
@@ -285,6 +374,7 @@ impl<'a> Fields<'a> {
                                                 item: directive,
                                                 schema_env: ctx_field.schema_env,
                                                 query_env: ctx_field.query_env,
+                                                batch_scope: ctx_field.batch_scope,
                                             };
                                             let directive_instance = directive_factory
                                                 .create(&ctx_directive, &directive.node)?;
@@ -299,7 +389,7 @@ impl<'a> Fields<'a> {
                                         }
                                     }
 
-                                    Ok((
+                                    Ok(FieldValue::Field(
                                         field_name,
                                         extensions
                                             .resolve(resolve_info, &mut resolve_fut)
@@ -311,7 +401,7 @@ impl<'a> Fields<'a> {
                         }
                     });
 
-                    self.0.push(resolve_fut);
+                    self.futures.push(resolve_fut);
                 }
                 selection => {
                     let (type_condition, selection_set) = match selection {
@@ -355,15 +445,91 @@ impl<'a> Fields<'a> {
                                 .get(&*introspection_type_name)
                                 .map_or(false, |interfaces| interfaces.contains(condition))
                     });
-                    if applies_concrete_object {
-                        root.collect_all_fields(&ctx.with_selection_set(selection_set), self)?;
-                    } else if type_condition.map_or(true, |condition| T::type_name() == condition) {
-                        // The fragment applies to an interface type.
-                        self.add_set(&ctx.with_selection_set(selection_set), root)?;
+                    let applies_interface = !applies_concrete_object
+                        && type_condition.map_or(true, |condition| T::type_name() == condition);
+                    if !applies_concrete_object && !applies_interface {
+                        continue;
+                    }
+
+                    let ctx_fragment = ctx.with_selection_set(selection_set);
+                    let directives = selection.directives();
+                    if directives.iter().any(|directive| {
+                        ctx.schema_env
+                            .custom_directives
+                            .contains_key(directive.node.name.node.as_str())
+                    }) {
+                        let mut fields = Fields {
+                            futures: Vec::new(),
+                            serial: self.serial,
+                        };
+                        fields.add_fragment_set(&ctx_fragment, root, applies_concrete_object)?;
+                        self.add_fragment(ctx_fragment, directives, fields)?;
+                    } else {
+                        self.add_fragment_set(&ctx_fragment, root, applies_concrete_object)?;
                     }
                 }
             }
         }
         Ok(())
     }
+
+    fn add_fragment_set<T: ContainerType + ?Sized>(
+        &mut self,
+        ctx: &ContextSelectionSet<'a>,
+        root: &'a T,
+        applies_concrete_object: bool,
+    ) -> ServerResult<()> {
+        if applies_concrete_object {
+            root.collect_all_fields(ctx, self)
+        } else {
+            // The fragment applies to an interface type.
+            self.add_set(ctx, root)
+        }
+    }
+
+    /// Add the fields of a fragment that are resolved together, so that the
+    /// custom directives of the fragment can wrap them.
+    fn add_fragment(
+        &mut self,
+        ctx: ContextSelectionSet<'a>,
+        directives: &'a [Positioned<Directive>],
+        fields: Fields<'a>,
+    ) -> ServerResult<()> {
+        let mut resolve_fut = async move { fields.resolve().await.map(Some) }.boxed();
+
+        for directive in directives {
+            if let Some(directive_factory) = ctx
+                .schema_env
+                .custom_directives
+                .get(directive.node.name.node.as_str())
+            {
+                let ctx_directive = ContextBase {
+                    path_node: ctx.path_node,
+                    is_for_introspection: false,
+                    item: directive,
+                    schema_env: ctx.schema_env,
+                    query_env: ctx.query_env,
+                    batch_scope: ctx.batch_scope,
+                };
+                let directive_instance =
+                    directive_factory.create(&ctx_directive, &directive.node)?;
+                resolve_fut = Box::pin({
+                    let ctx = ctx.clone();
+                    async move {
+                        directive_instance
+                            .resolve_fragment(&ctx, &mut resolve_fut)
+                            .await
+                    }
+                });
+            }
+        }
+
+        self.futures.push(Box::pin(async move {
+            match resolve_fut.await? {
+                Some(Value::Object(values)) => Ok(FieldValue::Fragment(values)),
+                _ => Ok(FieldValue::Fragment(IndexMap::new())),
+            }
+        }));
+        Ok(())
+    }
 }
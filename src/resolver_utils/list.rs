@@ -1,8 +1,137 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
 use crate::{
     extensions::ResolveInfo, parser::types::Field, ContextSelectionSet, OutputType, Positioned,
-    ServerResult, Value,
+    QueryPathNode, QueryPathSegment, ServerError, ServerResult, Value,
 };
 
+type BatchResults = Arc<futures_util::lock::Mutex<Option<Box<dyn Any + Send>>>>;
+
+/// The parents of the batch resolvers of a list.
+pub(crate) struct BatchScope<'a> {
+    depth: usize,
+    parents: Vec<Option<&'a (dyn Any + Send + Sync)>>,
+    results: Mutex<HashMap<String, BatchResults>>,
+}
+
+fn path_len(mut node: Option<&QueryPathNode>) -> usize {
+    let mut len = 0;
+    while let Some(current) = node {
+        len += 1;
+        node = current.parent;
+    }
+    len
+}
+
+impl<'a> BatchScope<'a> {
+    /// Returns the index of the parent if the field belongs to an item of the
+    /// list.
+    fn parent_index(&self, field_node: &QueryPathNode) -> Option<usize> {
+        let item_node = field_node.parent?;
+        match item_node.segment {
+            QueryPathSegment::Index(idx) if path_len(Some(item_node)) == self.depth => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// Calls the batch resolver once with all the parents of the list, and
+    /// returns the value of the parent at `idx`.
+    pub(crate) async fn resolve<'p, P, T, F, Fut>(
+        &'p self,
+        field: &Positioned<Field>,
+        field_node: &QueryPathNode<'_>,
+        parent: &'p P,
+        f: F,
+    ) -> ServerResult<T>
+    where
+        P: Any + Send + Sync,
+        T: Send + 'static,
+        F: FnOnce(Vec<&'p P>) -> Fut,
+        Fut: Future<Output = ServerResult<Vec<T>>>,
+    {
+        let idx = match self
+            .parent_index(field_node)
+            .filter(|idx| matches!(self.parents.get(*idx), Some(Some(parent)) if parent.is::<P>()))
+        {
+            Some(idx) => idx,
+            None => return resolve_batch_single(field, parent, f).await,
+        };
+
+        let key = format!(
+            "{}.{}",
+            std::any::type_name::<P>(),
+            field.node.response_key().node
+        );
+        let results = self.results.lock().unwrap().entry(key).or_default().clone();
+        let mut results = results.lock().await;
+
+        if results.is_none() {
+            let (indexes, parents): (Vec<_>, Vec<_>) = self
+                .parents
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, parent)| {
+                    parent
+                        .and_then(|parent| parent.downcast_ref::<P>())
+                        .map(|parent| (idx, parent))
+                })
+                .unzip();
+            let count = parents.len();
+            let res = f(parents).await.and_then(|values| {
+                if values.len() != count {
+                    return Err(batch_len_error(field, values.len(), count));
+                }
+                Ok(indexes.into_iter().zip(values).collect::<HashMap<_, _>>())
+            });
+            *results = Some(Box::new(res));
+        }
+
+        match results
+            .as_mut()
+            .and_then(|results| results.downcast_mut::<ServerResult<HashMap<usize, T>>>())
+            .expect("You definitely encountered a bug!")
+        {
+            Ok(values) => Ok(values
+                .remove(&idx)
+                .expect("You definitely encountered a bug!")),
+            Err(err) => Err(err.clone()),
+        }
+    }
+}
+
+/// Calls the batch resolver with a single parent, used when the object is not
+/// resolved as an item of a list.
+pub(crate) async fn resolve_batch_single<'p, P, T, F, Fut>(
+    field: &Positioned<Field>,
+    parent: &'p P,
+    f: F,
+) -> ServerResult<T>
+where
+    F: FnOnce(Vec<&'p P>) -> Fut,
+    Fut: Future<Output = ServerResult<Vec<T>>>,
+{
+    let mut values = f(vec![parent]).await?;
+    if values.len() != 1 {
+        return Err(batch_len_error(field, values.len(), 1));
+    }
+    Ok(values.remove(0))
+}
+
+fn batch_len_error(field: &Positioned<Field>, values: usize, parents: usize) -> ServerError {
+    ServerError::new(
+        format!(
+            "The batch resolver returned {} values for {} parents.",
+            values, parents
+        ),
+        Some(field.pos),
+    )
+}
+
 /// Resolve an list by executing each of the items concurrently.
 pub async fn resolve_list<'a, T: OutputType + 'a>(
     ctx: &ContextSelectionSet<'a>,
@@ -10,47 +139,68 @@ pub async fn resolve_list<'a, T: OutputType + 'a>(
     iter: impl IntoIterator<Item = T>,
     len: Option<usize>,
 ) -> ServerResult<Value> {
+    let mut items = len.map(Vec::with_capacity).unwrap_or_default();
+    items.extend(iter);
+
+    let parents = items
+        .iter()
+        .map(OutputType::batch_parent)
+        .collect::<Vec<_>>();
+    let batch_scope = if parents.iter().any(Option::is_some) {
+        Some(BatchScope {
+            depth: path_len(ctx.path_node.as_ref()) + 1,
+            parents,
+            results: Default::default(),
+        })
+    } else {
+        None
+    };
+    let batch_scope = batch_scope.as_ref();
+
     let extensions = &ctx.query_env.extensions;
     if !extensions.is_empty() {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            futures.push({
-                let ctx = ctx.clone();
-                async move {
-                    let ctx_idx = ctx.with_index(idx);
-                    let extensions = &ctx.query_env.extensions;
-
-                    let resolve_info = ResolveInfo {
-                        path_node: ctx_idx.path_node.as_ref().unwrap(),
-                        parent_type: &Vec::<T>::type_name(),
-                        return_type: &T::qualified_type_name(),
-                        name: field.node.name.node.as_str(),
-                        alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
-                        is_for_introspection: ctx_idx.is_for_introspection,
-                    };
-                    let resolve_fut = async {
-                        OutputType::resolve(&item, &ctx_idx, field)
-                            .await
-                            .map(Option::Some)
-                            .map_err(|err| ctx_idx.set_error_path(err))
-                    };
-                    futures_util::pin_mut!(resolve_fut);
-                    extensions
-                        .resolve(resolve_info, &mut resolve_fut)
-                        .await
-                        .map(|value| value.expect("You definitely encountered a bug!"))
+        let mut futures = Vec::with_capacity(items.len());
+        for (idx, item) in items.iter().enumerate() {
+            futures.push(async move {
+                let mut ctx_idx = ctx.with_index(idx);
+                if batch_scope.is_some() {
+                    ctx_idx.batch_scope = batch_scope;
                 }
+                let extensions = &ctx.query_env.extensions;
+
+                let resolve_info = ResolveInfo {
+                    path_node: ctx_idx.path_node.as_ref().unwrap(),
+                    parent_type: &Vec::<T>::type_name(),
+                    return_type: &T::qualified_type_name(),
+                    name: field.node.name.node.as_str(),
+                    alias: field.node.alias.as_ref().map(|alias| alias.node.as_str()),
+                    is_for_introspection: ctx_idx.is_for_introspection,
+                };
+                let resolve_fut = async {
+                    OutputType::resolve(item, &ctx_idx, field)
+                        .await
+                        .map(Option::Some)
+                        .map_err(|err| ctx_idx.set_error_path(err))
+                };
+                futures_util::pin_mut!(resolve_fut);
+                extensions
+                    .resolve(resolve_info, &mut resolve_fut)
+                    .await
+                    .map(|value| value.expect("You definitely encountered a bug!"))
             });
         }
         Ok(Value::List(
             futures_util::future::try_join_all(futures).await?,
         ))
     } else {
-        let mut futures = len.map(Vec::with_capacity).unwrap_or_default();
-        for (idx, item) in iter.into_iter().enumerate() {
-            let ctx_idx = ctx.with_index(idx);
+        let mut futures = Vec::with_capacity(items.len());
+        for (idx, item) in items.iter().enumerate() {
+            let mut ctx_idx = ctx.with_index(idx);
+            if batch_scope.is_some() {
+                ctx_idx.batch_scope = batch_scope;
+            }
             futures.push(async move {
-                OutputType::resolve(&item, &ctx_idx, field)
+                OutputType::resolve(item, &ctx_idx, field)
                     .await
                     .map_err(|err| ctx_idx.set_error_path(err))
             });
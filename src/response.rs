@@ -31,6 +31,11 @@ pub struct Response {
     /// HTTP headers
     #[serde(skip)]
     pub http_headers: HeaderMap,
+
+    /// The id of the subscription event, see
+    /// [`SubscriptionEvent`](crate::SubscriptionEvent).
+    #[serde(skip)]
+    pub event_id: Option<String>,
 }
 
 impl Response {
@@ -77,6 +82,15 @@ impl Response {
         }
     }
 
+    /// Set the id of the subscription event.
+    #[must_use]
+    pub fn event_id(self, event_id: impl Into<String>) -> Self {
+        Self {
+            event_id: Some(event_id.into()),
+            ..self
+        }
+    }
+
     /// Returns `true` if the response is ok.
     #[inline]
     pub fn is_ok(&self) -> bool {
@@ -6,7 +6,10 @@ use std::{
 };
 
 use async_graphql_parser::types::ExecutableDocument;
-use futures_util::stream::{self, BoxStream, Stream, StreamExt};
+use futures_util::{
+    stream::{self, BoxStream, Stream, StreamExt},
+    FutureExt,
+};
 
 use crate::{
     context::{Data, QueryEnvInner},
@@ -17,14 +20,14 @@ use crate::{
         types::{Directive, DocumentOperations, OperationType, Selection, SelectionSet},
         Positioned,
     },
-    registry::{Registry, SDLExportOptions},
+    registry::{MetaLink, Registry, SDLExportOptions},
     resolver_utils::{resolve_container, resolve_container_serial},
     subscription::collect_subscription_streams,
     types::QueryRoot,
     validation::{check_rules, ValidationMode},
     BatchRequest, BatchResponse, CacheControl, ContextBase, EmptyMutation, EmptySubscription,
     Executor, InputType, ObjectType, OutputType, QueryEnv, Request, Response, ServerError,
-    ServerResult, SubscriptionType, Variables,
+    ServerResult, SubscriptionBufferPolicy, SubscriptionType, Value, Variables,
 };
 
 /// Introspection mode
@@ -57,6 +60,7 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     recursive_depth: usize,
     extensions: Vec<Box<dyn ExtensionFactory>>,
     custom_directives: HashMap<String, Box<dyn CustomDirectiveFactory>>,
+    subscription_buffer_policy: SubscriptionBufferPolicy,
 }
 
 impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription> {
@@ -179,6 +183,32 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Import the definitions of another specification into the Federation
+    /// SDL with a `@link` directive.
+    ///
+    /// The custom directives must be imported before they are composed with
+    /// [`SchemaBuilder::compose_directive`].
+    #[must_use]
+    pub fn link<I, T>(mut self, url: impl Into<String>, import: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.registry.federation_links.push(MetaLink {
+            url: url.into(),
+            import: import.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Preserve a custom directive in the supergraph with a
+    /// `@composeDirective` directive, e.g. `@custom`.
+    #[must_use]
+    pub fn compose_directive(mut self, name: impl Into<String>) -> Self {
+        self.registry.compose_directives.push(name.into());
+        self
+    }
+
     /// Override the name of the specified input type.
     #[must_use]
     pub fn override_input_type_description<T: InputType>(mut self, desc: &'static str) -> Self {
@@ -225,6 +255,17 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Set the buffering policy of subscription fields, default is
+    /// `SubscriptionBufferPolicy::Unbounded`.
+    ///
+    /// A policy specified with `#[graphql(buffer_policy(...))]` on a
+    /// subscription field takes precedence over this one.
+    #[must_use]
+    pub fn subscription_buffer_policy(mut self, policy: SubscriptionBufferPolicy) -> Self {
+        self.subscription_buffer_policy = policy;
+        self
+    }
+
     /// Consumes this builder and returns a schema.
     pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
         // federation
@@ -232,6 +273,11 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
             self.registry.create_federation_types();
         }
 
+        // relay
+        if !self.registry.node_fetchers.is_empty() {
+            self.registry.create_node_root_fields();
+        }
+
         Schema(Arc::new(SchemaInner {
             validation_mode: self.validation_mode,
             query: self.query,
@@ -245,6 +291,7 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
                 registry: self.registry,
                 data: self.data,
                 custom_directives: self.custom_directives,
+                subscription_buffer_policy: self.subscription_buffer_policy,
             })),
         }))
     }
@@ -255,6 +302,7 @@ pub struct SchemaEnvInner {
     pub registry: Registry,
     pub data: Data,
     pub custom_directives: HashMap<String, Box<dyn CustomDirectiveFactory>>,
+    pub subscription_buffer_policy: SubscriptionBufferPolicy,
 }
 
 #[doc(hidden)]
@@ -358,6 +406,7 @@ where
             recursive_depth: 32,
             extensions: Default::default(),
             custom_directives: Default::default(),
+            subscription_buffer_policy: Default::default(),
         }
     }
 
@@ -382,6 +431,9 @@ where
             federation_subscription: false,
             ignore_name_conflicts,
             enable_suggestions: true,
+            node_fetchers: Default::default(),
+            federation_links: Default::default(),
+            compose_directives: Default::default(),
         };
         registry.add_system_types();
 
@@ -406,9 +458,11 @@ where
         Self::build(query, mutation, subscription).finish()
     }
 
+    /// Returns the type registry of this schema, which contains the metadata
+    /// of all types and fields, such as the type-system directives applied to
+    /// them.
     #[inline]
-    #[allow(unused)]
-    pub(crate) fn registry(&self) -> &Registry {
+    pub fn registry(&self) -> &Registry {
         &self.0.env.registry
     }
 
@@ -440,6 +494,55 @@ where
         )
     }
 
+    /// Calls the custom directives of the variable definitions, which can
+    /// replace the values of the variables before the operation is executed.
+    async fn resolve_variables(&self, mut env: QueryEnv) -> ServerResult<QueryEnv> {
+        let mut variables = Vec::new();
+        for variable_definition in &env.operation.node.variable_definitions {
+            let name = &variable_definition.node.name.node;
+            let mut value = None;
+            for directive in &variable_definition.node.directives {
+                if let Some(directive_factory) = self
+                    .0
+                    .env
+                    .custom_directives
+                    .get(directive.node.name.node.as_str())
+                {
+                    let ctx_directive = env.create_context(&self.0.env, None, directive);
+                    let directive_instance =
+                        directive_factory.create(&ctx_directive, &directive.node)?;
+                    let ctx =
+                        env.create_context(&self.0.env, None, &env.operation.node.selection_set);
+                    let current = match value.take() {
+                        Some(value) => value,
+                        None => env
+                            .variables
+                            .get(name)
+                            .or_else(|| variable_definition.node.default_value())
+                            .cloned()
+                            .unwrap_or_default(),
+                    };
+                    value = Some(
+                        directive_instance
+                            .resolve_variable(&ctx, name, current)
+                            .await?,
+                    );
+                }
+            }
+            if let Some(value) = value {
+                variables.push((name.clone(), value));
+            }
+        }
+
+        if !variables.is_empty() {
+            let inner = env
+                .get_mut()
+                .expect("the query environment is not shared before execution");
+            inner.variables.extend(variables);
+        }
+        Ok(env)
+    }
+
     async fn execute_once(&self, env: QueryEnv) -> Response {
         // execute
         let ctx = ContextBase {
@@ -448,24 +551,55 @@ where
             item: &env.operation.node.selection_set,
             schema_env: &self.0.env,
             query_env: &env,
+            batch_scope: None,
         };
 
-        let res = match &env.operation.node.ty {
-            OperationType::Query => resolve_container(&ctx, &self.0.query).await,
-            OperationType::Mutation => {
-                if self.0.env.registry.introspection_mode == IntrospectionMode::IntrospectionOnly
-                    || env.introspection_mode == IntrospectionMode::IntrospectionOnly
+        let res = async {
+            let mut resolve_fut = async {
+                match &env.operation.node.ty {
+                    OperationType::Query => resolve_container(&ctx, &self.0.query).await,
+                    OperationType::Mutation => {
+                        if self.0.env.registry.introspection_mode
+                            == IntrospectionMode::IntrospectionOnly
+                            || env.introspection_mode == IntrospectionMode::IntrospectionOnly
+                        {
+                            resolve_container_serial(&ctx, &EmptyMutation).await
+                        } else {
+                            resolve_container_serial(&ctx, &self.0.mutation).await
+                        }
+                    }
+                    OperationType::Subscription => Err(ServerError::new(
+                        "Subscriptions are not supported on this transport.",
+                        None,
+                    )),
+                }
+                .map(Some)
+            }
+            .boxed();
+
+            for directive in &env.operation.node.directives {
+                if let Some(directive_factory) = self
+                    .0
+                    .env
+                    .custom_directives
+                    .get(directive.node.name.node.as_str())
                 {
-                    resolve_container_serial(&ctx, &EmptyMutation).await
-                } else {
-                    resolve_container_serial(&ctx, &self.0.mutation).await
+                    let ctx_directive = env.create_context(&self.0.env, None, directive);
+                    let directive_instance =
+                        directive_factory.create(&ctx_directive, &directive.node)?;
+                    let ctx = &ctx;
+                    resolve_fut = async move {
+                        directive_instance
+                            .resolve_operation(ctx, &mut resolve_fut)
+                            .await
+                    }
+                    .boxed();
                 }
             }
-            OperationType::Subscription => Err(ServerError::new(
-                "Subscriptions are not supported on this transport.",
-                None,
-            )),
-        };
+
+            resolve_fut.await.map(Option::unwrap_or_default)
+        }
+        .await;
 
         let mut resp = match res {
             Ok(value) => Response::new(value),
@@ -497,17 +631,20 @@ where
                 )
                 .await
                 {
-                    Ok((env, cache_control)) => {
-                        let fut = async {
-                            self.execute_once(env.clone())
+                    Ok((env, cache_control)) => match self.resolve_variables(env).await {
+                        Ok(env) => {
+                            let fut = async {
+                                self.execute_once(env.clone())
+                                    .await
+                                    .cache_control(cache_control)
+                            };
+                            futures_util::pin_mut!(fut);
+                            env.extensions
+                                .execute(env.operation_name.as_deref(), &mut fut)
                                 .await
-                                .cache_control(cache_control)
-                        };
-                        futures_util::pin_mut!(fut);
-                        env.extensions
-                            .execute(env.operation_name.as_deref(), &mut fut)
-                            .await
-                    }
+                        }
+                        Err(err) => Response::from_errors(vec![err]),
+                    },
                     Err(errors) => Response::from_errors(errors),
                 }
             }
@@ -553,6 +690,13 @@ where
                         return;
                     }
                 };
+                let env = match schema.resolve_variables(env).await {
+                    Ok(env) => env,
+                    Err(err) => {
+                        yield Response::from_errors(vec![err]);
+                        return;
+                    }
+                };
 
                 if env.operation.node.ty != OperationType::Subscription {
                     yield schema.execute_once(env).await.cache_control(cache_control);
@@ -816,11 +960,20 @@ pub(crate) async fn prepare_request(
 
     let (operation_name, mut operation) = operation.map_err(|err| vec![err])?;
 
-    // remove skipped fields
+    // remove skipped fields, the conditions use the default values of the
+    // variables that are not provided
+    let mut variables = request.variables.clone();
+    for def in &operation.node.variable_definitions {
+        if !variables.contains_key(&def.node.name.node) {
+            if let Some(value) = def.node.default_value() {
+                variables.insert(def.node.name.node.clone(), value.clone());
+            }
+        }
+    }
     for fragment in document.fragments.values_mut() {
-        remove_skipped_selection(&mut fragment.node.selection_set.node, &request.variables);
+        remove_skipped_selection(&mut fragment.node.selection_set.node, &variables);
     }
-    remove_skipped_selection(&mut operation.node.selection_set.node, &request.variables);
+    remove_skipped_selection(&mut operation.node.selection_set.node, &variables);
 
     let env = QueryEnvInner {
         extensions,
@@ -829,12 +982,17 @@ pub(crate) async fn prepare_request(
         operation,
         fragments: document.fragments,
         uploads: request.uploads,
+        upload_streams: request.upload_streams,
         session_data,
         ctx_data: query_data,
         extension_data: Arc::new(request.data),
         http_headers: Default::default(),
         introspection_mode: request.introspection_mode,
         errors: Default::default(),
+        last_event_id: match request.extensions.get("lastEventId") {
+            Some(Value::String(id)) => Some(id.clone()),
+            _ => None,
+        },
     };
     Ok((QueryEnv::new(env), validation_result.cache_control))
 }
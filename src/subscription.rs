@@ -1,10 +1,20 @@
-use std::{borrow::Cow, pin::Pin};
+use std::{
+    borrow::Cow,
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
 
-use futures_util::stream::{Stream, StreamExt};
+use futures_util::{
+    future,
+    stream::{self, Stream, StreamExt},
+};
+use pin_project_lite::pin_project;
 
 use crate::{
-    parser::types::Selection, registry, registry::Registry, Context, ContextSelectionSet,
-    PathSegment, Response, ServerError, ServerResult,
+    extensions::DroppedEventsInfo, parser::types::Selection, registry, registry::Registry, Context,
+    ContextSelectionSet, Name, OutputType, PathSegment, Pos, QueryEnv, Response, ServerError,
+    ServerResult,
 };
 
 /// A GraphQL subscription object
@@ -80,3 +90,161 @@ impl<T: SubscriptionType> SubscriptionType for &T {
         T::create_field_stream(*self, ctx)
     }
 }
+
+/// Buffering policy for the events of a subscription stream.
+///
+/// The policy is applied every time the subscription stream is polled: all the
+/// events that the source stream has ready are moved into the buffer, and the
+/// policy decides which of them are kept when the client cannot keep up.
+///
+/// Dropped events are reported to
+/// [`Extension::subscription_events_dropped`](crate::extensions::Extension::subscription_events_dropped).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SubscriptionBufferPolicy {
+    /// Forward every event of the source stream, this is the default.
+    Unbounded,
+    /// Buffer at most `n` events, the oldest event is dropped when the buffer
+    /// is full.
+    DropOldest(usize),
+    /// Only keep the latest event, older events that have not been sent yet
+    /// are dropped.
+    CoalesceLatest,
+    /// Buffer at most `n` events, the subscription is ended with an error when
+    /// the buffer overflows.
+    DisconnectOnOverflow(usize),
+}
+
+impl Default for SubscriptionBufferPolicy {
+    fn default() -> Self {
+        SubscriptionBufferPolicy::Unbounded
+    }
+}
+
+/// The maximum number of events taken from the source stream in one poll.
+const DRAIN_BUDGET: usize = 128;
+
+pin_project! {
+    /// A stream that applies a [`SubscriptionBufferPolicy`] to the events of a
+    /// subscription field.
+    #[doc(hidden)]
+    pub struct SubscriptionBuffer<S: Stream> {
+        #[pin]
+        stream: S,
+        policy: SubscriptionBufferPolicy,
+        buffer: VecDeque<S::Item>,
+        finished: bool,
+        overflowed: bool,
+        query_env: QueryEnv,
+        field_name: Name,
+        pos: Pos,
+    }
+}
+
+impl<S: Stream> SubscriptionBuffer<S> {
+    #[doc(hidden)]
+    pub fn new(stream: S, policy: SubscriptionBufferPolicy, ctx: &Context<'_>) -> Self {
+        Self {
+            stream,
+            policy,
+            buffer: VecDeque::new(),
+            finished: false,
+            overflowed: false,
+            query_env: ctx.query_env.clone(),
+            field_name: ctx.item.node.response_key().node.clone(),
+            pos: ctx.item.pos,
+        }
+    }
+}
+
+impl<S: Stream> Stream for SubscriptionBuffer<S> {
+    type Item = ServerResult<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.policy == SubscriptionBufferPolicy::Unbounded {
+            return this.stream.poll_next(cx).map(|item| item.map(Ok));
+        }
+        if *this.overflowed {
+            return Poll::Ready(None);
+        }
+
+        let mut dropped = 0;
+        let mut budget = DRAIN_BUDGET;
+        while !*this.finished && budget > 0 {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    budget -= 1;
+                    match *this.policy {
+                        SubscriptionBufferPolicy::Unbounded => {}
+                        SubscriptionBufferPolicy::DropOldest(size) => {
+                            if this.buffer.len() >= size.max(1) {
+                                this.buffer.pop_front();
+                                dropped += 1;
+                            }
+                        }
+                        SubscriptionBufferPolicy::CoalesceLatest => {
+                            dropped += this.buffer.len();
+                            this.buffer.clear();
+                        }
+                        SubscriptionBufferPolicy::DisconnectOnOverflow(size) => {
+                            if this.buffer.len() >= size.max(1) {
+                                dropped += this.buffer.len() + 1;
+                                this.buffer.clear();
+                                *this.overflowed = true;
+                                break;
+                            }
+                        }
+                    }
+                    this.buffer.push_back(item);
+                }
+                Poll::Ready(None) => *this.finished = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if dropped > 0 {
+            this.query_env
+                .extensions
+                .subscription_events_dropped(DroppedEventsInfo {
+                    field_name: this.field_name,
+                    policy: *this.policy,
+                    count: dropped,
+                });
+        }
+
+        if *this.overflowed {
+            return Poll::Ready(Some(Err(ServerError::new(
+                "The subscription buffer overflowed.",
+                Some(*this.pos),
+            )
+            .with_path(vec![PathSegment::Field(this.field_name.to_string())]))));
+        }
+
+        match this.buffer.pop_front() {
+            Some(item) => Poll::Ready(Some(Ok(item))),
+            None if *this.finished => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Sends the replayed events before the events of the subscription stream,
+/// the events of the stream that have already been replayed are skipped.
+#[doc(hidden)]
+pub fn replay_events<S>(replayed: Vec<S::Item>, stream: S) -> impl Stream<Item = S::Item>
+where
+    S: Stream,
+    S::Item: OutputType,
+{
+    let replayed_ids = replayed
+        .iter()
+        .filter_map(|item| item.event_id().map(ToString::to_string))
+        .collect::<HashSet<_>>();
+    stream::iter(replayed).chain(stream.filter(move |item| {
+        future::ready(match item.event_id() {
+            Some(id) => !replayed_ids.contains(id),
+            None => true,
+        })
+    }))
+}
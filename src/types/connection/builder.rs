@@ -0,0 +1,212 @@
+use std::future::Future;
+
+use crate::{
+    connection::{
+        Connection, ConnectionNameType, ContextCursorType, Edge, EdgeNameType, EmptyFields,
+    },
+    OutputType,
+};
+
+/// The page requested by [`Connection::fetch`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PageRequest<Cursor> {
+    /// Fetch the items after this cursor.
+    pub after: Option<Cursor>,
+
+    /// Fetch the items before this cursor.
+    pub before: Option<Cursor>,
+
+    /// The maximum number of items to fetch, one more than the size of the
+    /// page to know whether there are more items. `None` means all the
+    /// items.
+    pub limit: Option<usize>,
+
+    /// If `true` (`last` is specified), fetch the items from the end, in
+    /// reverse order.
+    pub backward: bool,
+}
+
+impl<Node, Name, EdgeName> Connection<usize, Node, EmptyFields, EmptyFields, Name, EdgeName>
+where
+    Node: OutputType,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
+{
+    /// Create a connection from all the items, the cursors are the offsets of
+    /// the items.
+    ///
+    /// The parameters are the ones passed to the closure of
+    /// [`query`](super::query).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::{connection::*, *};
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn numbers(
+    ///         &self,
+    ///         after: Option<String>,
+    ///         before: Option<String>,
+    ///         first: Option<i32>,
+    ///         last: Option<i32>,
+    ///     ) -> Result<Connection<usize, i32>> {
+    ///         let numbers = (0..100).collect::<Vec<i32>>();
+    ///         query(after, before, first, last, |after, before, first, last| async move {
+    ///             Ok::<_, Error>(Connection::from_items(numbers, after, before, first, last))
+    ///         })
+    ///         .await
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let query = r#"{ numbers(after: "1", first: 2) { nodes pageInfo { hasPreviousPage hasNextPage } } }"#;
+    /// assert_eq!(schema.execute(query).await.into_result().unwrap().data, value!({
+    ///     "numbers": {
+    ///         "nodes": [2, 3],
+    ///         "pageInfo": { "hasPreviousPage": true, "hasNextPage": true },
+    ///     },
+    /// }));
+    /// # });
+    /// ```
+    pub fn from_items<I>(
+        items: I,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Node>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        let len = items.len();
+        let mut start = after.map(|after| after.saturating_add(1)).unwrap_or(0);
+        start = start.min(len);
+        let mut end = before.unwrap_or(len).min(len).max(start);
+        if let Some(first) = first {
+            end = end.min(start.saturating_add(first));
+        }
+        if let Some(last) = last {
+            start = start.max(end.saturating_sub(last));
+        }
+
+        let mut connection = Connection::new(start > 0, end < len);
+        connection.edges.extend(
+            items
+                .enumerate()
+                .skip(start)
+                .take(end - start)
+                .map(|(idx, node)| Edge::new(idx, node)),
+        );
+        connection
+    }
+}
+
+impl<Cursor, Node, Name, EdgeName>
+    Connection<Cursor, Node, EmptyFields, EmptyFields, Name, EdgeName>
+where
+    Cursor: ContextCursorType + Send + Sync,
+    Node: OutputType,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
+{
+    /// Create a connection from the items fetched by `fetch`, `cursor`
+    /// returns the cursor of an item.
+    ///
+    /// `fetch` should return at most [`PageRequest::limit`] items, which is
+    /// one more than the size of the page, so that the connection knows
+    /// whether there is a next page (or a previous page with `last`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::{connection::*, *};
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn numbers(
+    ///         &self,
+    ///         after: Option<String>,
+    ///         before: Option<String>,
+    ///         first: Option<i32>,
+    ///         last: Option<i32>,
+    ///     ) -> Result<Connection<i32, i32>> {
+    ///         query(after, before, first, last, |after, before, first, last| {
+    ///             Connection::fetch(after, before, first, last, |n| *n, |page| async move {
+    ///                 // SELECT n FROM numbers WHERE n > $after AND n < $before
+    ///                 // ORDER BY n [DESC if page.backward] LIMIT $limit
+    ///                 let numbers = (page.after.map(|n| n + 1).unwrap_or(0)..page.before.unwrap_or(100));
+    ///                 let limit = page.limit.unwrap_or(usize::MAX);
+    ///                 Ok::<_, Error>(if page.backward {
+    ///                     numbers.rev().take(limit).collect::<Vec<_>>()
+    ///                 } else {
+    ///                     numbers.take(limit).collect()
+    ///                 })
+    ///             })
+    ///         })
+    ///         .await
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    /// let query = r#"{ numbers(before: "10", last: 2) { nodes pageInfo { hasPreviousPage hasNextPage } } }"#;
+    /// assert_eq!(schema.execute(query).await.into_result().unwrap().data, value!({
+    ///     "numbers": {
+    ///         "nodes": [8, 9],
+    ///         "pageInfo": { "hasPreviousPage": true, "hasNextPage": true },
+    ///     },
+    /// }));
+    /// # });
+    /// ```
+    pub async fn fetch<C, F, R, E>(
+        after: Option<Cursor>,
+        before: Option<Cursor>,
+        first: Option<usize>,
+        last: Option<usize>,
+        cursor: C,
+        fetch: F,
+    ) -> Result<Self, E>
+    where
+        C: Fn(&Node) -> Cursor,
+        F: FnOnce(PageRequest<Cursor>) -> R,
+        R: Future<Output = Result<Vec<Node>, E>>,
+    {
+        let backward = first.is_none() && last.is_some();
+        let page_size = if backward { last } else { first };
+        let has_after = after.is_some();
+        let has_before = before.is_some();
+
+        let mut nodes = fetch(PageRequest {
+            after,
+            before,
+            limit: page_size.map(|size| size.saturating_add(1)),
+            backward,
+        })
+        .await?;
+        let has_more = matches!(page_size, Some(size) if nodes.len() > size);
+        if let Some(size) = page_size {
+            nodes.truncate(size);
+        }
+
+        let (has_previous_page, has_next_page) = if backward {
+            nodes.reverse();
+            (has_more, has_before)
+        } else {
+            (has_after, has_more)
+        };
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection
+            .edges
+            .extend(nodes.into_iter().map(|node| Edge::new(cursor(&node), node)));
+        Ok(connection)
+    }
+}
@@ -3,10 +3,10 @@ use std::{borrow::Cow, marker::PhantomData};
 use crate::{
     connection::{
         edge::Edge, ConnectionNameType, DefaultConnectionName, DefaultEdgeName, EdgeNameType,
-        PageInfo,
+        PageInfo, TotalCount,
     },
-    types::connection::{CursorType, EmptyFields},
-    Object, ObjectType, OutputType, TypeName,
+    types::connection::{ContextCursorType, EmptyFields},
+    Context, Error, Object, ObjectType, OutputType, Result, TypeName,
 };
 
 /// Connection type
@@ -20,7 +20,7 @@ pub struct Connection<
     Name = DefaultConnectionName,
     EdgeName = DefaultEdgeName,
 > where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     ConnectionFields: ObjectType,
     EdgeFields: ObjectType,
@@ -42,7 +42,7 @@ pub struct Connection<
 impl<Cursor, Node, EdgeFields, Name, EdgeName>
     Connection<Cursor, Node, EmptyFields, EdgeFields, Name, EdgeName>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     EdgeFields: ObjectType,
     Name: ConnectionNameType,
@@ -65,7 +65,7 @@ where
 impl<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>
     Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     ConnectionFields: ObjectType,
     EdgeFields: ObjectType,
@@ -90,11 +90,37 @@ where
     }
 }
 
+impl<Cursor, Node, EdgeFields, Name, EdgeName>
+    Connection<Cursor, Node, EmptyFields, EdgeFields, Name, EdgeName>
+where
+    Cursor: ContextCursorType + Send + Sync,
+    Node: OutputType,
+    EdgeFields: ObjectType,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
+{
+    /// Adds a `totalCount` field to the connection, which is computed only
+    /// when it is selected.
+    pub fn with_total_count(
+        self,
+        total_count: TotalCount,
+    ) -> Connection<Cursor, Node, TotalCount, EdgeFields, Name, EdgeName> {
+        Connection {
+            _mark1: PhantomData,
+            _mark2: PhantomData,
+            edges: self.edges,
+            additional_fields: total_count,
+            has_previous_page: self.has_previous_page,
+            has_next_page: self.has_next_page,
+        }
+    }
+}
+
 #[Object(internal, name_type)]
 impl<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>
     Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     ConnectionFields: ObjectType,
     EdgeFields: ObjectType,
@@ -102,13 +128,19 @@ where
     EdgeName: EdgeNameType,
 {
     /// Information to aid in pagination.
-    async fn page_info(&self) -> PageInfo {
-        PageInfo {
+    async fn page_info(&self, ctx: &Context<'_>) -> Result<PageInfo> {
+        let encode = |edge: &Edge<Cursor, Node, EdgeFields, EdgeName>| {
+            edge.cursor
+                .0
+                .encode_cursor_with_context(ctx)
+                .map_err(|err| Error::new(err.to_string()))
+        };
+        Ok(PageInfo {
             has_previous_page: self.has_previous_page,
             has_next_page: self.has_next_page,
-            start_cursor: self.edges.first().map(|edge| edge.cursor.0.encode_cursor()),
-            end_cursor: self.edges.last().map(|edge| edge.cursor.0.encode_cursor()),
-        }
+            start_cursor: self.edges.first().map(encode).transpose()?,
+            end_cursor: self.edges.last().map(encode).transpose()?,
+        })
     }
 
     /// A list of edges.
@@ -132,7 +164,7 @@ where
 impl<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName> TypeName
     for Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     ConnectionFields: ObjectType,
     EdgeFields: ObjectType,
@@ -9,7 +9,7 @@ use std::{
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::ID;
+use crate::{Context, ID};
 
 /// Cursor type
 ///
@@ -26,6 +26,38 @@ pub trait CursorType: Sized {
     fn encode_cursor(&self) -> String;
 }
 
+/// Cursor type that is encoded and decoded with the context of the field.
+///
+/// It is implemented for every [`CursorType`], implement it directly for the
+/// cursors that need data from the context, such as a key. The cursors of a
+/// [`Connection`](super::Connection) must implement this trait, use
+/// [`query_with_context`](super::query_with_context) to decode them.
+pub trait ContextCursorType: Sized {
+    /// Error type for `decode_cursor_with_context`.
+    type Error: Display;
+
+    /// Decode cursor from string with the context of the connection field.
+    fn decode_cursor_with_context(ctx: &Context<'_>, s: &str) -> Result<Self, Self::Error>;
+
+    /// Encode cursor to string with the context of the field that returns
+    /// it.
+    fn encode_cursor_with_context(&self, ctx: &Context<'_>) -> Result<String, Self::Error>;
+}
+
+impl<T: CursorType> ContextCursorType for T {
+    type Error = T::Error;
+
+    #[inline]
+    fn decode_cursor_with_context(_ctx: &Context<'_>, s: &str) -> Result<Self, Self::Error> {
+        Self::decode_cursor(s)
+    }
+
+    #[inline]
+    fn encode_cursor_with_context(&self, _ctx: &Context<'_>) -> Result<String, Self::Error> {
+        Ok(self.encode_cursor())
+    }
+}
+
 macro_rules! cursor_type_int_impl {
     ($($t:ty)*) => {$(
         impl CursorType for $t {
@@ -2,30 +2,35 @@ use std::{borrow::Cow, marker::PhantomData};
 
 use crate::{
     connection::{DefaultEdgeName, EmptyFields},
-    types::connection::{CursorType, EdgeNameType},
-    InputValueError, InputValueResult, ObjectType, OutputType, Scalar, ScalarType, SimpleObject,
-    TypeName, Value,
+    parser::types::Field,
+    registry,
+    types::connection::{ContextCursorType, EdgeNameType},
+    ContextSelectionSet, ObjectType, OutputType, Positioned, ServerError, ServerResult,
+    SimpleObject, TypeName, Value,
 };
 
-pub(crate) struct CursorScalar<T: CursorType>(pub(crate) T);
+pub(crate) struct CursorScalar<T: ContextCursorType>(pub(crate) T);
 
-#[Scalar(internal, name = "String")]
-impl<T: CursorType + Send + Sync> ScalarType for CursorScalar<T> {
-    fn parse(value: Value) -> InputValueResult<Self> {
-        match value {
-            Value::String(s) => T::decode_cursor(&s)
-                .map(Self)
-                .map_err(InputValueError::custom),
-            _ => Err(InputValueError::expected_type(value)),
-        }
+#[async_trait::async_trait]
+impl<T: ContextCursorType + Send + Sync> OutputType for CursorScalar<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("String")
     }
 
-    fn is_valid(value: &Value) -> bool {
-        matches!(value, Value::String(_))
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        <String as OutputType>::create_type_info(registry)
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.0.encode_cursor())
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        let ctx = ctx.with_field(field);
+        self.0
+            .encode_cursor_with_context(&ctx)
+            .map(Value::String)
+            .map_err(|err| ServerError::new(err.to_string(), Some(field.pos)))
     }
 }
 
@@ -34,7 +39,7 @@ impl<T: CursorType + Send + Sync> ScalarType for CursorScalar<T> {
 #[graphql(internal, name_type)]
 pub struct Edge<Cursor, Node, EdgeFields, Name = DefaultEdgeName>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     EdgeFields: ObjectType,
     Name: EdgeNameType,
@@ -51,7 +56,7 @@ where
 
 impl<Cursor, Node, EdgeFields, Name> TypeName for Edge<Cursor, Node, EdgeFields, Name>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     EdgeFields: ObjectType,
     Name: EdgeNameType,
@@ -65,7 +70,7 @@ where
 impl<Cursor, Node, EdgeFields, Name> Edge<Cursor, Node, EdgeFields, Name>
 where
     Name: EdgeNameType,
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     EdgeFields: ObjectType,
 {
@@ -87,7 +92,7 @@ where
 
 impl<Cursor, Node, Name> Edge<Cursor, Node, EmptyFields, Name>
 where
-    Cursor: CursorType + Send + Sync,
+    Cursor: ContextCursorType + Send + Sync,
     Node: OutputType,
     Name: EdgeNameType,
 {
@@ -1,18 +1,27 @@
 //! Types for Relay-compliant server
 
+mod builder;
 mod connection_type;
 mod cursor;
 mod edge;
 mod page_info;
+#[cfg(feature = "signed_cursor")]
+mod signed_cursor;
+mod total_count;
 
 use std::{fmt::Display, future::Future};
 
+pub use builder::PageRequest;
 pub use connection_type::Connection;
-pub use cursor::{CursorType, OpaqueCursor};
+pub use cursor::{ContextCursorType, CursorType, OpaqueCursor};
 pub use edge::Edge;
 pub use page_info::PageInfo;
+#[cfg(feature = "signed_cursor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signed_cursor")))]
+pub use signed_cursor::{CursorError, CursorKey, EncryptedCursor, SignedCursor};
+pub use total_count::TotalCount;
 
-use crate::{Error, ObjectType, OutputType, Result, SimpleObject};
+use crate::{Context, Error, ObjectType, OutputType, Result, SimpleObject};
 
 /// Empty additional fields
 #[derive(SimpleObject)]
@@ -315,6 +324,56 @@ where
     F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
     R: Future<Output = Result<T, E>>,
     E: Into<Error>,
+{
+    parse_and_query(after, before, first, last, Cursor::decode_cursor, f).await
+}
+
+/// Parses the parameters and executes the query like [`query_with`], but
+/// decodes the cursors with
+/// [`ContextCursorType::decode_cursor_with_context`].
+///
+/// Use it for the cursors that need data from the context, such as
+/// `SignedCursor` and `EncryptedCursor`.
+pub async fn query_with_context<Cursor, T, F, R, E>(
+    ctx: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    f: F,
+) -> Result<T>
+where
+    Cursor: ContextCursorType + Send + Sync,
+    <Cursor as ContextCursorType>::Error: Display + Send + Sync + 'static,
+    F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
+    R: Future<Output = Result<T, E>>,
+    E: Into<Error>,
+{
+    parse_and_query(
+        after,
+        before,
+        first,
+        last,
+        |s| Cursor::decode_cursor_with_context(ctx, s),
+        f,
+    )
+    .await
+}
+
+async fn parse_and_query<Cursor, T, D, DE, F, R, E>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    decode: D,
+    f: F,
+) -> Result<T>
+where
+    D: Fn(&str) -> Result<Cursor, DE>,
+    DE: Display + Send + Sync + 'static,
+    F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
+    R: Future<Output = Result<T, E>>,
+    E: Into<Error>,
 {
     if first.is_some() && last.is_some() {
         return Err("The \"first\" and \"last\" parameters cannot exist at the same time".into());
@@ -337,12 +396,12 @@ where
     };
 
     let before = match before {
-        Some(before) => Some(Cursor::decode_cursor(&before)?),
+        Some(before) => Some(decode(&before)?),
         None => None,
     };
 
     let after = match after {
-        Some(after) => Some(Cursor::decode_cursor(&after)?),
+        Some(after) => Some(decode(&after)?),
         None => None,
     };
 
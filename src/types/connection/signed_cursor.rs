@@ -0,0 +1,326 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::{Deref, DerefMut},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aes_gcm_siv::{
+    aead::{Aead, KeyInit},
+    Aes256GcmSiv, Nonce,
+};
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{connection::ContextCursorType, Context};
+
+const NONCE_SIZE: usize = 12;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Errors of [`SignedCursor`] and [`EncryptedCursor`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum CursorError {
+    /// The [`CursorKey`] is not in the schema data.
+    #[error("The cursor key is not in the schema data")]
+    MissingKey,
+
+    /// The cursor is malformed.
+    #[error("Invalid cursor")]
+    Invalid,
+
+    /// The signature of the cursor does not match its content.
+    #[error("The cursor has been tampered with")]
+    Tampered,
+
+    /// The cursor has expired.
+    #[error("The cursor has expired")]
+    Expired,
+}
+
+/// The key used to sign [`SignedCursor`] and encrypt [`EncryptedCursor`].
+///
+/// Put it in the schema data with
+/// [`SchemaBuilder::data`](crate::SchemaBuilder::data). The cursors are signed
+/// with HMAC-SHA256 and encrypted with AES-256-GCM-SIV, the keys of both
+/// cursor types are derived from the secret.
+#[derive(Clone)]
+pub struct CursorKey {
+    mac_key: [u8; 32],
+    cipher: Aes256GcmSiv,
+    ttl: Option<Duration>,
+}
+
+impl Debug for CursorKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorKey")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Serialize)]
+struct PayloadRef<'a, T> {
+    v: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Payload<T> {
+    v: T,
+    exp: Option<u64>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn mac(key: &[u8], parts: &[&[u8]]) -> HmacSha256 {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac
+}
+
+fn derive_key(secret: &[u8], info: &[u8]) -> [u8; 32] {
+    mac(secret, &[info]).finalize().into_bytes().into()
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, CursorError> {
+    base64::decode_config(s, base64::URL_SAFE_NO_PAD).map_err(|_| CursorError::Invalid)
+}
+
+fn encode_base64(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+impl CursorKey {
+    /// Create a cursor key from a secret.
+    ///
+    /// The secret should be random and at least 32 bytes long.
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        let secret = secret.as_ref();
+        let enc_key = derive_key(secret, b"async-graphql cursor encryption");
+        Self {
+            mac_key: derive_key(secret, b"async-graphql cursor signing"),
+            cipher: Aes256GcmSiv::new(&enc_key.into()),
+            ttl: None,
+        }
+    }
+
+    /// Cursors expire after the specified duration, by default they never
+    /// expire.
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+
+    fn payload<T: Serialize>(&self, value: &T) -> Vec<u8> {
+        let payload = PayloadRef {
+            v: value,
+            exp: self
+                .ttl
+                .map(|ttl| now_millis().saturating_add(ttl.as_millis() as u64)),
+        };
+        serde_json::to_vec(&payload).unwrap_or_default()
+    }
+
+    fn parse_payload<T: DeserializeOwned>(data: &[u8]) -> Result<T, CursorError> {
+        let payload: Payload<T> = serde_json::from_slice(data).map_err(|_| CursorError::Invalid)?;
+        match payload.exp {
+            Some(exp) if now_millis() > exp => Err(CursorError::Expired),
+            _ => Ok(payload.v),
+        }
+    }
+
+    /// Serializes the value to a cursor that can be read by the clients but
+    /// not modified.
+    pub fn sign<T: Serialize>(&self, value: &T) -> String {
+        let payload = self.payload(value);
+        let signature = mac(&self.mac_key, &[b"s", &payload])
+            .finalize()
+            .into_bytes();
+        format!("{}.{}", encode_base64(&payload), encode_base64(&signature))
+    }
+
+    /// Verifies a cursor created by [`CursorKey::sign`] and deserializes its
+    /// value.
+    pub fn verify<T: DeserializeOwned>(&self, cursor: &str) -> Result<T, CursorError> {
+        let (payload, signature) = cursor.split_once('.').ok_or(CursorError::Invalid)?;
+        let payload = decode_base64(payload)?;
+        let signature = decode_base64(signature)?;
+        mac(&self.mac_key, &[b"s", &payload])
+            .verify_slice(&signature)
+            .map_err(|_| CursorError::Tampered)?;
+        Self::parse_payload(&payload)
+    }
+
+    /// Serializes and encrypts the value to a cursor that can neither be read
+    /// nor modified by the clients.
+    ///
+    /// The encryption is deterministic, the same payload always gives the same
+    /// cursor. The payload includes the expiry when a time to live is set, so
+    /// the cursors of the same value differ over time.
+    pub fn encrypt<T: Serialize>(&self, value: &T) -> String {
+        let payload = self.payload(value);
+        // The nonce is derived from the payload, AES-GCM-SIV only reveals
+        // whether two cursors have the same payload.
+        let nonce = mac(&self.mac_key, &[b"e", &payload])
+            .finalize()
+            .into_bytes();
+        let nonce = Nonce::from_slice(&nonce[..NONCE_SIZE]);
+        let mut cursor = nonce.to_vec();
+        cursor.extend(
+            self.cipher
+                .encrypt(nonce, payload.as_slice())
+                .unwrap_or_default(),
+        );
+        encode_base64(&cursor)
+    }
+
+    /// Decrypts a cursor created by [`CursorKey::encrypt`] and deserializes
+    /// its value.
+    pub fn decrypt<T: DeserializeOwned>(&self, cursor: &str) -> Result<T, CursorError> {
+        let data = decode_base64(cursor)?;
+        if data.len() < NONCE_SIZE {
+            return Err(CursorError::Invalid);
+        }
+        let (nonce, data) = data.split_at(NONCE_SIZE);
+        let payload = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), data)
+            .map_err(|_| CursorError::Tampered)?;
+        Self::parse_payload(&payload)
+    }
+}
+
+fn cursor_key<'a>(ctx: &'a Context<'_>) -> Result<&'a CursorKey, CursorError> {
+    ctx.data_opt::<CursorKey>().ok_or(CursorError::MissingKey)
+}
+
+macro_rules! keyed_cursor {
+    ($(#[$meta:meta])* $name:ident, $encode:ident, $decode:ident) => {
+        $(#[$meta])*
+        pub struct $name<T>(pub T);
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $name<T> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<T> ContextCursorType for $name<T>
+        where
+            T: Serialize + DeserializeOwned,
+        {
+            type Error = CursorError;
+
+            fn decode_cursor_with_context(
+                ctx: &Context<'_>,
+                s: &str,
+            ) -> Result<Self, Self::Error> {
+                cursor_key(ctx)?.$decode(s).map(Self)
+            }
+
+            fn encode_cursor_with_context(&self, ctx: &Context<'_>) -> Result<String, Self::Error> {
+                Ok(cursor_key(ctx)?.$encode(&self.0))
+            }
+        }
+    };
+}
+
+keyed_cursor!(
+    /// A cursor signed with the [`CursorKey`] of the schema data, the clients
+    /// can read the value but cannot forge it.
+    ///
+    /// Use it with [`query_with_context`](super::query_with_context).
+    SignedCursor,
+    sign,
+    verify
+);
+
+keyed_cursor!(
+    /// A cursor encrypted with the [`CursorKey`] of the schema data, the
+    /// clients can neither read nor forge the value.
+    ///
+    /// Use it with [`query_with_context`](super::query_with_context).
+    EncryptedCursor,
+    encrypt,
+    decrypt
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed() {
+        let key = CursorKey::new("secret");
+        let cursor = key.sign(&(1, "a"));
+        assert_eq!(
+            key.verify::<(i32, String)>(&cursor),
+            Ok((1, "a".to_string()))
+        );
+
+        let (payload, signature) = cursor.split_once('.').unwrap();
+        let forged = format!("{}.{}", encode_base64(b"{\"v\":[2,\"a\"]}"), signature);
+        assert_eq!(
+            key.verify::<(i32, String)>(&forged),
+            Err(CursorError::Tampered)
+        );
+        assert_eq!(
+            CursorKey::new("other").verify::<(i32, String)>(&cursor),
+            Err(CursorError::Tampered)
+        );
+        assert_eq!(
+            key.verify::<(i32, String)>(payload),
+            Err(CursorError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_encrypted() {
+        let key = CursorKey::new("secret");
+        let value = "a value longer than a single block of the cipher".to_string();
+        let cursor = key.encrypt(&value);
+        assert!(!String::from_utf8_lossy(&decode_base64(&cursor).unwrap()).contains("value"));
+        assert_eq!(key.decrypt::<String>(&cursor), Ok(value.clone()));
+        assert_eq!(key.encrypt(&value), cursor);
+
+        let mut data = decode_base64(&cursor).unwrap();
+        data[NONCE_SIZE] ^= 1;
+        assert_eq!(
+            key.decrypt::<String>(&encode_base64(&data)),
+            Err(CursorError::Tampered)
+        );
+        assert_eq!(key.decrypt::<String>("AAAA"), Err(CursorError::Invalid));
+    }
+
+    #[test]
+    fn test_expired() {
+        let key = CursorKey::new("secret").ttl(Duration::from_millis(1));
+        let signed = key.sign(&1);
+        let encrypted = key.encrypt(&1);
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(key.verify::<i32>(&signed), Err(CursorError::Expired));
+        assert_eq!(key.decrypt::<i32>(&encrypted), Err(CursorError::Expired));
+    }
+}
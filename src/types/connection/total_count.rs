@@ -0,0 +1,41 @@
+use std::future::Future;
+
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+
+use crate::{Error, Object, Result};
+
+/// A `totalCount` field of a connection, computed only when it is selected.
+///
+/// Use it as the additional fields of a [`Connection`](super::Connection),
+/// with [`Connection::with_total_count`](super::Connection::with_total_count).
+pub struct TotalCount(Shared<BoxFuture<'static, Result<usize>>>);
+
+impl TotalCount {
+    /// Create a total count computed by `f` the first time the field is
+    /// resolved.
+    pub fn new<F, R, E>(f: F) -> Self
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Future<Output = Result<usize, E>> + Send + 'static,
+        E: Into<Error>,
+    {
+        Self(
+            async move { f().await.map_err(Into::into) }
+                .boxed()
+                .shared(),
+        )
+    }
+
+    /// Create a total count that is already known.
+    pub fn value(count: usize) -> Self {
+        Self(futures_util::future::ready(Ok(count)).boxed().shared())
+    }
+}
+
+#[Object(internal)]
+impl TotalCount {
+    /// The total number of items in the connection.
+    async fn total_count(&self) -> Result<usize> {
+        self.0.clone().await
+    }
+}
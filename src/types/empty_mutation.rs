@@ -56,11 +56,16 @@ impl OutputType for EmptyMutation {
             extends: false,
             shareable: false,
             keys: None,
+            resolvable: true,
+            interface_object: false,
+            authenticated: false,
+            requires_scopes: Default::default(),
             visible: None,
             inaccessible: false,
             tags: Default::default(),
             is_subscription: false,
             rust_typename: Some(std::any::type_name::<Self>()),
+            directive_invocations: Vec::new(),
         })
     }
 
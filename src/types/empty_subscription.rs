@@ -25,11 +25,16 @@ impl SubscriptionType for EmptySubscription {
             extends: false,
             shareable: false,
             keys: None,
+            resolvable: true,
+            interface_object: false,
+            authenticated: false,
+            requires_scopes: Default::default(),
             visible: None,
             inaccessible: false,
             tags: Default::default(),
             is_subscription: true,
             rust_typename: Some(std::any::type_name::<Self>()),
+            directive_invocations: Vec::new(),
         })
     }
 
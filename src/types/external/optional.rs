@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{any::Any, borrow::Cow};
 
 use crate::{
     parser::types::Field, registry, ContextSelectionSet, InputType, InputValueError,
@@ -77,6 +77,14 @@ impl<T: OutputType + Sync> OutputType for Option<T> {
             Ok(Value::Null)
         }
     }
+
+    fn event_id(&self) -> Option<&str> {
+        self.as_ref().and_then(OutputType::event_id)
+    }
+
+    fn batch_parent(&self) -> Option<&(dyn Any + Send + Sync)> {
+        self.as_ref().and_then(OutputType::batch_parent)
+    }
 }
 
 #[cfg(test)]
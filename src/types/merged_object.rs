@@ -34,6 +34,18 @@ where
             Err(err) => Err(err),
         }
     }
+
+    async fn find_entities(
+        &self,
+        ctx: &Context<'_>,
+        type_name: &str,
+        representations: &[(usize, &Value)],
+    ) -> Option<Vec<ServerResult<Option<Value>>>> {
+        match self.0.find_entities(ctx, type_name, representations).await {
+            Some(values) => Some(values),
+            None => self.1.find_entities(ctx, type_name, representations).await,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -79,11 +91,16 @@ where
                 extends: false,
                 shareable: false,
                 keys: None,
+                resolvable: true,
+                interface_object: false,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: Default::default(),
                 is_subscription: false,
                 rust_typename: Some(std::any::type_name::<Self>()),
+                directive_invocations: Vec::new(),
             }
         })
     }
@@ -140,11 +157,16 @@ where
                 extends: false,
                 shareable: false,
                 keys: None,
+                resolvable: true,
+                interface_object: false,
+                authenticated: false,
+                requires_scopes: Default::default(),
                 visible: None,
                 inaccessible: false,
                 tags: Default::default(),
                 is_subscription: false,
                 rust_typename: Some(std::any::type_name::<Self>()),
+                directive_invocations: Vec::new(),
             }
         })
     }
@@ -176,11 +198,16 @@ impl SubscriptionType for MergedObjectTail {
             extends: false,
             shareable: false,
             keys: None,
+            resolvable: true,
+            interface_object: false,
+            authenticated: false,
+            requires_scopes: Default::default(),
             visible: None,
             inaccessible: false,
             tags: Default::default(),
             is_subscription: false,
             rust_typename: Some(std::any::type_name::<Self>()),
+            directive_invocations: Vec::new(),
         })
     }
 
@@ -1,6 +1,8 @@
 //! Useful GraphQL types.
 
 pub mod connection;
+pub mod pagination;
+pub mod relay;
 
 mod any;
 mod empty_mutation;
@@ -12,6 +14,7 @@ mod merged_object;
 mod query_root;
 #[cfg(feature = "string_number")]
 mod string_number;
+mod subscription_event;
 mod upload;
 
 mod external;
@@ -26,4 +29,6 @@ pub use merged_object::{MergedObject, MergedObjectTail};
 pub(crate) use query_root::QueryRoot;
 #[cfg(feature = "string_number")]
 pub use string_number::StringNumber;
-pub use upload::{Upload, UploadValue};
+pub use subscription_event::SubscriptionEvent;
+pub use upload::{Upload, UploadStream, UploadValue};
+pub(crate) use upload::{UploadStreams, UPLOAD_PREFIX, UPLOAD_STREAM_PREFIX};
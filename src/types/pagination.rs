@@ -0,0 +1,264 @@
+//! Types for offset-based pagination
+
+use std::{borrow::Cow, future::Future, marker::PhantomData};
+
+use crate::{Error, Object, OutputType, Result, SimpleObject, TypeName};
+
+/// The page size used when the `pageSize` argument is not specified.
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// The maximum page size accepted by [`query`].
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Used to specify the page name.
+pub trait PageNameType: Send + Sync {
+    /// Returns the page type name.
+    fn type_name<T: OutputType>() -> String;
+}
+
+/// Name the page type by default with the default format.
+pub struct DefaultPageName;
+
+impl PageNameType for DefaultPageName {
+    fn type_name<T: OutputType>() -> String {
+        format!("{}Page", T::type_name())
+    }
+}
+
+/// The validated `page` and `pageSize` arguments of a query.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Pagination {
+    /// The page number, starting from 1.
+    pub page: usize,
+
+    /// The number of items per page.
+    pub page_size: usize,
+}
+
+impl Pagination {
+    /// Returns the number of items before the current page.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        (self.page - 1).saturating_mul(self.page_size)
+    }
+
+    /// Returns the maximum number of items in the current page.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.page_size
+    }
+}
+
+/// Information about pagination in a page
+#[derive(SimpleObject)]
+#[graphql(internal, name = "OffsetPageInfo")]
+pub struct PageInfo {
+    /// The current page number, starting from 1.
+    pub page: usize,
+
+    /// The number of items per page.
+    pub page_size: usize,
+
+    /// The total number of pages.
+    pub total_pages: usize,
+
+    /// Are there pages before the current page?
+    pub has_previous_page: bool,
+
+    /// Are there pages after the current page?
+    pub has_next_page: bool,
+}
+
+/// Page type
+///
+/// Page is the result of a query for `pagination::query`.
+pub struct Page<T, Name = DefaultPageName>
+where
+    T: OutputType,
+    Name: PageNameType,
+{
+    _mark: PhantomData<Name>,
+    /// The items of the current page.
+    pub items: Vec<T>,
+    /// The total number of items.
+    pub total_count: usize,
+    /// The arguments of the current page.
+    pub pagination: Pagination,
+}
+
+impl<T, Name> Page<T, Name>
+where
+    T: OutputType,
+    Name: PageNameType,
+{
+    /// Create a new page.
+    #[inline]
+    pub fn new(pagination: Pagination, items: Vec<T>, total_count: usize) -> Self {
+        Self {
+            _mark: PhantomData,
+            items,
+            total_count,
+            pagination,
+        }
+    }
+
+    /// Create a page from all the items.
+    pub fn from_items(pagination: Pagination, items: impl IntoIterator<Item = T>) -> Self {
+        let items = items.into_iter().collect::<Vec<_>>();
+        let total_count = items.len();
+        let items = items
+            .into_iter()
+            .skip(pagination.offset())
+            .take(pagination.limit())
+            .collect();
+        Self::new(pagination, items, total_count)
+    }
+
+    fn total_pages(&self) -> usize {
+        match self.total_count {
+            0 => 0,
+            total_count => (total_count - 1) / self.pagination.page_size + 1,
+        }
+    }
+}
+
+#[Object(internal, name_type)]
+impl<T, Name> Page<T, Name>
+where
+    T: OutputType,
+    Name: PageNameType,
+{
+    /// The items of the current page.
+    #[inline]
+    async fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The total number of items.
+    async fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    /// Information to aid in pagination.
+    async fn page_info(&self) -> PageInfo {
+        let total_pages = self.total_pages();
+        PageInfo {
+            page: self.pagination.page,
+            page_size: self.pagination.page_size,
+            total_pages,
+            has_previous_page: self.pagination.page > 1,
+            has_next_page: self.pagination.page < total_pages,
+        }
+    }
+}
+
+impl<T, Name> TypeName for Page<T, Name>
+where
+    T: OutputType,
+    Name: PageNameType,
+{
+    #[inline]
+    fn type_name() -> Cow<'static, str> {
+        Name::type_name::<T>().into()
+    }
+}
+
+/// Returns the complexity of a paginated field, which is the complexity of
+/// its subquery multiplied by the page size.
+///
+/// Use it in the `complexity` attribute of the field:
+/// `#[graphql(complexity = "pagination::complexity(page_size, child_complexity)")]`.
+pub fn complexity(page_size: impl Into<Option<i32>>, child_complexity: usize) -> usize {
+    let page_size = page_size
+        .into()
+        .map(|page_size| page_size.max(0) as usize)
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+    page_size.saturating_mul(child_complexity)
+}
+
+/// Validates the parameters and executes the query.
+///
+/// The page defaults to 1 and the page size to [`DEFAULT_PAGE_SIZE`], and it
+/// cannot exceed [`MAX_PAGE_SIZE`].
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{types::pagination::*, *};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     #[graphql(complexity = "pagination::complexity(page_size, child_complexity)")]
+///     async fn numbers(&self, page: Option<i32>, page_size: Option<i32>) -> Result<Page<i32>> {
+///         query(page, page_size, |pagination| async move {
+///             Ok::<_, Error>(Page::from_items(pagination, 0..95))
+///         })
+///         .await
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let query = "{ numbers(page: 4, pageSize: 30) { items totalCount pageInfo { totalPages hasNextPage } } }";
+/// assert_eq!(schema.execute(query).await.into_result().unwrap().data, value!({
+///     "numbers": {
+///         "items": [90, 91, 92, 93, 94],
+///         "totalCount": 95,
+///         "pageInfo": { "totalPages": 4, "hasNextPage": false },
+///     },
+/// }));
+/// # });
+/// ```
+pub async fn query<T, Name, F, R, E>(
+    page: Option<i32>,
+    page_size: Option<i32>,
+    f: F,
+) -> Result<Page<T, Name>>
+where
+    T: OutputType,
+    Name: PageNameType,
+    F: FnOnce(Pagination) -> R,
+    R: Future<Output = Result<Page<T, Name>, E>>,
+    E: Into<Error>,
+{
+    query_with_limits(page, page_size, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, f).await
+}
+
+/// Validates the parameters with the specified default and maximum page
+/// sizes and executes the query.
+pub async fn query_with_limits<T, F, R, E>(
+    page: Option<i32>,
+    page_size: Option<i32>,
+    default_page_size: usize,
+    max_page_size: usize,
+    f: F,
+) -> Result<T>
+where
+    F: FnOnce(Pagination) -> R,
+    R: Future<Output = Result<T, E>>,
+    E: Into<Error>,
+{
+    let page = match page {
+        Some(page) if page < 1 => {
+            return Err("The \"page\" parameter must be a positive number".into());
+        }
+        Some(page) => page as usize,
+        None => 1,
+    };
+
+    let page_size = match page_size {
+        Some(page_size) if page_size < 1 || page_size as usize > max_page_size => {
+            return Err(format!(
+                "The \"pageSize\" parameter must be between 1 and {}",
+                max_page_size
+            )
+            .into());
+        }
+        Some(page_size) => page_size as usize,
+        None => default_page_size,
+    };
+
+    f(Pagination { page, page_size }).await.map_err(Into::into)
+}
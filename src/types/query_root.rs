@@ -1,13 +1,16 @@
 use std::borrow::Cow;
 
+use indexmap::IndexMap;
+
 use crate::{
     model::{__Schema, __Type},
     parser::types::Field,
     registry::{self, SDLExportOptions},
     resolver_utils::{resolve_container, ContainerType},
     schema::IntrospectionMode,
+    types::relay,
     Any, Context, ContextSelectionSet, ObjectType, OutputType, Positioned, ServerError,
-    ServerResult, SimpleObject, Value,
+    ServerResult, SimpleObject, Value, ID,
 };
 
 /// Federation service
@@ -21,6 +24,58 @@ pub(crate) struct QueryRoot<T> {
     pub(crate) inner: T,
 }
 
+impl<T: ObjectType> QueryRoot<T> {
+    /// Resolves the representations of the `_entities` field.
+    ///
+    /// Representations are grouped by `__typename` so that batch entity
+    /// resolvers receive all of them at once. An entity that cannot be
+    /// resolved is `null` in the list, and its error is reported at its index.
+    async fn resolve_entities(&self, ctx: &Context<'_>, representations: &[Any]) -> Value {
+        let mut groups: IndexMap<&str, Vec<(usize, &Value)>> = IndexMap::new();
+        for (idx, Any(repr)) in representations.iter().enumerate() {
+            let type_name = match repr {
+                Value::Object(obj) => match obj.get("__typename") {
+                    Some(Value::String(type_name)) => type_name.as_str(),
+                    _ => "",
+                },
+                _ => "",
+            };
+            groups.entry(type_name).or_default().push((idx, repr));
+        }
+
+        let groups =
+            futures_util::future::join_all(groups.iter().map(|(type_name, group)| async move {
+                let results = match self.inner.find_entities(ctx, type_name, group).await {
+                    Some(results) => results,
+                    None => {
+                        futures_util::future::join_all(group.iter().map(|(idx, repr)| async move {
+                            self.inner.find_entity(&ctx.with_index(*idx), repr).await
+                        }))
+                        .await
+                    }
+                };
+                group.iter().map(|(idx, _)| *idx).zip(results)
+            }))
+            .await;
+
+        let mut values = vec![Value::Null; representations.len()];
+        for (idx, res) in groups.into_iter().flatten() {
+            match res {
+                Ok(Some(value)) => values[idx] = value,
+                Ok(None) => ctx.add_error(
+                    ctx.with_index(idx)
+                        .set_error_path(ServerError::new("Entity not found.", Some(ctx.item.pos))),
+                ),
+                Err(err) if err.path.is_empty() => {
+                    ctx.add_error(ctx.with_index(idx).set_error_path(err))
+                }
+                Err(err) => ctx.add_error(err),
+            }
+        }
+        Value::List(values)
+    }
+}
+
 #[async_trait::async_trait]
 impl<T: ObjectType> ContainerType for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>> {
@@ -71,15 +126,7 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
         if ctx.schema_env.registry.enable_federation || ctx.schema_env.registry.has_entities() {
             if ctx.item.node.name.node == "_entities" {
                 let (_, representations) = ctx.param_value::<Vec<Any>>("representations", None)?;
-                let res = futures_util::future::try_join_all(representations.iter().map(
-                    |item| async move {
-                        self.inner.find_entity(ctx, &item.0).await?.ok_or_else(|| {
-                            ServerError::new("Entity not found.", Some(ctx.item.pos))
-                        })
-                    },
-                ))
-                .await?;
-                return Ok(Some(Value::List(res)));
+                return Ok(Some(self.resolve_entities(ctx, &representations).await));
             } else if ctx.item.node.name.node == "_service" {
                 let mut ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                 ctx_obj.is_for_introspection = true;
@@ -99,6 +146,16 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
             }
         }
 
+        if !ctx.schema_env.registry.node_fetchers.is_empty() {
+            if ctx.item.node.name.node == "node" {
+                let (_, id) = ctx.param_value::<ID>("id", None)?;
+                return relay::resolve_node(ctx, None, &id).await.map(Some);
+            } else if ctx.item.node.name.node == "nodes" {
+                let (_, ids) = ctx.param_value::<Vec<ID>>("ids", None)?;
+                return Ok(Some(relay::resolve_nodes(ctx, &ids).await));
+            }
+        }
+
         self.inner.resolve_field(ctx).await
     }
 }
@@ -112,6 +169,10 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
     fn create_type_info(registry: &mut registry::Registry) -> String {
         let root = T::create_type_info(registry);
 
+        if !registry.node_fetchers.is_empty() {
+            registry.create_node_root_fields();
+        }
+
         if matches!(
             registry.introspection_mode,
             IntrospectionMode::Enabled | IntrospectionMode::IntrospectionOnly
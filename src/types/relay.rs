@@ -0,0 +1,194 @@
+//! Types for the Relay global object identification
+//!
+//! <https://relay.dev/graphql/objectidentification.htm>
+
+use std::fmt::{self, Display, Formatter};
+
+use futures_util::future::BoxFuture;
+
+use crate::{Context, Error, ObjectType, OutputType, Result, ServerResult, Value, ID};
+
+/// A global ID of an object, encoded as a standard base64 `Type:localId`
+/// string.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct GlobalId {
+    /// The GraphQL type name of the object.
+    pub type_name: String,
+
+    /// The ID of the object in its type.
+    pub id: String,
+}
+
+impl GlobalId {
+    /// Create a global ID.
+    pub fn new(type_name: impl Into<String>, id: impl Display) -> Self {
+        Self {
+            type_name: type_name.into(),
+            id: id.to_string(),
+        }
+    }
+
+    /// Decode a global ID.
+    pub fn decode(s: &str) -> Result<Self> {
+        let data = base64::decode(s)
+            .ok()
+            .and_then(|data| String::from_utf8(data).ok())
+            .ok_or_else(|| Error::new(format!("Invalid global ID \"{}\".", s)))?;
+        match data.split_once(':') {
+            Some((type_name, id)) if !type_name.is_empty() => Ok(Self::new(type_name, id)),
+            _ => Err(Error::new(format!("Invalid global ID \"{}\".", s))),
+        }
+    }
+
+    /// Encode the global ID.
+    pub fn encode(&self) -> String {
+        base64::encode(format!("{}:{}", self.type_name, self.id))
+    }
+}
+
+impl Display for GlobalId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+/// An object that implements the Relay `Node` interface.
+///
+/// Implement it for the types with the `#[graphql(node)]` attribute, the
+/// `node(id:)` and `nodes(ids:)` root fields call [`Node::fetch`] with the
+/// local ID of the object.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::{relay::*, *};
+///
+/// #[derive(SimpleObject)]
+/// #[graphql(node)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl Node for User {
+///     async fn fetch(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+///         let id: i32 = id.parse()?;
+///         Ok(Some(User { id, name: format!("user{}", id) }))
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn me(&self) -> User {
+///         User { id: 1, name: "user1".to_string() }
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// let id = GlobalId::new("User", 2).encode();
+/// let query = format!(r#"{{ me {{ id }} node(id: "{}") {{ ... on User {{ name }} }} }}"#, id);
+/// assert_eq!(schema.execute(query).await.into_result().unwrap().data, value!({
+///     "me": { "id": GlobalId::new("User", 1).encode() },
+///     "node": { "name": "user2" },
+/// }));
+/// # });
+/// ```
+#[async_trait::async_trait]
+pub trait Node: ObjectType + Sized {
+    /// Fetch the object with the local ID.
+    async fn fetch(ctx: &Context<'_>, id: &str) -> Result<Option<Self>>;
+}
+
+/// A function that fetches a node and resolves it with the selection set of
+/// the field, at the index of the list if specified.
+pub type NodeFetcher =
+    for<'a> fn(&'a Context<'a>, Option<usize>, &'a str) -> BoxFuture<'a, ServerResult<Value>>;
+
+pub(crate) fn fetch_node<'a, T: Node>(
+    ctx: &'a Context<'a>,
+    idx: Option<usize>,
+    id: &'a str,
+) -> BoxFuture<'a, ServerResult<Value>> {
+    Box::pin(async move {
+        let node = match T::fetch(ctx, id)
+            .await
+            .map_err(|err| err.into_server_error(ctx.item.pos))?
+        {
+            Some(node) => node,
+            None => return Ok(Value::Null),
+        };
+        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+        match idx {
+            Some(idx) => {
+                let ctx_idx = ctx_obj.with_index(idx);
+                OutputType::resolve(&node, &ctx_idx, ctx.item)
+                    .await
+                    .map_err(|err| ctx_idx.set_error_path(err))
+            }
+            None => OutputType::resolve(&node, &ctx_obj, ctx.item).await,
+        }
+    })
+}
+
+pub(crate) async fn resolve_node(
+    ctx: &Context<'_>,
+    idx: Option<usize>,
+    id: &str,
+) -> ServerResult<Value> {
+    let global_id = GlobalId::decode(id).map_err(|err| err.into_server_error(ctx.item.pos))?;
+    match ctx
+        .schema_env
+        .registry
+        .node_fetchers
+        .get(&global_id.type_name)
+    {
+        Some(fetcher) => fetcher(ctx, idx, &global_id.id).await,
+        None => Ok(Value::Null),
+    }
+}
+
+/// Resolves the nodes of the IDs, a failed node is `null` and its error is
+/// added at its index.
+pub(crate) async fn resolve_nodes(ctx: &Context<'_>, ids: &[ID]) -> Value {
+    let results = futures_util::future::join_all(
+        ids.iter()
+            .enumerate()
+            .map(|(idx, id)| resolve_node(ctx, Some(idx), id)),
+    )
+    .await;
+
+    Value::List(
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(idx, res)| match res {
+                Ok(value) => value,
+                Err(err) => {
+                    if err.path.is_empty() {
+                        ctx.add_error(ctx.with_index(idx).set_error_path(err));
+                    } else {
+                        ctx.add_error(err);
+                    }
+                    Value::Null
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_id() {
+        let id = GlobalId::new("User", "a:b");
+        assert_eq!(GlobalId::decode(&id.encode()).unwrap(), id);
+        assert!(GlobalId::decode("!!").is_err());
+        assert!(GlobalId::decode(&base64::encode_config("User", base64::URL_SAFE_NO_PAD)).is_err());
+    }
+}
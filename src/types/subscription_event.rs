@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+
+use crate::{
+    parser::types::Field, registry::Registry, ContextSelectionSet, OutputType, Positioned,
+    ServerResult, Value,
+};
+
+/// An event of a subscription stream with an event id.
+///
+/// When a subscription stream yields `SubscriptionEvent`, the id is attached
+/// to the [`Response`](crate::Response) as `Response::event_id`, and is sent to
+/// WebSocket clients as the `eventId` extension of the payload. Clients can
+/// resume the subscription by sending the last received id as the `lastEventId`
+/// extension of the request, see
+/// [`Context::last_event_id`](crate::context::ContextBase::last_event_id).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use futures_util::stream::{Stream, StreamExt};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct Subscription;
+///
+/// #[Subscription]
+/// impl Subscription {
+///     async fn values(&self) -> impl Stream<Item = SubscriptionEvent<i32>> {
+///         futures_util::stream::iter(0..3).map(|n| SubscriptionEvent::new(n.to_string(), n))
+///     }
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async move {
+/// let schema = Schema::new(Query, EmptyMutation, Subscription);
+/// let mut stream = schema.execute_stream("subscription { values }");
+/// let resp = stream.next().await.unwrap();
+/// assert_eq!(resp.data, value!({ "values": 0 }));
+/// assert_eq!(resp.event_id.as_deref(), Some("0"));
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionEvent<T> {
+    /// The id of the event.
+    pub id: String,
+
+    /// The value of the event.
+    pub value: T,
+}
+
+impl<T> SubscriptionEvent<T> {
+    /// Create an event with the id.
+    pub fn new(id: impl Into<String>, value: T) -> Self {
+        Self {
+            id: id.into(),
+            value,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: OutputType> OutputType for SubscriptionEvent<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::qualified_type_name()
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        T::create_type_info(registry)
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        self.value.resolve(ctx, field).await
+    }
+
+    fn event_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+}
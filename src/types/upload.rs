@@ -1,12 +1,24 @@
-use std::{borrow::Cow, io::Read};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    io::{self, Read},
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
 
+use bytes::Bytes;
 #[cfg(feature = "unblock")]
 use futures_util::io::AsyncRead;
+use futures_util::{lock::Mutex, stream::Stream};
+use multer::Multipart;
 
 use crate::{
     registry, registry::MetaTypeId, Context, InputType, InputValueError, InputValueResult, Value,
 };
 
+pub(crate) const UPLOAD_PREFIX: &str = "#__graphql_file__:";
+pub(crate) const UPLOAD_STREAM_PREFIX: &str = "#__graphql_file_stream__:";
+
 /// A file upload value.
 pub struct UploadValue {
     /// The name of the file.
@@ -92,6 +104,129 @@ impl UploadValue {
     }
 }
 
+/// The files of a multipart request that have not been read yet.
+///
+/// The files are read from the request body in the order in which they are
+/// sent, the files that are skipped can no longer be read.
+pub(crate) struct UploadStreams {
+    state: Mutex<UploadStreamsState>,
+}
+
+struct UploadStreamsState {
+    multipart: Multipart<'static>,
+    consumed: HashSet<String>,
+}
+
+impl UploadStreams {
+    pub(crate) fn new(multipart: Multipart<'static>) -> Self {
+        Self {
+            state: Mutex::new(UploadStreamsState {
+                multipart,
+                consumed: HashSet::new(),
+            }),
+        }
+    }
+
+    async fn next_file(&self, name: &str) -> io::Result<multer::Field<'static>> {
+        let mut state = self.state.lock().await;
+        loop {
+            if state.consumed.contains(name) {
+                return Err(io::Error::new(io::ErrorKind::Other, format!(
+                    "The file \"{}\" has already been read or skipped, files must be read in the order in which they are sent.",
+                    name
+                )));
+            }
+
+            let field =
+                match state.multipart.next_field().await {
+                    Ok(Some(field)) => field,
+                    Ok(None) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("The file \"{}\" does not exist.", name),
+                        ))
+                    }
+                    Err(multer::Error::LockFailure) => return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "The previous upload stream must be dropped before reading the next file.",
+                    )),
+                    Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err)),
+                };
+
+            if let Some(field_name) = field.name().map(ToString::to_string) {
+                state.consumed.insert(field_name.clone());
+                if field_name == name && field.file_name().is_some() {
+                    return Ok(field);
+                }
+            }
+        }
+    }
+}
+
+/// A stream of the content of an uploaded file.
+///
+/// For the requests received by
+/// [`receive_batch_body_streaming`](crate::http::receive_batch_body_streaming),
+/// the content is read from the request body as the stream is polled.
+pub struct UploadStream {
+    /// The name of the file.
+    pub filename: String,
+    /// The content type of the file.
+    pub content_type: Option<String>,
+    content: UploadStreamContent,
+    max_size: Option<usize>,
+    size: usize,
+}
+
+enum UploadStreamContent {
+    Buffered(Option<Bytes>),
+    #[cfg(feature = "tempfile")]
+    File(futures_util::stream::BoxStream<'static, io::Result<Vec<u8>>>),
+    Multipart(Box<multer::Field<'static>>),
+}
+
+impl Stream for UploadStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let chunk = match &mut self.content {
+            UploadStreamContent::Buffered(content) => content.take().map(Ok),
+            #[cfg(feature = "tempfile")]
+            UploadStreamContent::File(stream) => {
+                futures_util::ready!(stream.as_mut().poll_next(cx)).map(|res| res.map(Bytes::from))
+            }
+            UploadStreamContent::Multipart(field) => {
+                match futures_util::ready!(Pin::new(&mut **field).poll_next(cx)) {
+                    Some(Ok(chunk)) => Some(Ok(chunk)),
+                    Some(Err(err)) => Some(Err(io::Error::new(io::ErrorKind::Other, err))),
+                    None => None,
+                }
+            }
+        };
+
+        if let Some(Ok(chunk)) = &chunk {
+            self.size += chunk.len();
+            if let Some(max_size) = self.max_size {
+                if self.size > max_size {
+                    self.content = UploadStreamContent::Buffered(None);
+                    return Poll::Ready(Some(Err(file_too_large(max_size))));
+                }
+            }
+        }
+        Poll::Ready(chunk)
+    }
+}
+
+fn file_too_large(max_size: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "the file size must be less than or equal to {} bytes",
+            max_size
+        ),
+    )
+}
+
 /// Uploaded file
 ///
 /// **Reference:** <https://github.com/jaydenseric/graphql-multipart-request-spec>
@@ -131,12 +266,149 @@ impl UploadValue {
 /// --form 'map={ "0": ["variables.file"] }' \
 /// --form '0=@myFile.txt'
 /// ```
-pub struct Upload(usize);
+///
+/// # Streaming
+///
+/// For the requests received by
+/// [`receive_batch_body_streaming`](crate::http::receive_batch_body_streaming),
+/// the files are not written to temporary files, use [`Upload::stream`] to
+/// read the content from the request body. The files must be read in the order
+/// in which they are sent.
+///
+/// ```
+/// use async_graphql::*;
+/// use futures_util::TryStreamExt;
+///
+/// struct Mutation;
+///
+/// #[Object]
+/// impl Mutation {
+///     async fn upload(&self, ctx: &Context<'_>, file: Upload) -> Result<usize> {
+///         let mut stream = file.stream_with_limit(ctx, 10485760).await?;
+///         let mut size = 0;
+///         while let Some(chunk) = stream.try_next().await? {
+///             size += chunk.len();
+///         }
+///         Ok(size)
+///     }
+/// }
+/// ```
+pub struct Upload(UploadKind);
+
+enum UploadKind {
+    Buffered(usize),
+    Stream(String),
+}
 
 impl Upload {
     /// Get the upload value.
+    ///
+    /// Returns an error for the files of the requests received by
+    /// [`receive_batch_body_streaming`](crate::http::receive_batch_body_streaming),
+    /// use [`Upload::stream`] instead.
     pub fn value(&self, ctx: &Context<'_>) -> std::io::Result<UploadValue> {
-        ctx.query_env.uploads[self.0].try_clone()
+        match &self.0 {
+            UploadKind::Buffered(idx) => ctx.query_env.uploads[*idx].try_clone(),
+            UploadKind::Stream(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "The file is not buffered, use `Upload::stream` to read it.",
+            )),
+        }
+    }
+
+    /// Get the upload value, returns an error if the file is larger than
+    /// `max_size` bytes.
+    pub fn value_with_limit(
+        &self,
+        ctx: &Context<'_>,
+        max_size: usize,
+    ) -> std::io::Result<UploadValue> {
+        let value = self.value(ctx)?;
+        if value.size()? > max_size as u64 {
+            return Err(file_too_large(max_size));
+        }
+        Ok(value)
+    }
+
+    /// Get a stream of the content of the file.
+    ///
+    /// For the requests received by
+    /// [`receive_batch_body_streaming`](crate::http::receive_batch_body_streaming),
+    /// the content is read directly from the request body. The previous
+    /// `UploadStream` must be dropped before the next file can be read, and the
+    /// files sent before this one can no longer be read.
+    ///
+    /// With the `tempfile` feature, the other files are read from their
+    /// temporary file in chunks, on a thread pool if the `unblock` feature is
+    /// enabled.
+    pub async fn stream(&self, ctx: &Context<'_>) -> std::io::Result<UploadStream> {
+        self.create_stream(ctx, None).await
+    }
+
+    /// Get a stream of the content of the file, the stream returns an error
+    /// once more than `max_size` bytes have been read.
+    ///
+    /// See [`Upload::stream`].
+    pub async fn stream_with_limit(
+        &self,
+        ctx: &Context<'_>,
+        max_size: usize,
+    ) -> std::io::Result<UploadStream> {
+        self.create_stream(ctx, Some(max_size)).await
+    }
+
+    async fn create_stream(
+        &self,
+        ctx: &Context<'_>,
+        max_size: Option<usize>,
+    ) -> std::io::Result<UploadStream> {
+        match &self.0 {
+            UploadKind::Buffered(_) => {
+                let value = match max_size {
+                    Some(max_size) => self.value_with_limit(ctx, max_size)?,
+                    None => self.value(ctx)?,
+                };
+                let filename = value.filename.clone();
+                let content_type = value.content_type.clone();
+
+                #[cfg(all(feature = "tempfile", feature = "unblock"))]
+                let content = UploadStreamContent::File(Box::pin(crate::http::ReaderStream::new(
+                    value.into_async_read(),
+                )));
+
+                #[cfg(all(feature = "tempfile", not(feature = "unblock")))]
+                let content = UploadStreamContent::File(Box::pin(crate::http::ReaderStream::new(
+                    futures_util::io::AllowStdIo::new(value.content),
+                )));
+
+                #[cfg(not(feature = "tempfile"))]
+                let content = UploadStreamContent::Buffered(Some(value.content));
+
+                Ok(UploadStream {
+                    filename,
+                    content_type,
+                    content,
+                    max_size,
+                    size: 0,
+                })
+            }
+            UploadKind::Stream(name) => {
+                let streams = ctx.query_env.upload_streams.as_ref().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("The file \"{}\" does not exist.", name),
+                    )
+                })?;
+                let field = streams.next_file(name).await?;
+                Ok(UploadStream {
+                    filename: field.file_name().unwrap_or_default().to_string(),
+                    content_type: field.content_type().map(ToString::to_string),
+                    content: UploadStreamContent::Multipart(Box::new(field)),
+                    max_size,
+                    size: 0,
+                })
+            }
+        }
     }
 }
 
@@ -162,11 +434,15 @@ impl InputType for Upload {
     }
 
     fn parse(value: Option<Value>) -> InputValueResult<Self> {
-        const PREFIX: &str = "#__graphql_file__:";
         let value = value.unwrap_or_default();
         if let Value::String(s) = &value {
-            if let Some(filename) = s.strip_prefix(PREFIX) {
-                return Ok(Upload(filename.parse::<usize>().unwrap()));
+            if let Some(filename) = s.strip_prefix(UPLOAD_PREFIX) {
+                return Ok(Upload(UploadKind::Buffered(
+                    filename.parse::<usize>().unwrap(),
+                )));
+            }
+            if let Some(name) = s.strip_prefix(UPLOAD_STREAM_PREFIX) {
+                return Ok(Upload(UploadKind::Stream(name.to_string())));
             }
         }
         Err(InputValueError::expected_type(value))
@@ -0,0 +1,221 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_graphql::*;
+
+#[derive(Clone, Default)]
+struct Calls(Arc<AtomicUsize>);
+
+impl Calls {
+    fn inc(ctx: &Context<'_>) {
+        ctx.data_unchecked::<Calls>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+struct Post {
+    id: i32,
+}
+
+#[Object]
+impl Post {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+}
+
+struct User {
+    id: i32,
+}
+
+#[Object]
+impl User {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[graphql(batch)]
+    async fn name(parents: &[&Self], ctx: &Context<'_>, prefix: String) -> Vec<String> {
+        Calls::inc(ctx);
+        parents
+            .iter()
+            .map(|user| format!("{}{}", prefix, user.id))
+            .collect()
+    }
+
+    #[graphql(batch)]
+    async fn posts(parents: &[&Self], ctx: &Context<'_>) -> Result<Vec<Vec<Post>>> {
+        Calls::inc(ctx);
+        if parents.iter().any(|user| user.id < 0) {
+            return Err("invalid user".into());
+        }
+        Ok(parents
+            .iter()
+            .map(|user| vec![Post { id: user.id * 10 }])
+            .collect())
+    }
+
+    #[graphql(batch)]
+    async fn friends(parents: &[&Self]) -> Vec<Vec<User>> {
+        parents
+            .iter()
+            .map(|user| vec![User { id: user.id + 1 }, User { id: user.id + 2 }])
+            .collect()
+    }
+
+    #[graphql(batch)]
+    async fn broken(parents: &[&Self]) -> Vec<i32> {
+        parents.iter().skip(1).map(|user| user.id).collect()
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+struct Comment {
+    id: i32,
+}
+
+#[ComplexObject]
+impl Comment {
+    #[graphql(batch)]
+    async fn likes(parents: &[&Self], ctx: &Context<'_>) -> Vec<i32> {
+        Calls::inc(ctx);
+        parents.iter().map(|comment| comment.id * 2).collect()
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn users(&self, ids: Vec<i32>) -> Vec<User> {
+        ids.into_iter().map(|id| User { id }).collect()
+    }
+
+    async fn user(&self, id: i32) -> User {
+        User { id }
+    }
+
+    async fn comments(&self) -> Vec<Option<Comment>> {
+        vec![Some(Comment { id: 1 }), None, Some(Comment { id: 3 })]
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::new(Query, EmptyMutation, EmptySubscription)
+}
+
+#[tokio::test]
+pub async fn test_batch_resolver() {
+    let schema = schema();
+    let query = r#"{
+        users(ids: [1, 2, 3]) {
+            a: name(prefix: "a")
+            b: name(prefix: "b")
+            posts { id }
+        }
+    }"#;
+    let calls = Calls::default();
+    let resp = schema
+        .execute(Request::new(query).data(calls.clone()))
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "users": [
+                { "a": "a1", "b": "b1", "posts": [{ "id": 10 }] },
+                { "a": "a2", "b": "b2", "posts": [{ "id": 20 }] },
+                { "a": "a3", "b": "b3", "posts": [{ "id": 30 }] },
+            ]
+        })
+    );
+    // `a`, `b` and `posts` are each resolved by one call
+    assert_eq!(calls.get(), 3);
+}
+
+#[tokio::test]
+pub async fn test_batch_resolver_nested_lists() {
+    let schema = schema();
+    let query = r#"{
+        users(ids: [1, 10]) {
+            id
+            friends { id name(prefix: "f") }
+        }
+    }"#;
+    let calls = Calls::default();
+    let resp = schema
+        .execute(Request::new(query).data(calls.clone()))
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "users": [
+                { "id": 1, "friends": [{ "id": 2, "name": "f2" }, { "id": 3, "name": "f3" }] },
+                { "id": 10, "friends": [{ "id": 11, "name": "f11" }, { "id": 12, "name": "f12" }] },
+            ]
+        })
+    );
+    // One call for each `friends` list
+    assert_eq!(calls.get(), 2);
+}
+
+#[tokio::test]
+pub async fn test_batch_resolver_single_parent() {
+    let schema = schema();
+    let resp = schema
+        .execute(Request::new(r#"{ user(id: 5) { name(prefix: "x") } }"#).data(Calls::default()))
+        .await;
+    assert_eq!(resp.data, value!({ "user": { "name": "x5" } }));
+}
+
+#[tokio::test]
+pub async fn test_batch_resolver_errors() {
+    let schema = schema();
+    let resp = schema
+        .execute(Request::new("{ users(ids: [1, -1]) { posts { id } } }").data(Calls::default()))
+        .await;
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].message, "invalid user");
+    assert_eq!(
+        resp.errors[0].path,
+        vec![
+            PathSegment::Field("users".to_string()),
+            PathSegment::Index(0)
+        ]
+    );
+
+    let resp = schema
+        .execute(Request::new("{ users(ids: [1, 2]) { broken } }").data(Calls::default()))
+        .await;
+    assert_eq!(
+        resp.errors[0].message,
+        "The batch resolver returned 1 values for 2 parents."
+    );
+}
+
+#[tokio::test]
+pub async fn test_batch_resolver_complex_object() {
+    let schema = schema();
+    let calls = Calls::default();
+    let resp = schema
+        .execute(Request::new("{ comments { id likes } }").data(calls.clone()))
+        .await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "comments": [
+                { "id": 1, "likes": 2 },
+                null,
+                { "id": 3, "likes": 6 },
+            ]
+        })
+    );
+    assert_eq!(calls.get(), 1);
+}
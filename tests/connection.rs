@@ -167,3 +167,237 @@ pub async fn test_connection_nodes() {
         })
     );
 }
+
+#[cfg(feature = "signed_cursor")]
+#[tokio::test]
+pub async fn test_connection_signed_cursor() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn numbers(
+            &self,
+            ctx: &Context<'_>,
+            after: Option<String>,
+        ) -> Result<Connection<SignedCursor<usize>, i32>> {
+            connection::query_with_context(
+                ctx,
+                after,
+                None,
+                None,
+                None,
+                |after: Option<SignedCursor<usize>>, _, _, _| async move {
+                    let start = after.map(|after| *after + 1).unwrap_or(0);
+                    let mut connection = Connection::new(start > 0, true);
+                    connection
+                        .edges
+                        .extend((start..start + 2).map(|n| Edge::new(SignedCursor(n), n as i32)));
+                    Ok::<_, Error>(connection)
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(CursorKey::new("secret"))
+        .finish();
+
+    let resp = schema
+        .execute("{ numbers { edges { cursor } pageInfo { endCursor } } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data
+        .into_json()
+        .unwrap();
+    let cursor = resp["numbers"]["pageInfo"]["endCursor"].as_str().unwrap();
+    assert_eq!(resp["numbers"]["edges"][1]["cursor"], cursor);
+
+    let query = format!("{{ numbers(after: \"{}\") {{ nodes }} }}", cursor);
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "numbers": { "nodes": [2, 3] } })
+    );
+
+    let forged = format!("{}.{}", "MQ", cursor.split_once('.').unwrap().1);
+    let query = format!("{{ numbers(after: \"{}\") {{ nodes }} }}", forged);
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap_err()[0].message,
+        "The cursor has been tampered with"
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ numbers { edges { cursor } } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "The cursor key is not in the schema data"
+    );
+}
+
+#[tokio::test]
+pub async fn test_connection_from_items() {
+    let items = || (0..10).collect::<Vec<i32>>();
+    let nodes = |connection: &Connection<usize, i32>| {
+        connection
+            .edges
+            .iter()
+            .map(|edge| edge.node)
+            .collect::<Vec<_>>()
+    };
+
+    let connection = Connection::<usize, i32>::from_items(items(), None, None, Some(3), None);
+    assert_eq!(nodes(&connection), vec![0, 1, 2]);
+    assert!(!connection.has_previous_page);
+    assert!(connection.has_next_page);
+
+    let connection = Connection::<usize, i32>::from_items(items(), Some(5), None, None, Some(2));
+    assert_eq!(nodes(&connection), vec![8, 9]);
+    assert!(connection.has_previous_page);
+    assert!(!connection.has_next_page);
+
+    let connection = Connection::<usize, i32>::from_items(items(), Some(2), Some(6), None, None);
+    assert_eq!(nodes(&connection), vec![3, 4, 5]);
+
+    let connection = Connection::<usize, i32>::from_items(items(), Some(20), None, Some(3), None);
+    assert!(connection.edges.is_empty());
+    assert!(connection.has_previous_page);
+    assert!(!connection.has_next_page);
+
+    let connection = Connection::<usize, &i32>::from_items(&[1, 2, 3], None, None, None, None);
+    assert_eq!(connection.edges.len(), 3);
+}
+
+#[tokio::test]
+pub async fn test_connection_fetch() {
+    async fn fetch(
+        after: Option<i32>,
+        before: Option<i32>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Connection<i32, i32> {
+        Connection::fetch(
+            after,
+            before,
+            first,
+            last,
+            |n| *n,
+            |page| async move {
+                let numbers = page.after.map(|n| n + 1).unwrap_or(0)..page.before.unwrap_or(10);
+                let limit = page.limit.unwrap_or(usize::MAX);
+                Ok::<_, Error>(if page.backward {
+                    numbers.rev().take(limit).collect::<Vec<_>>()
+                } else {
+                    numbers.take(limit).collect()
+                })
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    let connection = fetch(Some(6), None, Some(2), None).await;
+    assert_eq!(
+        connection
+            .edges
+            .iter()
+            .map(|edge| edge.node)
+            .collect::<Vec<_>>(),
+        vec![7, 8]
+    );
+    assert!(connection.has_previous_page);
+    assert!(connection.has_next_page);
+
+    let connection = fetch(Some(6), None, Some(3), None).await;
+    assert_eq!(connection.edges.len(), 3);
+    assert!(!connection.has_next_page);
+
+    let connection = fetch(None, None, None, Some(2)).await;
+    assert_eq!(
+        connection
+            .edges
+            .iter()
+            .map(|edge| edge.node)
+            .collect::<Vec<_>>(),
+        vec![8, 9]
+    );
+    assert!(connection.has_previous_page);
+    assert!(!connection.has_next_page);
+
+    let connection = fetch(None, None, None, None).await;
+    assert_eq!(connection.edges.len(), 10);
+    assert!(!connection.has_previous_page);
+    assert!(!connection.has_next_page);
+}
+
+#[tokio::test]
+pub async fn test_connection_total_count() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct Query {
+        counts: Arc<AtomicUsize>,
+    }
+
+    #[Object]
+    impl Query {
+        async fn numbers(&self, first: Option<i32>) -> Result<Connection<usize, i32, TotalCount>> {
+            let counts = self.counts.clone();
+            query(
+                None,
+                None,
+                first,
+                None,
+                |after, before, first, last| async move {
+                    let numbers = (0..10).collect::<Vec<i32>>();
+                    Ok::<_, Error>(
+                        Connection::from_items(numbers, after, before, first, last)
+                            .with_total_count(TotalCount::new(move || async move {
+                                counts.fetch_add(1, Ordering::SeqCst);
+                                Ok::<_, Error>(10)
+                            })),
+                    )
+                },
+            )
+            .await
+        }
+    }
+
+    let counts = Arc::new(AtomicUsize::default());
+    let schema = Schema::new(
+        Query {
+            counts: counts.clone(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+    assert!(schema.sdl().contains("totalCount: Int!"));
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(first: 2) { nodes } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "numbers": { "nodes": [0, 1] } })
+    );
+    assert_eq!(counts.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(first: 2) { a: totalCount b: totalCount } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "numbers": { "a": 10, "b": 10 } })
+    );
+    assert_eq!(counts.load(Ordering::SeqCst), 1);
+}
@@ -47,6 +47,41 @@ pub async fn test_directive_skip() {
     );
 }
 
+#[tokio::test]
+pub async fn test_directive_skip_default_variable() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        pub async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"
+        query($skip: Boolean = true) {
+            value1: value @skip(if: $skip)
+            value2: value @include(if: $skip)
+        }
+    "#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({ "value2": 10 })
+    );
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(query).variables(Variables::from_value(value!({ "skip": false })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value1": 10 })
+    );
+}
+
 #[tokio::test]
 pub async fn test_directive_include() {
     struct Query;
@@ -127,3 +162,222 @@ pub async fn test_custom_directive() {
         value!({ "value": "&abc*" })
     );
 }
+
+#[tokio::test]
+pub async fn test_custom_directive_on_fragments() {
+    struct Rename {
+        prefix: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Rename {
+        async fn resolve_fragment(
+            &self,
+            _ctx: &ContextSelectionSet<'_>,
+            resolve: ResolveFut<'_>,
+        ) -> ServerResult<Option<Value>> {
+            resolve.await.map(|value| {
+                value.map(|value| match value {
+                    Value::Object(values) => Value::Object(
+                        values
+                            .into_iter()
+                            .map(|(name, value)| {
+                                (Name::new(format!("{}{}", self.prefix, name)), value)
+                            })
+                            .collect(),
+                    ),
+                    _ => value,
+                })
+            })
+        }
+    }
+
+    #[Directive(location = "fragment_spread", location = "inline_fragment")]
+    fn rename(prefix: String) -> impl CustomDirective {
+        Rename { prefix }
+    }
+
+    struct Hide;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Hide {
+        async fn resolve_fragment(
+            &self,
+            _ctx: &ContextSelectionSet<'_>,
+            _resolve: ResolveFut<'_>,
+        ) -> ServerResult<Option<Value>> {
+            Ok(None)
+        }
+    }
+
+    #[Directive(location = "inline_fragment")]
+    fn hide() -> impl CustomDirective {
+        Hide
+    }
+
+    #[derive(SimpleObject)]
+    struct Query {
+        a: i32,
+        b: i32,
+        c: i32,
+    }
+
+    let schema = Schema::build(Query { a: 1, b: 2, c: 3 }, EmptyMutation, EmptySubscription)
+        .directive(rename)
+        .directive(hide)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(
+                r#"
+                {
+                    a
+                    ... @rename(prefix: "x_") { b }
+                    ...F @rename(prefix: "y_")
+                    ... @hide { a c }
+                }
+
+                fragment F on Query { c }
+                "#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "a": 1, "x_b": 2, "y_c": 3 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_custom_directive_on_operation() {
+    struct Wrap {
+        key: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Wrap {
+        async fn resolve_operation(
+            &self,
+            _ctx: &ContextSelectionSet<'_>,
+            resolve: ResolveFut<'_>,
+        ) -> ServerResult<Option<Value>> {
+            let value = resolve.await?.unwrap_or_default();
+            let mut map = indexmap::IndexMap::new();
+            map.insert(Name::new(&self.key), value);
+            Ok(Some(Value::Object(map)))
+        }
+    }
+
+    #[Directive(location = "query", location = "mutation")]
+    fn wrap(key: String) -> impl CustomDirective {
+        Wrap { key }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn action(&self) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .directive(wrap)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(r#"query @wrap(key: "cached") { value }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "cached": { "value": 10 } })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"mutation @wrap(key: "result") { action }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "result": { "action": true } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_custom_directive_on_variable_definition() {
+    struct Upper;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Upper {
+        async fn resolve_variable(
+            &self,
+            _ctx: &ContextSelectionSet<'_>,
+            _name: &str,
+            value: Value,
+        ) -> ServerResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.to_uppercase())),
+                Value::Null => Err(ServerError::new("The variable is required.", None)),
+                value => Ok(value),
+            }
+        }
+    }
+
+    #[Directive(location = "variable_definition")]
+    fn upper() -> impl CustomDirective {
+        Upper
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, value: Option<String>) -> Option<String> {
+            value
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(upper)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(r#"query($v: String @upper) { echo(value: $v) }"#)
+                    .variables(Variables::from_value(value!({ "v": "abc" })))
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "echo": "ABC" })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"query($v: String = "def" @upper) { echo(value: $v) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "echo": "DEF" })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"query($v: String @upper) { echo(value: $v) }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError::new("The variable is required.", None)]
+    );
+}
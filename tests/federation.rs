@@ -1,6 +1,13 @@
 #![allow(unreachable_code)]
 
-use std::{collections::HashMap, convert::Infallible};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use async_graphql::{
     dataloader::{DataLoader, Loader},
@@ -239,8 +246,10 @@ pub async fn test_find_entity_with_context() {
                 }
             }
         }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(resp.data, value!({ "_entities": [null] }));
     assert_eq!(
-        schema.execute(query).await.into_result().unwrap_err(),
+        resp.errors,
         vec![ServerError {
             message: "Not found".to_string(),
             source: None,
@@ -248,12 +257,122 @@ pub async fn test_find_entity_with_context() {
                 line: 2,
                 column: 13
             }],
-            path: vec![PathSegment::Field("_entities".to_owned())],
+            path: vec![
+                PathSegment::Field("_entities".to_owned()),
+                PathSegment::Index(0)
+            ],
             extensions: None,
         }]
     );
 }
 
+#[tokio::test]
+pub async fn test_entity_batch() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+    }
+
+    struct Query {
+        batches: Arc<AtomicUsize>,
+    }
+
+    #[Object]
+    impl Query {
+        #[graphql(entity, batch)]
+        async fn find_users_by_id(
+            &self,
+            #[graphql(name = "id")] ids: Vec<ID>,
+        ) -> Vec<Option<User>> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            ids.into_iter()
+                .map(|id| {
+                    (id.as_str() != "999").then(|| User {
+                        name: format!("user{}", id.as_str()),
+                        id,
+                    })
+                })
+                .collect()
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Result<Product> {
+            if upc == "bad" {
+                return Err("Invalid upc".into());
+            }
+            Ok(Product { upc })
+        }
+    }
+
+    let batches = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::new(
+        Query {
+            batches: batches.clone(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let query = r#"{
+            _entities(representations: [
+                {__typename: "User", id: "1"},
+                {__typename: "Product", upc: "a"},
+                {__typename: "User", id: "999"},
+                {__typename: "Product", upc: "bad"},
+                {__typename: "User", id: "2"}
+            ]) {
+                __typename
+                ... on User { id name }
+                ... on Product { upc }
+            }
+        }"#;
+    let resp = schema.execute(query).await;
+    assert_eq!(batches.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        resp.data,
+        value!({
+            "_entities": [
+                {"__typename": "User", "id": "1", "name": "user1"},
+                {"__typename": "Product", "upc": "a"},
+                null,
+                null,
+                {"__typename": "User", "id": "2", "name": "user2"},
+            ]
+        })
+    );
+    assert_eq!(
+        resp.errors
+            .into_iter()
+            .map(|err| (err.message, err.path))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "Entity not found.".to_string(),
+                vec![
+                    PathSegment::Field("_entities".to_owned()),
+                    PathSegment::Index(2)
+                ]
+            ),
+            (
+                "Invalid upc".to_string(),
+                vec![
+                    PathSegment::Field("_entities".to_owned()),
+                    PathSegment::Index(3)
+                ]
+            ),
+        ]
+    );
+
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().federation())
+        .contains(r#"type User @key(fields: "id")"#));
+}
+
 #[tokio::test]
 pub async fn test_entity_union() {
     #[derive(SimpleObject)]
@@ -826,3 +945,97 @@ pub async fn test_entity_tag() {
         panic!("schema was not up-to-date. rerun")
     }
 }
+
+#[tokio::test]
+pub async fn test_fed2_directives() {
+    #[derive(SimpleObject)]
+    #[graphql(unresolvable = "id")]
+    struct User {
+        id: ID,
+    }
+
+    struct Media {
+        id: ID,
+    }
+
+    #[Object(interface_object, authenticated)]
+    impl Media {
+        async fn id(&self) -> &ID {
+            &self.id
+        }
+
+        #[graphql(requires_scopes = "read:media read:reviews", requires_scopes = "admin")]
+        async fn reviews(&self) -> Vec<String> {
+            todo!()
+        }
+
+        #[graphql(override_from = "media", override_label = "percent(25)")]
+        async fn title(&self) -> String {
+            todo!()
+        }
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        field(name = "secret", type = "&String", authenticated),
+        requires_scopes = "read:secrets"
+    )]
+    enum Secret {
+        Note(Note),
+    }
+
+    #[derive(SimpleObject)]
+    struct Note {
+        secret: String,
+        #[graphql(authenticated)]
+        author: User,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn secret(&self) -> Secret {
+            todo!()
+        }
+
+        #[graphql(entity)]
+        async fn find_media_by_id(&self, id: ID) -> Media {
+            Media { id }
+        }
+    }
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .link("https://custom.dev/custom/v1.0", ["@custom"])
+        .compose_directive("@custom")
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+
+    assert!(schema_sdl.contains(r#"type User @key(fields: "id", resolvable: false) {"#));
+    assert!(
+        schema_sdl.contains(r#"type Media @key(fields: "id") @interfaceObject @authenticated {"#)
+    );
+    assert!(schema_sdl.contains(
+        r#"reviews: [String!]! @requiresScopes(scopes: [["read:media", "read:reviews"], ["admin"]])"#
+    ));
+    assert!(schema_sdl.contains(r#"title: String! @override(from: "media", label: "percent(25)")"#));
+    assert!(
+        schema_sdl.contains(r#"interface Secret @requiresScopes(scopes: [["read:secrets"]]) {"#)
+    );
+    assert!(schema_sdl.contains("\tsecret: String! @authenticated\n"));
+    assert!(schema_sdl.contains("\tauthor: User! @authenticated\n"));
+    assert!(schema_sdl.contains(
+        r#"import: ["@key", "@tag", "@shareable", "@inaccessible", "@override", "@external", "@provides", "@requires", "@composeDirective", "@interfaceObject", "@authenticated", "@requiresScopes"]"#
+    ));
+    assert!(schema_sdl.contains(r#"url: "https://specs.apollo.dev/federation/v2.7","#));
+    assert!(schema_sdl.contains(
+        "extend schema @link(url: \"https://custom.dev/custom/v1.0\", import: [\"@custom\"])\n"
+    ));
+    assert!(schema_sdl.contains("extend schema @composeDirective(name: \"@custom\")\n"));
+
+    // the unresolvable entity is not in the `_Entity` union
+    let sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .finish()
+        .sdl();
+    assert!(sdl.contains("union _Entity = Media\n"));
+}
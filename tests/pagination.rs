@@ -0,0 +1,155 @@
+use async_graphql::{pagination::*, *};
+
+struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(complexity = "pagination::complexity(page_size, child_complexity)")]
+    async fn numbers(&self, page: Option<i32>, page_size: Option<i32>) -> Result<Page<i32>> {
+        query(page, page_size, |pagination| async move {
+            let items = (pagination.offset()..(pagination.offset() + pagination.limit()).min(45))
+                .map(|n| n as i32)
+                .collect();
+            Ok::<_, Error>(Page::new(pagination, items, 45))
+        })
+        .await
+    }
+}
+
+#[tokio::test]
+pub async fn test_page() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: 2) { items totalCount pageInfo { page pageSize totalPages hasPreviousPage hasNextPage } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "numbers": {
+                "items": (20..40).collect::<Vec<_>>(),
+                "totalCount": 45,
+                "pageInfo": {
+                    "page": 2,
+                    "pageSize": 20,
+                    "totalPages": 3,
+                    "hasPreviousPage": true,
+                    "hasNextPage": true,
+                },
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: 5, pageSize: 10) { items pageInfo { hasNextPage } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "numbers": {
+                "items": [40, 41, 42, 43, 44],
+                "pageInfo": { "hasNextPage": false },
+            }
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("type IntPage {"));
+    assert!(sdl.contains("type OffsetPageInfo {"));
+}
+
+#[tokio::test]
+pub async fn test_page_validation() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: 0) { totalCount } }")
+            .await
+            .errors[0]
+            .message,
+        "The \"page\" parameter must be a positive number"
+    );
+    assert_eq!(
+        schema
+            .execute("{ numbers(pageSize: 101) { totalCount } }")
+            .await
+            .errors[0]
+            .message,
+        "The \"pageSize\" parameter must be between 1 and 100"
+    );
+    assert_eq!(
+        schema
+            .execute("{ numbers(pageSize: 0) { totalCount } }")
+            .await
+            .errors[0]
+            .message,
+        "The \"pageSize\" parameter must be between 1 and 100"
+    );
+}
+
+#[tokio::test]
+pub async fn test_page_complexity() {
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(30)
+        .finish();
+
+    // 10 * (items + totalCount)
+    assert!(schema
+        .execute("{ numbers(pageSize: 10) { items totalCount } }")
+        .await
+        .is_ok());
+    // 20 * (items + totalCount)
+    assert_eq!(
+        schema
+            .execute("{ numbers { items totalCount } }")
+            .await
+            .errors[0]
+            .message,
+        "Query is too complex."
+    );
+}
+
+#[tokio::test]
+pub async fn test_page_name() {
+    struct MyPageName;
+
+    impl PageNameType for MyPageName {
+        fn type_name<T: OutputType>() -> String {
+            "MyPage".to_string()
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn numbers(&self, page: Option<i32>) -> Result<Page<i32, MyPageName>> {
+            query_with_limits(page, None, 2, 2, |pagination| async move {
+                Ok::<_, Error>(Page::from_items(pagination, 0..5))
+            })
+            .await
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: 3) { __typename items pageInfo { totalPages } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "numbers": {
+                "__typename": "MyPage",
+                "items": [4],
+                "pageInfo": { "totalPages": 3 },
+            }
+        })
+    );
+}
@@ -0,0 +1,212 @@
+use async_graphql::{relay::*, *};
+
+#[derive(SimpleObject)]
+#[graphql(node)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+#[async_trait::async_trait]
+impl Node for User {
+    async fn fetch(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+        let id: i32 = id.parse()?;
+        Ok(if id > 0 {
+            Some(User {
+                id,
+                name: format!("user{}", id),
+            })
+        } else {
+            None
+        })
+    }
+}
+
+struct Post {
+    slug: String,
+}
+
+#[Object(node)]
+impl Post {
+    async fn id(&self) -> &str {
+        &self.slug
+    }
+
+    async fn title(&self) -> String {
+        self.slug.to_uppercase()
+    }
+}
+
+#[async_trait::async_trait]
+impl Node for Post {
+    async fn fetch(_ctx: &Context<'_>, id: &str) -> Result<Option<Self>> {
+        Ok(Some(Post {
+            slug: id.to_string(),
+        }))
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn me(&self) -> User {
+        User {
+            id: 1,
+            name: "user1".to_string(),
+        }
+    }
+}
+
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .register_output_type::<Post>()
+        .finish()
+}
+
+#[tokio::test]
+pub async fn test_node_id() {
+    let schema = schema();
+    assert_eq!(
+        schema
+            .execute("{ me { id name } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "me": {
+                "id": GlobalId::new("User", 1).encode(),
+                "name": "user1",
+            }
+        })
+    );
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains("interface Node {"));
+    assert!(sdl.contains("type User implements Node {"));
+    assert!(sdl.contains("type Post implements Node {"));
+    assert!(sdl.contains("node(id: ID!): Node"));
+    assert!(sdl.contains("nodes(ids: [ID!]!): [Node]!"));
+}
+
+#[tokio::test]
+pub async fn test_node_field() {
+    let schema = schema();
+    let query = format!(
+        r#"{{
+            user: node(id: "{}") {{ id ... on User {{ name }} }}
+            post: node(id: "{}") {{ __typename ... on Post {{ title }} }}
+            missing: node(id: "{}") {{ id }}
+            unknown: node(id: "{}") {{ id }}
+        }}"#,
+        GlobalId::new("User", 2).encode(),
+        GlobalId::new("Post", "hello").encode(),
+        GlobalId::new("User", 0).encode(),
+        GlobalId::new("Comment", 1).encode(),
+    );
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "user": { "id": GlobalId::new("User", 2).encode(), "name": "user2" },
+            "post": { "__typename": "Post", "title": "HELLO" },
+            "missing": null,
+            "unknown": null,
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_nodes_field() {
+    let schema = schema();
+    let query = format!(
+        r#"{{ nodes(ids: ["{}", "{}", "{}"]) {{ id }} }}"#,
+        GlobalId::new("User", 1).encode(),
+        GlobalId::new("User", -1).encode(),
+        GlobalId::new("Post", "a").encode(),
+    );
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "nodes": [
+                { "id": GlobalId::new("User", 1).encode() },
+                null,
+                { "id": GlobalId::new("Post", "a").encode() },
+            ]
+        })
+    );
+
+    let resp = schema.execute(r#"{ node(id: "abc") { id } }"#).await;
+    assert_eq!(resp.errors[0].message, r#"Invalid global ID "abc"."#);
+}
+
+#[tokio::test]
+pub async fn test_nodes_field_errors() {
+    let schema = schema();
+    let query = format!(
+        r#"{{ nodes(ids: ["{}", "{}", "abc"]) {{ id }} }}"#,
+        GlobalId::new("User", 1).encode(),
+        GlobalId::new("User", "a").encode(),
+    );
+    let resp = schema.execute(query).await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "nodes": [
+                { "id": GlobalId::new("User", 1).encode() },
+                null,
+                null,
+            ]
+        })
+    );
+    assert_eq!(resp.errors.len(), 2);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![
+            PathSegment::Field("nodes".to_string()),
+            PathSegment::Index(1)
+        ]
+    );
+    assert_eq!(resp.errors[1].message, r#"Invalid global ID "abc"."#);
+    assert_eq!(
+        resp.errors[1].path,
+        vec![
+            PathSegment::Field("nodes".to_string()),
+            PathSegment::Index(2)
+        ]
+    );
+}
+
+#[test]
+pub fn test_global_id_encoding() {
+    // Standard padded base64, as used by the other Relay servers
+    assert_eq!(GlobalId::new("User", 10).encode(), "VXNlcjoxMA==");
+    assert_eq!(
+        GlobalId::decode("VXNlcjoxMA==").unwrap(),
+        GlobalId::new("User", 10)
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "The field `node` of the query type `Query` conflicts with the field generated for the `Node` interface"
+)]
+pub fn test_node_field_conflict() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> i32 {
+            1
+        }
+
+        async fn me(&self) -> User {
+            User {
+                id: 1,
+                name: "user1".to_string(),
+            }
+        }
+    }
+
+    Schema::new(Query, EmptyMutation, EmptySubscription);
+}
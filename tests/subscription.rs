@@ -420,3 +420,244 @@ pub async fn test_subscription_fieldresult() {
 
     assert!(stream.next().await.is_none());
 }
+
+#[tokio::test]
+pub async fn test_subscription_buffer_policy() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use async_graphql::extensions::{
+        DroppedEventsInfo, Extension, ExtensionContext, ExtensionFactory,
+    };
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+
+        #[graphql(buffer_policy(drop_oldest = 3))]
+        async fn drop_oldest(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+
+        #[graphql(buffer_policy(coalesce_latest))]
+        async fn coalesce_latest(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+
+        #[graphql(buffer_policy(disconnect_on_overflow = 3))]
+        async fn disconnect_on_overflow(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+
+        #[graphql(buffer_policy(unbounded))]
+        async fn unbounded(&self) -> impl Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct DroppedCounter(Arc<AtomicUsize>);
+
+    impl ExtensionFactory for DroppedCounter {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(self.clone())
+        }
+    }
+
+    impl Extension for DroppedCounter {
+        fn subscription_events_dropped(
+            &self,
+            _ctx: &ExtensionContext<'_>,
+            info: DroppedEventsInfo<'_>,
+        ) {
+            self.0.fetch_add(info.count, Ordering::SeqCst);
+        }
+    }
+
+    async fn collect(
+        schema: &Schema<Query, EmptyMutation, Subscription>,
+        query: &str,
+    ) -> Vec<Response> {
+        schema.execute_stream(query).collect::<Vec<_>>().await
+    }
+
+    let counter = DroppedCounter::default();
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .extension(counter.clone())
+        .finish();
+
+    assert_eq!(collect(&schema, "subscription { values }").await.len(), 10);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        collect(&schema, "subscription { dropOldest }")
+            .await
+            .into_iter()
+            .map(|resp| resp.data)
+            .collect::<Vec<_>>(),
+        vec![
+            value!({ "dropOldest": 7 }),
+            value!({ "dropOldest": 8 }),
+            value!({ "dropOldest": 9 }),
+        ]
+    );
+    assert_eq!(counter.0.swap(0, Ordering::SeqCst), 7);
+
+    assert_eq!(
+        collect(&schema, "subscription { coalesceLatest }")
+            .await
+            .into_iter()
+            .map(|resp| resp.data)
+            .collect::<Vec<_>>(),
+        vec![value!({ "coalesceLatest": 9 })]
+    );
+    assert_eq!(counter.0.swap(0, Ordering::SeqCst), 9);
+
+    let responses = collect(&schema, "subscription { disconnectOnOverflow }").await;
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses[0].errors,
+        vec![ServerError {
+            message: "The subscription buffer overflowed.".to_string(),
+            source: None,
+            locations: vec![Pos {
+                line: 1,
+                column: 16
+            }],
+            path: vec![PathSegment::Field("disconnectOnOverflow".to_owned())],
+            extensions: None,
+        }]
+    );
+    assert_eq!(counter.0.swap(0, Ordering::SeqCst), 4);
+
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .subscription_buffer_policy(SubscriptionBufferPolicy::CoalesceLatest)
+        .finish();
+    assert_eq!(
+        collect(&schema, "subscription { values }")
+            .await
+            .into_iter()
+            .map(|resp| resp.data)
+            .collect::<Vec<_>>(),
+        vec![value!({ "values": 9 })]
+    );
+    assert_eq!(
+        collect(&schema, "subscription { unbounded }").await.len(),
+        10
+    );
+}
+
+#[tokio::test]
+pub async fn test_subscription_event_replay() {
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        #[graphql(replay = "Self::replay_values")]
+        async fn values(&self) -> impl Stream<Item = SubscriptionEvent<i32>> {
+            futures_util::stream::iter(3..6).map(|n| SubscriptionEvent::new(n.to_string(), n))
+        }
+    }
+
+    impl Subscription {
+        async fn replay_values(
+            &self,
+            _ctx: &Context<'_>,
+            last_event_id: &str,
+        ) -> Result<Vec<SubscriptionEvent<i32>>> {
+            let last_event_id: i32 = last_event_id.parse()?;
+            Ok((last_event_id + 1..5)
+                .map(|n| SubscriptionEvent::new(n.to_string(), n))
+                .collect())
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+
+    let responses = schema
+        .execute_stream("subscription { values }")
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses
+            .iter()
+            .map(|resp| (resp.data.clone(), resp.event_id.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            (value!({ "values": 3 }), Some("3")),
+            (value!({ "values": 4 }), Some("4")),
+            (value!({ "values": 5 }), Some("5")),
+        ]
+    );
+
+    let responses = schema
+        .execute_stream(Request::new("subscription { values }").last_event_id("1"))
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        responses
+            .iter()
+            .map(|resp| (resp.data.clone(), resp.event_id.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            (value!({ "values": 2 }), Some("2")),
+            (value!({ "values": 3 }), Some("3")),
+            (value!({ "values": 4 }), Some("4")),
+            (value!({ "values": 5 }), Some("5")),
+        ]
+    );
+
+    let mut stream =
+        schema.execute_stream(Request::new("subscription { values }").last_event_id("a"));
+    let resp = stream.next().await.unwrap();
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("values".to_string())]
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+pub async fn test_subscription_event_id_in_wrappers() {
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn results(&self) -> impl Stream<Item = Result<SubscriptionEvent<i32>>> {
+            futures_util::stream::iter(0..2)
+                .map(|n| Ok(SubscriptionEvent::new(format!("r{}", n), n)))
+        }
+
+        async fn options(&self) -> impl Stream<Item = Option<SubscriptionEvent<i32>>> {
+            futures_util::stream::iter(0..2)
+                .map(|n| Some(SubscriptionEvent::new(format!("o{}", n), n)))
+        }
+
+        async fn boxed(&self) -> impl Stream<Item = Box<SubscriptionEvent<i32>>> {
+            futures_util::stream::iter(0..2)
+                .map(|n| Box::new(SubscriptionEvent::new(format!("b{}", n), n)))
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    for (field, prefix) in [("results", "r"), ("options", "o"), ("boxed", "b")] {
+        let responses = schema
+            .execute_stream(format!("subscription {{ {} }}", field))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            responses
+                .iter()
+                .map(|resp| resp.event_id.clone())
+                .collect::<Vec<_>>(),
+            vec![Some(format!("{}0", prefix)), Some(format!("{}1", prefix))]
+        );
+    }
+}
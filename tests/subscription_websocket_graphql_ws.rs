@@ -727,3 +727,88 @@ pub async fn test_ping_pong() {
             .is_err()
     );
 }
+
+#[tokio::test]
+pub async fn test_subscription_ws_transport_event_id() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = SubscriptionEvent<i32>> {
+            let start = ctx
+                .last_event_id()
+                .and_then(|id| id.parse::<i32>().ok())
+                .map(|id| id + 1)
+                .unwrap_or_default();
+            futures_util::stream::iter(start..3).map(|n| SubscriptionEvent::new(n.to_string(), n))
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "connection_init",
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "connection_ack",
+        }),
+    );
+
+    tx.send(
+        serde_json::to_string(&value!({
+            "type": "start",
+            "id": "1",
+            "payload": {
+                "query": "subscription { values }",
+                "extensions": { "lastEventId": "0" },
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    for i in 1..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": {
+                    "data": { "values": i },
+                    "extensions": { "eventId": i.to_string() },
+                },
+            }),
+        );
+    }
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap().unwrap_text())
+            .unwrap(),
+        serde_json::json!({
+            "type": "complete",
+            "id": "1",
+        }),
+    );
+}
@@ -0,0 +1,408 @@
+#![cfg(feature = "federation")]
+
+use async_graphql::{federation::Supergraph, *};
+
+#[derive(SimpleObject)]
+struct User {
+    id: ID,
+    username: String,
+}
+
+struct AccountsQuery;
+
+#[Object]
+impl AccountsQuery {
+    async fn me(&self) -> User {
+        User {
+            id: "1".into(),
+            username: "alice".to_string(),
+        }
+    }
+
+    #[graphql(entity)]
+    async fn find_user_by_id(&self, id: ID) -> Result<User> {
+        if id.as_str() == "999" {
+            return Err("User not found".into());
+        }
+        Ok(User {
+            username: format!("user{}", id.as_str()),
+            id,
+        })
+    }
+}
+
+#[derive(SimpleObject)]
+struct Review {
+    body: String,
+    author: ReviewUser,
+    product: ReviewProduct,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "User", complex)]
+struct ReviewUser {
+    id: ID,
+}
+
+#[ComplexObject]
+impl ReviewUser {
+    async fn reviews(&self) -> Vec<Review> {
+        vec![Review {
+            body: format!("review of user {}", self.id.as_str()),
+            author: ReviewUser {
+                id: self.id.clone(),
+            },
+            product: ReviewProduct {
+                upc: "1".to_string(),
+            },
+        }]
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Product", complex)]
+struct ReviewProduct {
+    upc: String,
+}
+
+#[ComplexObject]
+impl ReviewProduct {
+    async fn reviews(&self) -> Vec<Option<Review>> {
+        vec![
+            Some(Review {
+                body: "great".to_string(),
+                author: ReviewUser { id: "1".into() },
+                product: ReviewProduct {
+                    upc: self.upc.clone(),
+                },
+            }),
+            Some(Review {
+                body: "bad".to_string(),
+                author: ReviewUser { id: "999".into() },
+                product: ReviewProduct {
+                    upc: self.upc.clone(),
+                },
+            }),
+        ]
+    }
+}
+
+struct ReviewsQuery;
+
+#[Object]
+impl ReviewsQuery {
+    #[graphql(entity)]
+    async fn find_user_by_id(&self, id: ID) -> ReviewUser {
+        ReviewUser { id }
+    }
+
+    #[graphql(entity)]
+    async fn find_product_by_upc(&self, upc: String) -> ReviewProduct {
+        ReviewProduct { upc }
+    }
+}
+
+#[derive(SimpleObject)]
+struct Product {
+    upc: String,
+    name: String,
+    price: i32,
+}
+
+struct ProductsQuery;
+
+#[Object]
+impl ProductsQuery {
+    async fn top_products(&self, #[graphql(default = 5)] first: usize) -> Vec<Product> {
+        (1..=first)
+            .map(|i| Product {
+                upc: i.to_string(),
+                name: format!("product{}", i),
+                price: i as i32 * 100,
+            })
+            .collect()
+    }
+
+    #[graphql(entity)]
+    async fn find_product_by_upc(&self, upc: String) -> Product {
+        Product {
+            name: format!("product{}", upc),
+            price: 100,
+            upc,
+        }
+    }
+}
+
+struct ProductsMutation;
+
+#[Object]
+impl ProductsMutation {
+    async fn create_product(&self, name: String) -> Product {
+        Product {
+            upc: "100".to_string(),
+            name,
+            price: 0,
+        }
+    }
+}
+
+async fn supergraph() -> Supergraph {
+    Supergraph::build()
+        .subgraph(
+            "accounts",
+            Schema::new(AccountsQuery, EmptyMutation, EmptySubscription),
+        )
+        .subgraph(
+            "reviews",
+            Schema::new(ReviewsQuery, EmptyMutation, EmptySubscription),
+        )
+        .subgraph(
+            "products",
+            Schema::new(ProductsQuery, ProductsMutation, EmptySubscription),
+        )
+        .finish()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+pub async fn test_supergraph_query() {
+    let supergraph = supergraph().await;
+    let query = r#"{
+        me {
+            username
+            reviews {
+                body
+                product { name price }
+            }
+        }
+        topProducts(first: 1) {
+            __typename
+            name
+            reviews { author { id username } }
+        }
+    }"#;
+    let resp = supergraph.execute(query).await;
+    assert_eq!(
+        resp.data,
+        value!({
+            "me": {
+                "username": "alice",
+                "reviews": [{
+                    "body": "review of user 1",
+                    "product": { "name": "product1", "price": 100 },
+                }],
+            },
+            "topProducts": [{
+                "__typename": "Product",
+                "name": "product1",
+                // The non-null username of the failed entity propagates to the
+                // nullable review
+                "reviews": [
+                    { "author": { "id": "1", "username": "user1" } },
+                    null,
+                ],
+            }],
+        })
+    );
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(resp.errors[0].message, "User not found");
+    assert_eq!(
+        resp.errors[0].path,
+        vec![
+            PathSegment::Field("topProducts".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Field("reviews".to_string()),
+            PathSegment::Index(1),
+            PathSegment::Field("author".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+pub async fn test_supergraph_plan() {
+    let supergraph = supergraph().await;
+    let plan = supergraph
+        .plan("{ me { username reviews { body } } }")
+        .unwrap();
+    assert_eq!(
+        plan.to_string(),
+        r#"QueryPlan(Parallel) {
+  Fetch(service: "accounts") query { me { __typename username ... on User { __typename id } } }
+    Flatten(path: "me", type: "User") Fetch(service: "reviews") query($_representations: [_Any!]!) { _entities(representations: $_representations) { __typename ... on User { reviews { __typename body } } } }
+}"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_supergraph_variables_and_fragments() {
+    let supergraph = supergraph().await;
+    let query = r#"
+        query($first: Int!, $withPrice: Boolean!) {
+            topProducts(first: $first) { ...ProductFields }
+        }
+
+        fragment ProductFields on Product {
+            upc
+            price @include(if: $withPrice)
+            reviews { body }
+        }
+    "#;
+    let resp = supergraph
+        .execute(Request::new(query).variables(Variables::from_value(value!({
+            "first": 2,
+            "withPrice": false,
+        }))))
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({
+            "topProducts": [
+                { "upc": "1", "reviews": [{ "body": "great" }, { "body": "bad" }] },
+                { "upc": "2", "reviews": [{ "body": "great" }, { "body": "bad" }] },
+            ],
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_supergraph_invalid_fragments() {
+    let supergraph = supergraph().await;
+
+    let resp = supergraph.execute("{ ... on User { id } }").await;
+    assert_eq!(
+        resp.errors[0].message,
+        r#"Fragment cannot be spread here as objects of type "Query" can never be of type "User""#
+    );
+
+    let resp = supergraph
+        .execute("{ me { ...ProductFields } } fragment ProductFields on Product { upc }")
+        .await;
+    assert_eq!(
+        resp.errors[0].message,
+        r#"Fragment cannot be spread here as objects of type "User" can never be of type "Product""#
+    );
+
+    // A fragment on the root type is flattened
+    let resp = supergraph.execute("{ ... on Query { me { id } } }").await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({ "me": { "id": "1" } })
+    );
+}
+
+#[tokio::test]
+pub async fn test_supergraph_mutation() {
+    let supergraph = supergraph().await;
+    let resp = supergraph
+        .execute(r#"mutation { createProduct(name: "abc") { name reviews { body } } }"#)
+        .await;
+    assert_eq!(
+        resp.into_result().unwrap().data,
+        value!({
+            "createProduct": {
+                "name": "abc",
+                "reviews": [{ "body": "great" }, { "body": "bad" }],
+            },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_supergraph_sdl() {
+    let supergraph = supergraph().await;
+    let sdl = supergraph.sdl();
+    assert!(sdl.contains("type User {\n\tid: ID!\n\tusername: String!\n\treviews: [Review!]!\n}"));
+    assert!(sdl.contains("\ttopProducts(first: Int! = 5): [Product!]!\n"));
+    assert!(!sdl.contains("_entities"));
+}
+
+#[tokio::test]
+pub async fn test_supergraph_composition_errors() {
+    struct Query1;
+
+    #[Object]
+    impl Query1 {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Query2;
+
+    #[Object]
+    impl Query2 {
+        async fn value(&self) -> String {
+            "1".to_string()
+        }
+    }
+
+    let err = Supergraph::build()
+        .subgraph(
+            "a",
+            Schema::build(Query1, EmptyMutation, EmptySubscription)
+                .enable_federation()
+                .finish(),
+        )
+        .subgraph(
+            "b",
+            Schema::build(Query2, EmptyMutation, EmptySubscription)
+                .enable_federation()
+                .finish(),
+        )
+        .finish()
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.0,
+        vec![
+            r#"Field "Query.value" has type "String!" in subgraph "b", but "Int!" in another subgraph"#
+                .to_string()
+        ]
+    );
+
+    #[derive(SimpleObject)]
+    struct Item {
+        id: ID,
+        name: String,
+    }
+
+    struct Query3;
+
+    #[Object]
+    impl Query3 {
+        async fn item(&self) -> Item {
+            Item {
+                id: "1".into(),
+                name: "a".to_string(),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query3, EmptyMutation, EmptySubscription)
+        .enable_federation()
+        .finish();
+    let err = Supergraph::build()
+        .subgraph("a", schema.clone())
+        .subgraph_with_sdl(
+            "b",
+            "type Item @key(fields: \"sku\") { id: ID! }\n\
+             extend schema @link(url: \"https://specs.apollo.dev/federation/v2.0\", import: [\"@key\"])",
+            schema,
+        )
+        .finish()
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.0,
+        vec![
+            r#"@key of type "Item" in subgraph "b" references unknown field "sku""#.to_string(),
+            r#"Non-shareable field "Item.id" is resolved from multiple subgraphs: "a", "b""#
+                .to_string(),
+            r#"Type "Item" has fields that can only be resolved by subgraph "a", but it defines no resolvable @key for it"#
+                .to_string(),
+        ]
+    );
+}
@@ -0,0 +1,139 @@
+use async_graphql::*;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Role {
+    Admin,
+    User,
+}
+
+/// Restricts the access to the annotated element.
+#[TypeDirective(
+    location = "object",
+    location = "field_definition",
+    location = "argument_definition"
+)]
+fn auth(role: Role) {}
+
+#[TypeDirective(location = "field_definition", location = "input_field_definition")]
+fn unit(name: String, #[graphql(default = 1)] scale: i32) {}
+
+#[TypeDirective(name = "legacy", location = "enum_value")]
+fn legacy_value(since: Option<String>) {}
+
+#[tokio::test]
+pub async fn test_type_directive_sdl() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        #[graphql(directive = "legacy_value::apply(Some(\"1.0\".to_string()))")]
+        Crimson,
+        #[graphql(directive = "legacy_value::apply(None)")]
+        Scarlet,
+    }
+
+    #[derive(InputObject)]
+    struct Measure {
+        #[graphql(directive = "unit::apply(\"kg\".to_string(), 1000)")]
+        weight: f64,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(complex, directive = "auth::apply(Role::User)")]
+    struct Item {
+        #[graphql(directive = "unit::apply(\"kg\".to_string(), 1)")]
+        weight: f64,
+    }
+
+    #[ComplexObject]
+    impl Item {
+        #[graphql(directive = "auth::apply(Role::Admin)")]
+        async fn price(&self) -> i32 {
+            100
+        }
+    }
+
+    struct Query;
+
+    #[Object(directive = "auth::apply(Role::User)")]
+    impl Query {
+        #[graphql(
+            directive = "auth::apply(Role::User)",
+            directive = "unit::apply(\"g\".to_string(), 1)"
+        )]
+        async fn item(
+            &self,
+            #[graphql(directive = "auth::apply(Role::Admin)")] measure: Option<Measure>,
+            color: Option<Color>,
+        ) -> Item {
+            let _ = (measure, color);
+            Item { weight: 1.0 }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.sdl(),
+        r#""""
+Restricts the access to the annotated element.
+"""
+directive @auth(role: Role!) on OBJECT | FIELD_DEFINITION | ARGUMENT_DEFINITION
+
+directive @legacy(since: String) on ENUM_VALUE
+
+directive @unit(name: String!, scale: Int! = 1) on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+
+enum Color {
+	RED
+	CRIMSON @legacy(since: "1.0")
+	SCARLET @legacy
+}
+
+
+
+
+type Item @auth(role: USER) {
+	weight: Float! @unit(name: "kg", scale: 1)
+	price: Int! @auth(role: ADMIN)
+}
+
+input Measure {
+	weight: Float! @unit(name: "kg", scale: 1000)
+}
+
+type Query @auth(role: USER) {
+	item(measure: Measure @auth(role: ADMIN), color: Color): Item! @auth(role: USER) @unit(name: "g", scale: 1)
+}
+
+enum Role {
+	ADMIN
+	USER
+}
+
+
+schema {
+	query: Query
+}
+"#
+    );
+}
+
+#[tokio::test]
+pub async fn test_type_directive_registry() {
+    #[derive(SimpleObject)]
+    struct Query {
+        #[graphql(directive = "unit::apply(\"kg\".to_string(), 1)")]
+        weight: f64,
+    }
+
+    let schema = Schema::new(Query { weight: 1.0 }, EmptyMutation, EmptySubscription);
+    let field = schema.registry().types["Query"]
+        .field_by_name("weight")
+        .unwrap();
+    assert_eq!(field.directive_invocations.len(), 1);
+    let invocation = &field.directive_invocations[0];
+    assert_eq!(invocation.name, "unit");
+    assert_eq!(invocation.args["name"], value!("kg"));
+    assert_eq!(invocation.args["scale"], value!(1));
+    assert!(schema.registry().directives.contains_key("unit"));
+}
@@ -0,0 +1,285 @@
+use async_graphql::{http::MultipartOptions, *};
+use futures_util::{io::Cursor, TryStreamExt};
+
+const BOUNDARY: &str = "boundary";
+
+fn multipart_body(fields: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
+    let mut body = String::new();
+    for (name, filename, content) in fields {
+        body.push_str(&format!("--{}\r\n", BOUNDARY));
+        match filename {
+            Some(filename) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: text/plain\r\n\r\n",
+                name, filename
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                name
+            )),
+        }
+        body.push_str(content);
+        body.push_str("\r\n");
+    }
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+    body.into_bytes()
+}
+
+fn content_type() -> Option<String> {
+    Some(format!("multipart/form-data; boundary={}", BOUNDARY))
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn read(&self, ctx: &Context<'_>, file: Upload) -> Result<String> {
+        let stream = file.stream(ctx).await?;
+        let filename = stream.filename.clone();
+        let chunks: Vec<_> = stream.try_collect().await?;
+        Ok(format!(
+            "{}:{}",
+            filename,
+            String::from_utf8(chunks.concat()).unwrap()
+        ))
+    }
+
+    async fn read_limited(&self, ctx: &Context<'_>, file: Upload) -> Result<String> {
+        let chunks: Vec<_> = file.stream_with_limit(ctx, 4).await?.try_collect().await?;
+        Ok(String::from_utf8(chunks.concat()).unwrap())
+    }
+
+    async fn size_limited(&self, ctx: &Context<'_>, file: Upload) -> Result<u64> {
+        Ok(file.value_with_limit(ctx, 4)?.size()?)
+    }
+}
+
+#[tokio::test]
+pub async fn test_upload_field_order() {
+    let body = multipart_body(&[
+        ("map", None, r#"{ "0": ["variables.file"] }"#),
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($file: Upload!) { read(file: $file) }", "variables": { "file": null } }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+    ]);
+    assert!(matches!(
+        http::receive_body(
+            content_type(),
+            Cursor::new(body),
+            MultipartOptions::default()
+        )
+        .await,
+        Err(ParseRequestError::MissingOperatorsPart)
+    ));
+
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($file: Upload!) { read(file: $file) }", "variables": { "file": null } }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("map", None, r#"{ "0": ["variables.file"] }"#),
+    ]);
+    assert!(matches!(
+        http::receive_body_streaming(
+            content_type(),
+            Cursor::new(body),
+            MultipartOptions::default()
+        )
+        .await,
+        Err(ParseRequestError::MissingMapPart)
+    ));
+}
+
+#[tokio::test]
+pub async fn test_upload_stream() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($a: Upload!, $b: Upload!) { a: read(file: $a) b: read(file: $b) }", "variables": { "a": null, "b": null } }"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{ "0": ["variables.a"], "1": ["variables.b"] }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("1", Some("b.txt"), "defg"),
+    ]);
+
+    let request = http::receive_body(
+        content_type(),
+        Cursor::new(body.clone()),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "a": "a.txt:abc", "b": "b.txt:defg" })
+    );
+
+    let request = http::receive_body_streaming(
+        content_type(),
+        Cursor::new(body),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        value!({ "a": "a.txt:abc", "b": "b.txt:defg" })
+    );
+}
+
+#[tokio::test]
+pub async fn test_upload_stream_order() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($a: Upload!, $b: Upload!) { b: read(file: $b) a: read(file: $a) }", "variables": { "a": null, "b": null } }"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{ "0": ["variables.a"], "1": ["variables.b"] }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("1", Some("b.txt"), "defg"),
+    ]);
+
+    let request = http::receive_body_streaming(
+        content_type(),
+        Cursor::new(body),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    let resp = schema.execute(request).await;
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("a".to_string())]
+    );
+    assert_eq!(
+        resp.errors[0].message,
+        "The file \"0\" has already been read or skipped, files must be read in the order in which they are sent."
+    );
+}
+
+#[tokio::test]
+pub async fn test_upload_max_file_size() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($a: Upload!, $b: Upload!) { a: readLimited(file: $a) b: readLimited(file: $b) }", "variables": { "a": null, "b": null } }"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{ "0": ["variables.a"], "1": ["variables.b"] }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("1", Some("b.txt"), "defgh"),
+    ]);
+
+    let request = http::receive_body_streaming(
+        content_type(),
+        Cursor::new(body),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    let resp = schema.execute(request).await;
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("b".to_string())]
+    );
+    assert_eq!(
+        resp.errors[0].message,
+        "the file size must be less than or equal to 4 bytes"
+    );
+
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($a: Upload!, $b: Upload!) { a: sizeLimited(file: $a) b: sizeLimited(file: $b) }", "variables": { "a": null, "b": null } }"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{ "0": ["variables.a"], "1": ["variables.b"] }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("1", Some("b.txt"), "defgh"),
+    ]);
+    let request = http::receive_body(
+        content_type(),
+        Cursor::new(body),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    let resp = schema.execute(request).await;
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("b".to_string())]
+    );
+    assert_eq!(
+        resp.errors[0].message,
+        "the file size must be less than or equal to 4 bytes"
+    );
+    // the buffered files are streamed with the same limit
+    let body = multipart_body(&[
+        (
+            "operations",
+            None,
+            r#"{ "query": "mutation ($a: Upload!, $b: Upload!) { a: readLimited(file: $a) b: readLimited(file: $b) }", "variables": { "a": null, "b": null } }"#,
+        ),
+        (
+            "map",
+            None,
+            r#"{ "0": ["variables.a"], "1": ["variables.b"] }"#,
+        ),
+        ("0", Some("a.txt"), "abc"),
+        ("1", Some("b.txt"), "defgh"),
+    ]);
+    let request = http::receive_body(
+        content_type(),
+        Cursor::new(body),
+        MultipartOptions::default(),
+    )
+    .await
+    .unwrap();
+    let resp = schema.execute(request).await;
+    assert_eq!(resp.errors.len(), 1);
+    assert_eq!(
+        resp.errors[0].path,
+        vec![PathSegment::Field("b".to_string())]
+    );
+    assert_eq!(
+        resp.errors[0].message,
+        "the file size must be less than or equal to 4 bytes"
+    );
+}